@@ -27,11 +27,12 @@ use crate::app_in_memory_config::{
 use crate::auto_launcher::AutoLauncher;
 use crate::binaries::{Binaries, BinaryResolver};
 use crate::configs::config_core::{AirdropTokens, ConfigCore, ConfigCoreContent};
-use crate::configs::config_mining::{ConfigMining, ConfigMiningContent};
+use crate::configs::config_mining::{ConfigMining, ConfigMiningContent, GpuDeviceSettings};
 use crate::configs::config_pools::{ConfigPools, ConfigPoolsContent};
 use crate::configs::config_ui::{ConfigUI, ConfigUIContent, DisplayMode};
 use crate::configs::config_wallet::{ConfigWallet, ConfigWalletContent, WalletId};
 use crate::configs::trait_config::ConfigImpl;
+use crate::cpu_miner_backend::CpuMinerBackend;
 use crate::events::ConnectionStatusPayload;
 use crate::events_emitter::EventsEmitter;
 use crate::events_manager::EventsManager;
@@ -42,20 +43,45 @@ use crate::gpu_miner::EngineType;
 use crate::gpu_miner_adapter::{GpuMinerStatus, GpuNodeSource};
 use crate::gpu_status_file::GpuStatus;
 use crate::internal_wallet::{mnemonic_to_tari_cipher_seed, InternalWallet, PaperWalletConfig};
-use crate::node::node_adapter::BaseNodeStatus;
-use crate::node::node_manager::NodeType;
-use crate::p2pool::models::{Connections, P2poolStats};
+use crate::merge_mining_stats::MergeMiningStats;
+use crate::mining_mode_advisor::{get_recommendation, MiningModeRecommendation};
+use crate::monero_node_prober::{
+    normalize_monero_node_url, probe_monero_node, probe_monero_nodes, MoneroNodeStatus,
+};
+use crate::node::local_node_adapter::{validate_node_config_override, validate_seed_peer};
+use crate::node::node_adapter::{
+    BaseNodeStatus, BlockHeaderInfo, DifficultySample, MempoolStats, PeerDetails, PowAlgorithm,
+};
+use crate::node::node_database_maintenance::{
+    NodeDatabaseBackupInfo, NodeDatabaseMaintenance, NodeDatabaseRepairOutcome, NodeDatabaseReport,
+};
+use crate::node::node_manager::{peer_host, NodeStorageMode, NodeType};
+use crate::node::node_snapshot::{NodeSnapshot, NodeSnapshotSource};
+use crate::node::remote_node_adapter::parse_grpc_address;
+use crate::node::remote_node_prober::RemoteNodeEndpointStatus;
+use crate::p2pool::diagnostics::P2poolDiagnostics;
+use crate::p2pool::models::{Connections, P2poolStats, P2poolStatsSnapshot};
 use crate::pin::PinManager;
+use crate::progress_trackers::progress_plans::{ProgressNodeMaintenancePlan, ProgressPlans};
 use crate::setup::setup_manager::{SetupManager, SetupPhase};
+use crate::stratum_hub::stratum_hub_manager::StratumHubStatus;
 use crate::tapplets::interface::ActiveTapplet;
 use crate::tapplets::tapplet_server::start_tapplet;
 use crate::tasks_tracker::TasksTrackers;
-use crate::tor_adapter::TorConfig;
+use crate::tor_adapter::{BandwidthLimits, TorConfig};
 use crate::utils::address_utils::verify_send;
 use crate::utils::app_flow_utils::FrontendReadyChannel;
+use crate::utils::rand_utils;
+use crate::wallet::payments_scheduler::{
+    ScheduledPayment, ScheduledPaymentKind, ScheduledPaymentRecord,
+};
+use crate::wallet::wallet_backup::WalletBackup;
 use crate::wallet::wallet_manager::WalletManagerError;
-use crate::wallet::wallet_types::{TariAddressVariants, TransactionInfo};
+use crate::wallet::wallet_types::{
+    SelfSweepPreview, TariAddressVariants, TransactionInfo, UnspentOutput,
+};
 use crate::websocket_manager::WebsocketManagerStatusMessage;
+use crate::xmrig_adapter::XmrigTuning;
 use crate::{airdrop, PoolStatus, UniverseAppState};
 
 use base64::prelude::*;
@@ -63,8 +89,11 @@ use log::{debug, error, info, warn};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fs::{read_dir, remove_dir_all, remove_file, File};
+use std::net::IpAddr;
+use std::path::Path;
 use std::str::FromStr;
 use std::sync::atomic::Ordering;
 use std::thread::sleep;
@@ -76,6 +105,7 @@ use tari_core::transactions::tari_amount::{MicroMinotari, Minotari};
 use tari_key_manager::mnemonic::{Mnemonic, MnemonicLanguage};
 use tari_key_manager::mnemonic_wordlists::MNEMONIC_ENGLISH_WORDS;
 use tari_utilities::encoding::MBase58;
+use tari_utilities::hex::Hex;
 use tari_utilities::SafePassword;
 use tauri::ipc::InvokeError;
 use tauri::{Manager, PhysicalPosition, PhysicalSize};
@@ -85,6 +115,7 @@ use urlencoding::encode;
 const MAX_ACCEPTABLE_COMMAND_TIME: Duration = Duration::from_secs(1);
 const LOG_TARGET: &str = "tari::universe::commands";
 const LOG_TARGET_WEB: &str = "tari::universe::web";
+const MAX_CHAIN_INSPECTION_BLOCKS: u64 = 1000;
 
 pub enum CpuMinerConnection {
     BuiltInProxy,
@@ -152,6 +183,51 @@ pub struct SignWsDataResponse {
     pub pub_key: String,
 }
 
+#[derive(Debug, Serialize, Clone)]
+pub struct BlockRewardInfo {
+    pub height: u64,
+    pub timestamp: u64,
+    pub pow_algo: PowAlgorithm,
+    pub reward: MicroMinotari,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct NetworkUsage {
+    pub tor_bytes_read: u64,
+    pub tor_bytes_written: u64,
+    /// System wide totals of every network interface, not just Universe: the OS doesn't report
+    /// network traffic per process, only Tor counts its own
+    pub system_wide_bytes_received: u64,
+    pub system_wide_bytes_transmitted: u64,
+    pub bandwidth_limits: BandwidthLimits,
+    pub metered_connection: bool,
+    pub is_local_sync_paused: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct NodeConfigEntry {
+    pub key: String,
+    pub value: String,
+    pub is_user_override: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct NodeStorageModeStatus {
+    pub configured_mode: NodeStorageMode,
+    pub database_mode: Option<NodeStorageMode>,
+    pub is_database_compatible: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TariWalletSummary {
+    pub id: String,
+    pub name: String,
+    pub tari_address_base58: String,
+    pub is_selected: bool,
+    pub receives_mining_rewards: bool,
+    pub is_locked: bool,
+}
+
 #[tauri::command]
 pub async fn close_splashscreen(app: tauri::AppHandle) {
     let close_max_retries: u32 = 10; // Maximum number of retries
@@ -475,6 +551,59 @@ pub async fn get_p2pool_stats(
     Ok(p2pool_stats)
 }
 
+#[tauri::command]
+pub async fn get_p2pool_miner_stats(
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<Option<P2poolStatsSnapshot>, String> {
+    let timer = Instant::now();
+    // The last stats broadcast outlives the process, don't report them as current
+    if !state.p2pool_manager.is_running().await {
+        return Err("p2pool is not running".to_string());
+    }
+    let p2pool_stats = state.p2pool_latest_status.borrow().clone();
+    let miner_stats = match p2pool_stats {
+        Some(p2pool_stats) => {
+            // Shares are credited to the address the miners are paid to
+            let mining_address = InternalWallet::mining_rewards_address().await.to_base58();
+            Some(P2poolStatsSnapshot::from_stats(
+                &p2pool_stats,
+                &mining_address,
+            ))
+        }
+        None => None,
+    };
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "get_p2pool_miner_stats took too long: {:?}", timer.elapsed());
+    }
+    Ok(miner_stats)
+}
+
+#[tauri::command]
+pub async fn get_p2pool_stats_history(
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<Vec<P2poolStatsSnapshot>, String> {
+    Ok(state.p2pool_manager.get_stats_history().await)
+}
+
+#[tauri::command]
+pub async fn get_p2pool_diagnostics(
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<P2poolDiagnostics, InvokeError> {
+    let timer = Instant::now();
+    let p2pool_stats = state.p2pool_latest_status.borrow().clone();
+    let diagnostics = state
+        .p2pool_manager
+        .get_diagnostics(p2pool_stats)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "get_p2pool_diagnostics took too long: {:?}", timer.elapsed());
+    }
+    Ok(diagnostics)
+}
+
 #[tauri::command]
 pub async fn get_p2pool_connections(
     state: tauri::State<'_, UniverseAppState>,
@@ -689,6 +818,18 @@ pub async fn confirm_exchange_address(
     Ok(())
 }
 
+#[tauri::command]
+pub async fn get_stratum_hub_status(
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<StratumHubStatus, InvokeError> {
+    let timer = Instant::now();
+    let status = state.stratum_hub_manager.get_status().await;
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "get_stratum_hub_status took too long: {:?}", timer.elapsed());
+    }
+    Ok(status)
+}
+
 #[tauri::command]
 pub async fn get_tor_config(
     _window: tauri::Window,
@@ -791,6 +932,7 @@ pub async fn forgot_pin(
 #[tauri::command]
 pub async fn import_seed_words(
     seed_words: Vec<String>,
+    wallet_name: Option<String>,
     state: tauri::State<'_, UniverseAppState>,
     app_handle: tauri::AppHandle,
 ) -> Result<(), InvokeError> {
@@ -799,8 +941,9 @@ pub async fn import_seed_words(
     SetupManager::get_instance()
         .shutdown_phases(vec![SetupPhase::Wallet, SetupPhase::Mining])
         .await;
+    let previous_wallet_key = InternalWallet::wallet_data_key().await;
 
-    match InternalWallet::import_tari_seed_words(seed_words, &app_handle).await {
+    match InternalWallet::import_tari_seed_words(seed_words, wallet_name, &app_handle).await {
         Ok((wallet_id, _seed_binary)) => {
             ConfigCore::update_field(
                 ConfigCoreContent::set_exchange_id,
@@ -817,23 +960,309 @@ pub async fn import_seed_words(
         }
     }
 
-    let base_path = app_handle
-        .path()
-        .app_local_data_dir()
-        .map_err(|_| "Could not find wallet data dir".to_string())?;
-    state
-        .wallet_manager
-        .clean_data_folder(&base_path)
+    resume_wallet_phases(&state, app_handle, previous_wallet_key, true).await?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "import_seed_words took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn import_watch_only_wallet(
+    view_private_key: String,
+    spend_public_key: String,
+    wallet_birthday: Option<u16>,
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, InvokeError> {
+    let timer = Instant::now();
+    let watch_only_wallet_details = InternalWallet::get_watch_only_wallet_details(
+        &view_private_key,
+        &spend_public_key,
+        wallet_birthday.unwrap_or(0),
+    )
+    .map_err(InvokeError::from_anyhow)?;
+    let tari_address = watch_only_wallet_details.tari_address.to_base58();
+    info!(target: LOG_TARGET, "[import_watch_only_wallet] called for address: {tari_address}");
+
+    SetupManager::get_instance()
+        .shutdown_phases(vec![SetupPhase::Wallet, SetupPhase::Mining])
+        .await;
+    let previous_wallet_key = InternalWallet::wallet_data_key().await;
+
+    let result =
+        InternalWallet::initialize_watch_only(&app_handle, Some(watch_only_wallet_details)).await;
+    if result.is_ok() {
+        ConfigCore::update_field(
+            ConfigCoreContent::set_exchange_id,
+            DEFAULT_EXCHANGE_ID.to_string(),
+        )
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(InvokeError::from_anyhow)?;
+        EventsEmitter::emit_exchange_id_changed(DEFAULT_EXCHANGE_ID.to_string()).await;
+    }
+    resume_wallet_phases(&state, app_handle, previous_wallet_key, result.is_ok()).await?;
+    result.map_err(InvokeError::from_anyhow)?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "import_watch_only_wallet took too long: {:?}", timer.elapsed());
+    }
+    Ok(tari_address)
+}
+
+#[tauri::command]
+pub async fn get_tari_wallets() -> Result<Vec<TariWalletSummary>, InvokeError> {
+    let wallet_config = ConfigWallet::content().await;
+    let selected_wallet_id = wallet_config.tari_wallets().first().cloned();
+    let rewards_wallet_id = wallet_config
+        .mining_rewards_wallet_id()
+        .clone()
+        .or(selected_wallet_id.clone());
+
+    Ok(InternalWallet::list_tari_wallets()
+        .await
+        .into_iter()
+        .map(|record| TariWalletSummary {
+            id: record.details.id.as_str().to_string(),
+            name: record.name,
+            tari_address_base58: record.details.tari_address.to_base58(),
+            is_selected: selected_wallet_id.as_ref() == Some(&record.details.id),
+            receives_mining_rewards: rewards_wallet_id.as_ref() == Some(&record.details.id),
+            is_locked: record.is_locked,
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn create_tari_wallet(
+    wallet_name: String,
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[create_tari_wallet] called with name: {wallet_name:?}");
 
     SetupManager::get_instance()
-        .resume_phases(app_handle, vec![SetupPhase::Wallet, SetupPhase::Mining])
+        .shutdown_phases(vec![SetupPhase::Wallet, SetupPhase::Mining])
         .await;
+    let previous_wallet_key = InternalWallet::wallet_data_key().await;
+
+    let result = InternalWallet::create_tari_wallet(&app_handle, wallet_name).await;
+    resume_wallet_phases(&state, app_handle, previous_wallet_key, result.is_ok()).await?;
+    let wallet_id = result.map_err(InvokeError::from_anyhow)?;
 
     if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
-        warn!(target: LOG_TARGET, "import_seed_words took too long: {:?}", timer.elapsed());
+        warn!(target: LOG_TARGET, "create_tari_wallet took too long: {:?}", timer.elapsed());
+    }
+    Ok(wallet_id.as_str().to_string())
+}
+
+#[tauri::command]
+pub async fn switch_tari_wallet(
+    wallet_id: String,
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[switch_tari_wallet] called with wallet_id: {wallet_id:?}");
+    if ConfigWallet::content().await.tari_wallets().first()
+        == Some(&WalletId::new(wallet_id.clone()))
+    {
+        return Ok(());
+    }
+
+    SetupManager::get_instance()
+        .shutdown_phases(vec![SetupPhase::Wallet, SetupPhase::Mining])
+        .await;
+    let previous_wallet_key = InternalWallet::wallet_data_key().await;
+
+    let result = InternalWallet::switch_tari_wallet(&app_handle, WalletId::new(wallet_id)).await;
+    resume_wallet_phases(&state, app_handle, previous_wallet_key, result.is_ok()).await?;
+    result.map_err(InvokeError::from_anyhow)?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "switch_tari_wallet took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn rename_tari_wallet(wallet_id: String, wallet_name: String) -> Result<(), InvokeError> {
+    if wallet_name.trim().is_empty() {
+        return Err(InvokeError::from("Wallet name can't be empty".to_string()));
+    }
+    ConfigWallet::update_field(
+        ConfigWalletContent::set_tari_wallet_name,
+        (WalletId::new(wallet_id), wallet_name.trim().to_string()),
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn remove_tari_wallet(
+    wallet_id: String,
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    info!(target: LOG_TARGET, "[remove_tari_wallet] called with wallet_id: {wallet_id:?}");
+    let wallet_id = WalletId::new(wallet_id);
+    let wallet_config = ConfigWallet::content().await;
+    let is_rewards_wallet = wallet_config.mining_rewards_wallet_id().as_ref() == Some(&wallet_id);
+    let wallet_key = wallet_config
+        .tari_wallet_records()
+        .iter()
+        .find(|r| r.details.id == wallet_id)
+        .map(|r| r.details.spend_public_key_hex.clone());
+
+    InternalWallet::delete_tari_wallet(&app_handle, wallet_id)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    if let Some(wallet_key) = wallet_key {
+        let base_path = app_handle
+            .path()
+            .app_local_data_dir()
+            .map_err(|_| "Could not find wallet data dir".to_string())?;
+        if let Err(e) = state
+            .wallet_manager
+            .remove_parked_data_folder(&base_path, &wallet_key)
+            .await
+        {
+            warn!(target: LOG_TARGET, "Failed to remove data folder of removed wallet: {e}");
+        }
+    }
+
+    if is_rewards_wallet {
+        // Rewards fall back to the selected wallet
+        SetupManager::get_instance()
+            .add_phases_to_restart_queue(vec![SetupPhase::Mining])
+            .await;
+        SetupManager::get_instance()
+            .restart_phases_from_queue(app_handle)
+            .await;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_mining_rewards_wallet(
+    wallet_id: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    info!(target: LOG_TARGET, "[set_mining_rewards_wallet] called with wallet_id: {wallet_id:?}");
+    let wallet_id = wallet_id.map(WalletId::new);
+    if let Some(ref id) = wallet_id {
+        if !ConfigWallet::content().await.tari_wallets().contains(id) {
+            return Err(InvokeError::from(format!(
+                "Tari wallet {} not found",
+                id.as_str()
+            )));
+        }
+    }
+
+    ConfigWallet::update_field_requires_restart(
+        ConfigWalletContent::set_mining_rewards_wallet_id,
+        wallet_id,
+        vec![SetupPhase::Mining],
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+    SetupManager::get_instance()
+        .restart_phases_from_queue(app_handle)
+        .await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn export_wallet_backup(
+    path: String,
+    passphrase: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[export_wallet_backup] called with path: {path:?}");
+    WalletBackup::export(
+        &app_handle,
+        Path::new(&path),
+        SafePassword::from(passphrase),
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "export_wallet_backup took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn import_wallet_backup(
+    path: String,
+    passphrase: String,
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[import_wallet_backup] called with path: {path:?}");
+    // Decrypt before stopping anything so a wrong passphrase leaves the app untouched
+    let payload = WalletBackup::read(Path::new(&path), SafePassword::from(passphrase))
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    SetupManager::get_instance()
+        .shutdown_phases(vec![SetupPhase::Wallet, SetupPhase::Mining])
+        .await;
+    let previous_wallet_key = InternalWallet::wallet_data_key().await;
+
+    let result = WalletBackup::restore(&app_handle, payload).await;
+    if result.is_ok() {
+        ConfigCore::update_field(
+            ConfigCoreContent::set_exchange_id,
+            DEFAULT_EXCHANGE_ID.to_string(),
+        )
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+        EventsEmitter::emit_exchange_id_changed(DEFAULT_EXCHANGE_ID.to_string()).await;
+    }
+    resume_wallet_phases(&state, app_handle, previous_wallet_key, result.is_ok()).await?;
+    result.map_err(InvokeError::from_anyhow)?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "import_wallet_backup took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+// The wallet database is bound to the wallet keys, so it is swapped whenever the selected wallet changes
+async fn resume_wallet_phases(
+    state: &tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+    previous_wallet_key: Option<String>,
+    wallet_changed: bool,
+) -> Result<(), InvokeError> {
+    if wallet_changed {
+        let base_path = app_handle
+            .path()
+            .app_local_data_dir()
+            .map_err(|_| "Could not find wallet data dir".to_string())?;
+        let next_wallet_key = InternalWallet::wallet_data_key().await;
+        state
+            .wallet_manager
+            .swap_data_folder(
+                &base_path,
+                previous_wallet_key.as_deref(),
+                next_wallet_key.as_deref(),
+            )
+            .await
+            .map_err(|e| e.to_string())?;
     }
+
+    SetupManager::get_instance()
+        .resume_phases(app_handle, vec![SetupPhase::Wallet, SetupPhase::Mining])
+        .await;
     Ok(())
 }
 
@@ -971,6 +1400,7 @@ pub async fn reset_settings(
             .map_err(|e| e.to_string())?;
     } else {
         folder_block_list.push("wallet");
+        folder_block_list.push("wallet_data");
         files_block_list.push("credentials_backup.bin");
     }
     // handle App Config reset individually
@@ -1218,6 +1648,38 @@ pub async fn toggle_device_exclusion(device_index: u32, excluded: bool) -> Resul
     Ok(())
 }
 
+#[tauri::command]
+pub async fn set_gpu_device_settings(
+    device_index: u32,
+    intensity: Option<u32>,
+    grid_size: Option<u32>,
+) -> Result<(), InvokeError> {
+    info!(target: LOG_TARGET, "[set_gpu_device_settings] called for device {device_index} with intensity: {intensity:?}, grid_size: {grid_size:?}");
+    if intensity.is_some_and(|intensity| !(1..=100).contains(&intensity)) {
+        return Err(InvokeError::from(
+            "Intensity must be between 1 and 100".to_string(),
+        ));
+    }
+    if grid_size == Some(0) {
+        return Err(InvokeError::from(
+            "Grid size must be greater than zero".to_string(),
+        ));
+    }
+    // Applied the next time the GPU miner starts
+    ConfigMining::update_field(
+        ConfigMiningContent::set_gpu_device_mining_parameters,
+        GpuDeviceSettings::new(device_index, intensity, grid_size),
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+
+    EventsEmitter::emit_update_gpu_devices_settings(
+        ConfigMining::content().await.gpu_devices_settings().clone(),
+    )
+    .await;
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn set_gpu_mining_enabled(enabled: bool) -> Result<(), InvokeError> {
     let timer = Instant::now();
@@ -1320,28 +1782,127 @@ pub async fn set_monero_address(
 }
 
 #[tauri::command]
-pub async fn set_monerod_config(
-    use_monero_fail: bool,
-    monero_nodes: Vec<String>,
-    app_handle: tauri::AppHandle,
-) -> Result<(), InvokeError> {
+pub async fn get_merge_mining_stats(
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<MergeMiningStats, InvokeError> {
     let timer = Instant::now();
-    info!(target: LOG_TARGET, "[set_monerod_config] called with use_monero_fail: {use_monero_fail:?}, monero_nodes: {monero_nodes:?}");
-    ConfigCore::update_field_requires_restart(
-        ConfigCoreContent::set_mmproxy_monero_nodes,
-        monero_nodes.clone(),
-        vec![SetupPhase::Mining],
-    )
-    .await
-    .map_err(InvokeError::from_anyhow)?;
+    let mut stats = state.mm_proxy_manager.get_merge_mining_stats().await;
 
-    ConfigCore::update_field_requires_restart(
-        ConfigCoreContent::set_mmproxy_use_monero_failover,
-        use_monero_fail,
-        vec![SetupPhase::Mining],
-    )
-    .await
-    .map_err(InvokeError::from_anyhow)?;
+    // xmrig only submits to the proxy when mining through the built-in proxy
+    let is_merge_mining = matches!(
+        state.cpu_miner_config.read().await.node_connection,
+        CpuMinerConnection::BuiltInProxy
+    );
+    if is_merge_mining {
+        if let Some(summary) = state.cpu_miner.read().await.get_summary() {
+            stats.shares_submitted = Some(summary.shares_total);
+            stats.shares_accepted = Some(summary.shares_good);
+        }
+    }
+    stats.hub_blocks_found = state
+        .stratum_hub_manager
+        .get_status()
+        .await
+        .workers
+        .iter()
+        .map(|worker| worker.blocks_found)
+        .sum();
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "get_merge_mining_stats took too long: {:?}", timer.elapsed());
+    }
+    Ok(stats)
+}
+
+#[tauri::command]
+pub async fn get_mining_mode_recommendation(
+    app_handle: tauri::AppHandle,
+) -> Result<MiningModeRecommendation, InvokeError> {
+    let timer = Instant::now();
+    let recommendation = get_recommendation(&app_handle).await;
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "get_mining_mode_recommendation took too long: {:?}", timer.elapsed());
+    }
+    Ok(recommendation)
+}
+
+#[tauri::command]
+pub async fn get_monero_nodes_status(
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<Vec<MoneroNodeStatus>, InvokeError> {
+    let timer = Instant::now();
+    let nodes = state.mm_proxy_manager.get_monero_nodes_status().await;
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "get_monero_nodes_status took too long: {:?}", timer.elapsed());
+    }
+    Ok(nodes)
+}
+
+#[tauri::command]
+pub async fn refresh_monero_nodes_status() -> Result<Vec<MoneroNodeStatus>, InvokeError> {
+    info!(target: LOG_TARGET, "[refresh_monero_nodes_status] called");
+    let monero_nodes = ConfigCore::content().await.mmproxy_monero_nodes().clone();
+    Ok(probe_monero_nodes(&monero_nodes).await)
+}
+
+#[tauri::command]
+pub async fn test_monero_node(url: String) -> Result<MoneroNodeStatus, InvokeError> {
+    info!(target: LOG_TARGET, "[test_monero_node] called with url: {url}");
+    let url = normalize_monero_node_url(&url).map_err(InvokeError::from_anyhow)?;
+    Ok(probe_monero_node(&url).await)
+}
+
+#[tauri::command]
+pub async fn set_mining_mode_auto_switch(
+    enabled: bool,
+    threshold_percent: u32,
+) -> Result<(), InvokeError> {
+    info!(target: LOG_TARGET, "[set_mining_mode_auto_switch] called with enabled: {enabled}, threshold_percent: {threshold_percent}");
+    if !(1..=100).contains(&threshold_percent) {
+        return Err(InvokeError::from(
+            "Threshold must be between 1 and 100 percent".to_string(),
+        ));
+    }
+    ConfigPools::update_field(
+        ConfigPoolsContent::set_auto_switch_threshold_percent,
+        threshold_percent,
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+    ConfigPools::update_field(ConfigPoolsContent::set_auto_switch_enabled, enabled)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_monerod_config(
+    use_monero_fail: bool,
+    monero_nodes: Vec<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[set_monerod_config] called with use_monero_fail: {use_monero_fail:?}, monero_nodes: {monero_nodes:?}");
+    let monero_nodes = monero_nodes
+        .iter()
+        .map(|url| normalize_monero_node_url(url))
+        .collect::<Result<Vec<String>, _>>()
+        .map_err(InvokeError::from_anyhow)?;
+    ConfigCore::update_field_requires_restart(
+        ConfigCoreContent::set_mmproxy_monero_nodes,
+        monero_nodes.clone(),
+        vec![SetupPhase::Mining],
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+
+    ConfigCore::update_field_requires_restart(
+        ConfigCoreContent::set_mmproxy_use_monero_failover,
+        use_monero_fail,
+        vec![SetupPhase::Mining],
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
 
     SetupManager::get_instance()
         .restart_phases_from_queue(app_handle)
@@ -1423,6 +1984,48 @@ pub async fn set_should_auto_launch(should_auto_launch: bool) -> Result<(), Invo
     Ok(())
 }
 
+/// Enables the LAN mining hub. The stratum port serves RandomX jobs to xmrig workers, the SHA3x
+/// port forwards the local node's gRPC (every method the node allows, not only mining) to GPU
+/// miners. Both listen on all interfaces; the gRPC forward only accepts private network clients.
+#[tauri::command]
+pub async fn set_stratum_hub_config(
+    enabled: bool,
+    stratum_port: u16,
+    sha3x_port: u16,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[set_stratum_hub_config] called with enabled: {enabled}, stratum_port: {stratum_port}, sha3x_port: {sha3x_port}");
+    if stratum_port == 0 || sha3x_port == 0 || stratum_port == sha3x_port {
+        return Err(InvokeError::from(
+            "Stratum and SHA3x ports must be set and different".to_string(),
+        ));
+    }
+
+    ConfigMining::update_field(ConfigMiningContent::set_stratum_hub_port, stratum_port)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+    ConfigMining::update_field(ConfigMiningContent::set_stratum_hub_sha3x_port, sha3x_port)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+    ConfigMining::update_field_requires_restart(
+        ConfigMiningContent::set_stratum_hub_enabled,
+        enabled,
+        vec![SetupPhase::Mining],
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+
+    SetupManager::get_instance()
+        .restart_phases_from_queue(app_handle)
+        .await;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "set_stratum_hub_config took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn set_tor_config(
     config: TorConfig,
@@ -1548,14 +2151,14 @@ pub async fn start_cpu_mining(
     let cpu_miner_running = cpu_miner.is_running().await;
 
     let mut cpu_config = state.cpu_miner_config.write().await;
-    let tari_address = InternalWallet::tari_address().await;
+    let tari_address = InternalWallet::mining_rewards_address().await;
     cpu_config.load_from_config_pools(ConfigPools::content().await.clone(), &tari_address);
     drop(cpu_config);
 
     drop(cpu_miner);
     let cpu_miner_config = state.cpu_miner_config.read().await;
     drop(cpu_miner_config);
-    let tari_address = InternalWallet::tari_address().await;
+    let tari_address = InternalWallet::mining_rewards_address().await;
 
     if cpu_mining_enabled && !cpu_miner_running {
         let cpu_miner_config = state.cpu_miner_config.read().await;
@@ -1629,7 +2232,7 @@ pub async fn start_gpu_mining(
         telemetry_id = "tari-universe".to_string();
     }
 
-    let tari_address = InternalWallet::tari_address().await;
+    let tari_address = InternalWallet::mining_rewards_address().await;
 
     info!(target: LOG_TARGET, "3. Starting gpu miner");
 
@@ -1914,6 +2517,76 @@ pub async fn stop_mining_status(state: tauri::State<'_, UniverseAppState>) -> Re
     Ok(())
 }
 
+#[tauri::command]
+pub async fn get_cpu_miner_backends() -> Result<(Vec<String>, String), InvokeError> {
+    let selected = *ConfigMining::content().await.cpu_miner_backend();
+    let backends = CpuMinerBackend::all()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    Ok((backends, selected.to_string()))
+}
+
+#[tauri::command]
+pub async fn set_cpu_miner_backend(
+    backend: &str,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    info!(target: LOG_TARGET, "[set_cpu_miner_backend] called with backend: {backend:?}");
+    let timer = Instant::now();
+
+    let backend = CpuMinerBackend::from_string(backend).map_err(InvokeError::from_anyhow)?;
+    // Make sure the binary is available before the mining phase tries to start it
+    BinaryResolver::current()
+        .initialize_binary(backend.binary(), None)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    ConfigMining::update_field_requires_restart(
+        ConfigMiningContent::set_cpu_miner_backend,
+        backend,
+        vec![SetupPhase::Mining],
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+
+    SetupManager::get_instance()
+        .restart_phases_from_queue(app_handle)
+        .await;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "set_cpu_miner_backend took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_xmrig_tuning() -> Result<XmrigTuning, InvokeError> {
+    Ok(ConfigMining::content().await.xmrig_tuning().clone())
+}
+
+#[tauri::command]
+pub async fn set_xmrig_tuning(
+    tuning: XmrigTuning,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    info!(target: LOG_TARGET, "[set_xmrig_tuning] called with tuning: {tuning:?}");
+    tuning.validate().map_err(InvokeError::from_anyhow)?;
+
+    ConfigMining::update_field_requires_restart(
+        ConfigMiningContent::set_xmrig_tuning,
+        tuning,
+        vec![SetupPhase::Mining],
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+
+    SetupManager::get_instance()
+        .restart_phases_from_queue(app_handle)
+        .await;
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn set_selected_engine(
     selected_engine: &str,
@@ -2028,6 +2701,181 @@ pub async fn send_one_sided_to_stealth_address(
     Ok(())
 }
 
+#[tauri::command]
+pub async fn get_unspent_outputs(
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<Vec<UnspentOutput>, String> {
+    let timer = Instant::now();
+    let outputs = state
+        .wallet_manager
+        .get_unspent_outputs()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "get_unspent_outputs took too long: {:?}", timer.elapsed());
+    }
+    Ok(outputs)
+}
+
+#[tauri::command]
+pub async fn preview_sweep_to_self(
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<SelfSweepPreview, String> {
+    let timer = Instant::now();
+    let preview = state
+        .wallet_manager
+        .preview_sweep_to_self()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "preview_sweep_to_self took too long: {:?}", timer.elapsed());
+    }
+    Ok(preview)
+}
+
+/// Sends the spendable balance to our own address. The wallet picks the inputs, so this usually
+/// merges many small outputs but does not guarantee which ones are spent.
+#[tauri::command]
+pub async fn sweep_to_self(
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<SelfSweepPreview, String> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[sweep_to_self] called");
+    if !InternalWallet::is_internal().await {
+        return Err("Sweeping to self requires an internal wallet".to_string());
+    }
+    let result = state
+        .wallet_manager
+        .sweep_to_self(&app_handle)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let balance = state.wallet_manager.get_balance().await;
+    if let Ok(balance) = balance {
+        EventsEmitter::emit_wallet_balance_update(balance).await;
+    }
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "sweep_to_self took too long: {:?}", timer.elapsed());
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn get_scheduled_payments() -> Result<Vec<ScheduledPayment>, String> {
+    Ok(ConfigWallet::content().await.scheduled_payments().clone())
+}
+
+#[tauri::command]
+pub async fn get_scheduled_payments_history() -> Result<Vec<ScheduledPaymentRecord>, String> {
+    Ok(ConfigWallet::content()
+        .await
+        .scheduled_payments_history()
+        .clone())
+}
+
+#[tauri::command]
+pub async fn add_scheduled_payment(
+    app_handle: tauri::AppHandle,
+    name: String,
+    destination: String,
+    payment_id: Option<String>,
+    kind: ScheduledPaymentKind,
+) -> Result<ScheduledPayment, InvokeError> {
+    info!(target: LOG_TARGET, "[add_scheduled_payment] called with args: (name: {name:?}, destination: {destination:?}, kind: {kind:?})");
+    if !InternalWallet::is_internal().await {
+        return Err(InvokeError::from(
+            "Scheduled payments require an internal wallet".to_string(),
+        ));
+    }
+    verify_send(destination.clone(), TariAddressFeatures::ONE_SIDED)?;
+    match &kind {
+        ScheduledPaymentKind::Sweep { threshold } => {
+            if threshold.as_u64() == 0 {
+                return Err(InvokeError::from(
+                    "Sweep threshold must be greater than zero".to_string(),
+                ));
+            }
+        }
+        ScheduledPaymentKind::Recurring { amount, schedule } => {
+            if amount.as_u64() == 0 {
+                return Err(InvokeError::from(
+                    "Payment amount must be greater than zero".to_string(),
+                ));
+            }
+            schedule.validate()?;
+        }
+    }
+
+    // Validate PIN if pin locked
+    let _unused = PinManager::get_validated_pin_if_defined(&app_handle)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    let payment = ScheduledPayment {
+        id: rand_utils::get_rand_string(8),
+        name,
+        destination,
+        payment_id: payment_id.filter(|p| !p.is_empty()),
+        kind,
+        enabled: true,
+        created_at: std::time::SystemTime::now(),
+        last_run_at: None,
+    };
+    ConfigWallet::update_field(ConfigWalletContent::add_scheduled_payment, payment.clone())
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    Ok(payment)
+}
+
+#[tauri::command]
+pub async fn remove_scheduled_payment(scheduled_payment_id: String) -> Result<(), InvokeError> {
+    ConfigWallet::update_field(
+        ConfigWalletContent::remove_scheduled_payment,
+        scheduled_payment_id,
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_scheduled_payment_enabled(
+    app_handle: tauri::AppHandle,
+    scheduled_payment_id: String,
+    enabled: bool,
+) -> Result<(), InvokeError> {
+    if enabled {
+        // Validate PIN if pin locked
+        let _unused = PinManager::get_validated_pin_if_defined(&app_handle)
+            .await
+            .map_err(InvokeError::from_anyhow)?;
+    }
+    ConfigWallet::update_field(
+        ConfigWalletContent::set_scheduled_payment_enabled,
+        (scheduled_payment_id, enabled),
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_payments_min_reserve(amount: String) -> Result<(), InvokeError> {
+    let t_amount = Minotari::from_str(&amount).map_err(|e| e.to_string())?;
+    ConfigWallet::update_field(
+        ConfigWalletContent::set_payments_min_reserve,
+        MicroMinotari::from(t_amount),
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn websocket_close(
     _: tauri::AppHandle,
@@ -2152,6 +3000,853 @@ pub async fn set_node_type(
     Ok(())
 }
 
+#[tauri::command]
+pub async fn get_remote_base_node_statuses(
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<Vec<RemoteNodeEndpointStatus>, InvokeError> {
+    Ok(state.node_manager.get_remote_endpoints_statuses().await)
+}
+
+#[tauri::command]
+pub async fn set_remote_base_node_fallback_addresses(
+    addresses: Vec<String>,
+) -> Result<(), InvokeError> {
+    info!(target: LOG_TARGET, "[set_remote_base_node_fallback_addresses] called with {addresses:?}");
+    for address in &addresses {
+        parse_grpc_address(address).map_err(|e| {
+            InvokeError::from(format!("Invalid remote base node address {address}: {e}"))
+        })?;
+    }
+
+    // Picked up the next time the node phase starts
+    ConfigCore::update_field(
+        ConfigCoreContent::set_remote_base_node_fallback_addresses,
+        addresses,
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_node_snapshot_source(
+    snapshot_source: Option<NodeSnapshotSource>,
+) -> Result<(), InvokeError> {
+    info!(target: LOG_TARGET, "[set_node_snapshot_source] called with {snapshot_source:?}");
+    if let Some(snapshot_source) = &snapshot_source {
+        snapshot_source
+            .validate()
+            .map_err(InvokeError::from_anyhow)?;
+    }
+
+    ConfigCore::update_field(ConfigCoreContent::set_node_snapshot_source, snapshot_source)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    Ok(())
+}
+
+/// Replaces the local node database with the configured snapshot
+#[tauri::command]
+pub async fn bootstrap_node_from_snapshot(
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    let timer = Instant::now();
+    let Some(snapshot_source) = ConfigCore::content().await.node_snapshot_source().clone() else {
+        return Err(InvokeError::from(
+            "No node snapshot source configured".to_string(),
+        ));
+    };
+    if !state.node_manager.is_local().await {
+        return Err(InvokeError::from(
+            "Snapshots can only be used with a local node".to_string(),
+        ));
+    }
+    let base_path = app_handle
+        .path()
+        .app_local_data_dir()
+        .map_err(|_| InvokeError::from("Could not find node data dir".to_string()))?;
+
+    // Download and verify while the node keeps running, the current database stays untouched
+    // until the verified snapshot is swapped in
+    let snapshot_root = match NodeSnapshot::stage(&snapshot_source, &base_path, None).await {
+        Ok(snapshot_root) => snapshot_root,
+        Err(e) => {
+            NodeSnapshot::remove_staging(&base_path).await;
+            return Err(InvokeError::from_anyhow(e));
+        }
+    };
+
+    let phases = vec![SetupPhase::Node, SetupPhase::Wallet, SetupPhase::Mining];
+    SetupManager::get_instance()
+        .shutdown_phases(phases.clone())
+        .await;
+    let install_result =
+        NodeSnapshot::install(&snapshot_root, &NodeSnapshot::network_dir(&base_path)).await;
+    SetupManager::get_instance()
+        .resume_phases(app_handle, phases)
+        .await;
+    NodeSnapshot::remove_staging(&base_path).await;
+    install_result.map_err(InvokeError::from_anyhow)?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "bootstrap_node_from_snapshot took too long: {:?}", timer.elapsed());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn verify_node_database(
+    app_handle: tauri::AppHandle,
+) -> Result<NodeDatabaseReport, InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[verify_node_database] called");
+    let base_path = app_handle
+        .path()
+        .app_local_data_dir()
+        .map_err(|_| InvokeError::from("Could not find node data dir".to_string()))?;
+    let (timeout_watcher_sender, _timeout_watcher_rx) = tokio::sync::watch::channel(0);
+    let mut progress_stepper = NodeDatabaseMaintenance::create_progress_stepper(
+        app_handle,
+        &[
+            ProgressNodeMaintenancePlan::Verify,
+            ProgressNodeMaintenancePlan::Done,
+        ],
+        timeout_watcher_sender,
+    );
+
+    // Read only, safe to run while the node is up
+    let report = NodeDatabaseMaintenance::verify(&NodeDatabaseMaintenance::network_dir(&base_path))
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+    progress_stepper
+        .resolve_step(ProgressPlans::NodeMaintenance(
+            ProgressNodeMaintenancePlan::Verify,
+        ))
+        .await;
+    progress_stepper
+        .resolve_step(ProgressPlans::NodeMaintenance(
+            ProgressNodeMaintenancePlan::Done,
+        ))
+        .await;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "verify_node_database took too long: {:?}", timer.elapsed());
+    }
+    Ok(report)
+}
+
+/// Truncates the unused tail of the node database files, see `NodeDatabaseMaintenance::trim`
+#[tauri::command]
+pub async fn trim_node_database(
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<u64, InvokeError> {
+    info!(target: LOG_TARGET, "[trim_node_database] called");
+    if !state.node_manager.is_local().await {
+        return Err(InvokeError::from(
+            "Database maintenance is only available for a local node".to_string(),
+        ));
+    }
+    let base_path = app_handle
+        .path()
+        .app_local_data_dir()
+        .map_err(|_| InvokeError::from("Could not find node data dir".to_string()))?;
+    let (timeout_watcher_sender, _timeout_watcher_rx) = tokio::sync::watch::channel(0);
+    let mut progress_stepper = NodeDatabaseMaintenance::create_progress_stepper(
+        app_handle.clone(),
+        &[
+            ProgressNodeMaintenancePlan::Trim,
+            ProgressNodeMaintenancePlan::Done,
+        ],
+        timeout_watcher_sender,
+    );
+
+    let phases = vec![SetupPhase::Node, SetupPhase::Wallet, SetupPhase::Mining];
+    SetupManager::get_instance()
+        .shutdown_phases(phases.clone())
+        .await;
+    let result =
+        NodeDatabaseMaintenance::trim(&NodeDatabaseMaintenance::network_dir(&base_path)).await;
+    progress_stepper
+        .resolve_step(ProgressPlans::NodeMaintenance(
+            ProgressNodeMaintenancePlan::Trim,
+        ))
+        .await;
+    SetupManager::get_instance()
+        .resume_phases(app_handle, phases)
+        .await;
+    progress_stepper
+        .resolve_step(ProgressPlans::NodeMaintenance(
+            ProgressNodeMaintenancePlan::Done,
+        ))
+        .await;
+
+    result.map_err(InvokeError::from_anyhow)
+}
+
+#[tauri::command]
+pub async fn backup_node_database(
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<NodeDatabaseBackupInfo, InvokeError> {
+    info!(target: LOG_TARGET, "[backup_node_database] called");
+    if !state.node_manager.is_local().await {
+        return Err(InvokeError::from(
+            "Database maintenance is only available for a local node".to_string(),
+        ));
+    }
+    let base_path = app_handle
+        .path()
+        .app_local_data_dir()
+        .map_err(|_| InvokeError::from("Could not find node data dir".to_string()))?;
+    let block_height = state.node_status_watch_rx.borrow().block_height;
+    let (timeout_watcher_sender, _timeout_watcher_rx) = tokio::sync::watch::channel(0);
+    let mut progress_stepper = NodeDatabaseMaintenance::create_progress_stepper(
+        app_handle.clone(),
+        &[
+            ProgressNodeMaintenancePlan::Backup,
+            ProgressNodeMaintenancePlan::Done,
+        ],
+        timeout_watcher_sender,
+    );
+    let backup_tracker = progress_stepper.channel_step_range_updates(
+        ProgressPlans::NodeMaintenance(ProgressNodeMaintenancePlan::Backup),
+        Some(ProgressPlans::NodeMaintenance(
+            ProgressNodeMaintenancePlan::Done,
+        )),
+    );
+
+    let phases = vec![SetupPhase::Node, SetupPhase::Wallet, SetupPhase::Mining];
+    SetupManager::get_instance()
+        .shutdown_phases(phases.clone())
+        .await;
+    let result = NodeDatabaseMaintenance::backup(
+        &NodeDatabaseMaintenance::network_dir(&base_path),
+        &NodeDatabaseMaintenance::backup_dir(&base_path),
+        block_height,
+        backup_tracker,
+    )
+    .await;
+    SetupManager::get_instance()
+        .resume_phases(app_handle, phases)
+        .await;
+    progress_stepper
+        .resolve_step(ProgressPlans::NodeMaintenance(
+            ProgressNodeMaintenancePlan::Done,
+        ))
+        .await;
+
+    result.map_err(InvokeError::from_anyhow)
+}
+
+#[tauri::command]
+pub async fn get_node_database_backup(
+    app_handle: tauri::AppHandle,
+) -> Result<Option<NodeDatabaseBackupInfo>, InvokeError> {
+    let base_path = app_handle
+        .path()
+        .app_local_data_dir()
+        .map_err(|_| InvokeError::from("Could not find node data dir".to_string()))?;
+    Ok(
+        NodeDatabaseMaintenance::read_backup_info(&NodeDatabaseMaintenance::backup_dir(&base_path))
+            .await,
+    )
+}
+
+/// Replaces a corrupt database with the whole last backup, or clears it for a resync
+#[tauri::command]
+pub async fn repair_node_database(
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<NodeDatabaseRepairOutcome, InvokeError> {
+    info!(target: LOG_TARGET, "[repair_node_database] called");
+    if !state.node_manager.is_local().await {
+        return Err(InvokeError::from(
+            "Database maintenance is only available for a local node".to_string(),
+        ));
+    }
+    let base_path = app_handle
+        .path()
+        .app_local_data_dir()
+        .map_err(|_| InvokeError::from("Could not find node data dir".to_string()))?;
+    let (timeout_watcher_sender, _timeout_watcher_rx) = tokio::sync::watch::channel(0);
+    let mut progress_stepper = NodeDatabaseMaintenance::create_progress_stepper(
+        app_handle.clone(),
+        &[
+            ProgressNodeMaintenancePlan::Repair,
+            ProgressNodeMaintenancePlan::Done,
+        ],
+        timeout_watcher_sender,
+    );
+    let repair_tracker = progress_stepper.channel_step_range_updates(
+        ProgressPlans::NodeMaintenance(ProgressNodeMaintenancePlan::Repair),
+        Some(ProgressPlans::NodeMaintenance(
+            ProgressNodeMaintenancePlan::Done,
+        )),
+    );
+
+    let phases = vec![SetupPhase::Node, SetupPhase::Wallet, SetupPhase::Mining];
+    SetupManager::get_instance()
+        .shutdown_phases(phases.clone())
+        .await;
+    let mut result = NodeDatabaseMaintenance::repair(
+        &NodeDatabaseMaintenance::network_dir(&base_path),
+        &NodeDatabaseMaintenance::backup_dir(&base_path),
+        repair_tracker,
+    )
+    .await;
+    if let Ok(NodeDatabaseRepairOutcome::Resync) = result {
+        // Same as a corrupt database on startup, the node resyncs (or uses a configured snapshot)
+        result = state
+            .node_manager
+            .clean_data_folder(&base_path)
+            .await
+            .map(|_| NodeDatabaseRepairOutcome::Resync);
+    }
+    SetupManager::get_instance()
+        .resume_phases(app_handle, phases)
+        .await;
+    progress_stepper
+        .resolve_step(ProgressPlans::NodeMaintenance(
+            ProgressNodeMaintenancePlan::Done,
+        ))
+        .await;
+
+    result.map_err(InvokeError::from_anyhow)
+}
+
+/// Clears the local node database after the user confirmed a resync, e.g. when the node is
+/// stuck on an orphan chain it can't rewind
+#[tauri::command]
+pub async fn resync_node_database(
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    info!(target: LOG_TARGET, "[resync_node_database] called");
+    if !state.node_manager.is_local().await {
+        return Err(InvokeError::from(
+            "Resync is only available for a local node".to_string(),
+        ));
+    }
+    let base_path = app_handle
+        .path()
+        .app_local_data_dir()
+        .map_err(|_| InvokeError::from("Could not find node data dir".to_string()))?;
+
+    let phases = vec![SetupPhase::Node, SetupPhase::Wallet, SetupPhase::Mining];
+    SetupManager::get_instance()
+        .shutdown_phases(phases.clone())
+        .await;
+    let result = state.node_manager.clean_data_folder(&base_path).await;
+    SetupManager::get_instance()
+        .resume_phases(app_handle, phases)
+        .await;
+
+    result.map_err(InvokeError::from_anyhow)
+}
+
+#[tauri::command]
+pub async fn get_node_effective_config(
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<Vec<NodeConfigEntry>, InvokeError> {
+    let config_overrides = ConfigCore::content().await.node_config_overrides().clone();
+    let entries = state
+        .node_manager
+        .get_local_node_effective_config()
+        .await
+        .into_iter()
+        .map(|(key, value)| NodeConfigEntry {
+            is_user_override: config_overrides.contains_key(&key),
+            key,
+            value,
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+#[tauri::command]
+pub async fn set_node_config_overrides(
+    overrides: HashMap<String, String>,
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    info!(target: LOG_TARGET, "[set_node_config_overrides] called with {overrides:?}");
+    for (key, value) in &overrides {
+        validate_node_config_override(key, value).map_err(InvokeError::from_anyhow)?;
+    }
+
+    if state.node_manager.is_local().await {
+        ConfigCore::update_field_requires_restart(
+            ConfigCoreContent::set_node_config_overrides,
+            overrides,
+            vec![SetupPhase::Node, SetupPhase::Wallet, SetupPhase::Mining],
+        )
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+        SetupManager::get_instance()
+            .restart_phases_from_queue(app_handle)
+            .await;
+    } else {
+        ConfigCore::update_field(ConfigCoreContent::set_node_config_overrides, overrides)
+            .await
+            .map_err(InvokeError::from_anyhow)?;
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_block_headers(
+    from_height: Option<u64>,
+    count: u64,
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<Vec<BlockHeaderInfo>, InvokeError> {
+    // Starting from height 0 lists the headers below the tip
+    state
+        .node_manager
+        .get_block_headers(
+            from_height.unwrap_or(0),
+            count.min(MAX_CHAIN_INSPECTION_BLOCKS),
+        )
+        .await
+        .map_err(InvokeError::from_anyhow)
+}
+
+#[tauri::command]
+pub async fn get_block_header_by_hash(
+    hash: String,
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<BlockHeaderInfo, InvokeError> {
+    let hash = Vec::<u8>::from_hex(hash.trim())
+        .map_err(|e| InvokeError::from(format!("Invalid block hash: {e}")))?;
+    state
+        .node_manager
+        .get_block_header_by_hash(hash)
+        .await
+        .map_err(InvokeError::from_anyhow)
+}
+
+#[tauri::command]
+pub async fn get_difficulty_history(
+    num_blocks: u64,
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<Vec<DifficultySample>, InvokeError> {
+    state
+        .node_manager
+        .get_difficulty_history(num_blocks.min(MAX_CHAIN_INSPECTION_BLOCKS))
+        .await
+        .map_err(InvokeError::from_anyhow)
+}
+
+#[tauri::command]
+pub async fn get_mempool_stats(
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<MempoolStats, InvokeError> {
+    let timer = Instant::now();
+    let stats = state
+        .node_manager
+        .get_mempool_stats()
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "get_mempool_stats took too long: {:?}", timer.elapsed());
+    }
+    Ok(stats)
+}
+
+#[tauri::command]
+pub async fn get_recent_block_rewards(
+    count: u64,
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<Vec<BlockRewardInfo>, InvokeError> {
+    let headers = state
+        .node_manager
+        .get_block_headers(0, count.min(MAX_CHAIN_INSPECTION_BLOCKS))
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    Ok(headers
+        .into_iter()
+        .map(|header| BlockRewardInfo {
+            height: header.height,
+            timestamp: header.timestamp,
+            pow_algo: header.pow_algo,
+            reward: header.reward,
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn get_node_peers(
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<Vec<PeerDetails>, InvokeError> {
+    state
+        .node_manager
+        .list_connected_peers_details()
+        .await
+        .map_err(InvokeError::from_anyhow)
+}
+
+#[tauri::command]
+pub async fn add_node_seed_peer(
+    peer: String,
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    info!(target: LOG_TARGET, "[add_node_seed_peer] called with {peer}");
+    let peer = peer.trim().to_string();
+    validate_seed_peer(&peer).map_err(InvokeError::from_anyhow)?;
+
+    let mut seed_peers = ConfigCore::content().await.node_seed_peers().clone();
+    if seed_peers.contains(&peer) {
+        return Ok(());
+    }
+    seed_peers.push(peer);
+    update_node_seed_peers(seed_peers, &state, app_handle).await
+}
+
+#[tauri::command]
+pub async fn remove_node_seed_peer(
+    peer: String,
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    info!(target: LOG_TARGET, "[remove_node_seed_peer] called with {peer}");
+    let mut seed_peers = ConfigCore::content().await.node_seed_peers().clone();
+    seed_peers.retain(|seed_peer| seed_peer != &peer);
+    update_node_seed_peers(seed_peers, &state, app_handle).await
+}
+
+async fn update_node_seed_peers(
+    seed_peers: Vec<String>,
+    state: &tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    // Seed peers are only read by the local node on startup
+    if state.node_manager.is_local().await {
+        ConfigCore::update_field_requires_restart(
+            ConfigCoreContent::set_node_seed_peers,
+            seed_peers,
+            vec![SetupPhase::Node, SetupPhase::Wallet, SetupPhase::Mining],
+        )
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+        SetupManager::get_instance()
+            .restart_phases_from_queue(app_handle)
+            .await;
+    } else {
+        ConfigCore::update_field(ConfigCoreContent::set_node_seed_peers, seed_peers)
+            .await
+            .map_err(InvokeError::from_anyhow)?;
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_node_banned_peers() -> Result<Vec<String>, InvokeError> {
+    Ok(ConfigCore::content().await.node_banned_peers().clone())
+}
+
+/// Bans a connected peer by its IP address, the local node stops dialing it after the restart.
+/// Peers only reachable over tor have no address we can ban.
+#[tauri::command]
+pub async fn ban_node_peer(
+    public_key: String,
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    info!(target: LOG_TARGET, "[ban_node_peer] called with {public_key}");
+    let peers = state
+        .node_manager
+        .list_connected_peers_details()
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+    let host = peers
+        .iter()
+        .find(|peer| peer.public_key == public_key)
+        .ok_or_else(|| InvokeError::from(format!("Peer {public_key} is not connected")))?
+        .address
+        .as_deref()
+        .and_then(peer_host)
+        .filter(|host| host.parse::<IpAddr>().is_ok())
+        .ok_or_else(|| {
+            InvokeError::from("Only peers with an IP address can be banned".to_string())
+        })?;
+
+    let mut banned_peers = ConfigCore::content().await.node_banned_peers().clone();
+    if banned_peers.contains(&host) {
+        return Ok(());
+    }
+    banned_peers.push(host);
+    update_node_banned_peers(banned_peers, &state, app_handle).await
+}
+
+#[tauri::command]
+pub async fn unban_node_peer(
+    host: String,
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    info!(target: LOG_TARGET, "[unban_node_peer] called with {host}");
+    let mut banned_peers = ConfigCore::content().await.node_banned_peers().clone();
+    banned_peers.retain(|banned_peer| banned_peer != &host);
+    update_node_banned_peers(banned_peers, &state, app_handle).await
+}
+
+async fn update_node_banned_peers(
+    banned_peers: Vec<String>,
+    state: &tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    // Excluded dial addresses are only read by the local node on startup
+    if state.node_manager.is_local().await {
+        ConfigCore::update_field_requires_restart(
+            ConfigCoreContent::set_node_banned_peers,
+            banned_peers,
+            vec![SetupPhase::Node, SetupPhase::Wallet, SetupPhase::Mining],
+        )
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+        SetupManager::get_instance()
+            .restart_phases_from_queue(app_handle)
+            .await;
+    } else {
+        ConfigCore::update_field(ConfigCoreContent::set_node_banned_peers, banned_peers)
+            .await
+            .map_err(InvokeError::from_anyhow)?;
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_node_storage_mode(
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<NodeStorageModeStatus, InvokeError> {
+    let base_path = app_handle
+        .path()
+        .app_local_data_dir()
+        .map_err(|_| InvokeError::from("Could not find node data dir".to_string()))?;
+    let configured_mode = ConfigCore::content().await.node_storage_mode().clone();
+    let database_mode = state
+        .node_manager
+        .get_database_storage_mode(&base_path)
+        .map_err(InvokeError::from_anyhow)?;
+    let is_database_compatible = database_mode
+        .as_ref()
+        .is_none_or(|mode| configured_mode.is_compatible_with_database(mode));
+
+    Ok(NodeStorageModeStatus {
+        configured_mode,
+        database_mode,
+        is_database_compatible,
+    })
+}
+
+/// Returns `false` without applying the change when the existing database can't be reused
+/// and `resync` was not confirmed
+#[tauri::command]
+pub async fn set_node_storage_mode(
+    storage_mode: NodeStorageMode,
+    resync: bool,
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<bool, InvokeError> {
+    let timer = Instant::now();
+    info!(target: LOG_TARGET, "[set_node_storage_mode] called with {storage_mode:?}, resync: {resync}");
+
+    if matches!(storage_mode, NodeStorageMode::Pruned { horizon } if horizon == 0) {
+        return Err(InvokeError::from(
+            "Pruning horizon must be greater than 0".to_string(),
+        ));
+    }
+    if ConfigCore::content().await.node_storage_mode() == &storage_mode {
+        return Ok(true);
+    }
+
+    let base_path = app_handle
+        .path()
+        .app_local_data_dir()
+        .map_err(|_| InvokeError::from("Could not find node data dir".to_string()))?;
+    let database_mode = state
+        .node_manager
+        .get_database_storage_mode(&base_path)
+        .map_err(InvokeError::from_anyhow)?;
+    let requires_resync = database_mode
+        .as_ref()
+        .is_some_and(|database_mode| !storage_mode.is_compatible_with_database(database_mode));
+    if requires_resync && !resync {
+        info!(target: LOG_TARGET, "[set_node_storage_mode] Node database is {database_mode:?}, resync required");
+        return Ok(false);
+    }
+
+    if requires_resync {
+        // Confirmed by the user, the node itself never removes an incompatible database
+        let phases = vec![SetupPhase::Node, SetupPhase::Wallet, SetupPhase::Mining];
+        SetupManager::get_instance()
+            .shutdown_phases(phases.clone())
+            .await;
+        let result = async {
+            state.node_manager.remove_database(&base_path).await?;
+            ConfigCore::update_field(ConfigCoreContent::set_node_storage_mode, storage_mode).await
+        }
+        .await;
+        SetupManager::get_instance()
+            .resume_phases(app_handle, phases)
+            .await;
+        result.map_err(InvokeError::from_anyhow)?;
+    } else {
+        ConfigCore::update_field_requires_restart(
+            ConfigCoreContent::set_node_storage_mode,
+            storage_mode,
+            vec![SetupPhase::Node, SetupPhase::Wallet, SetupPhase::Mining],
+        )
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+        SetupManager::get_instance()
+            .restart_phases_from_queue(app_handle)
+            .await;
+    }
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "set_node_storage_mode took too long: {:?}", timer.elapsed());
+    }
+    Ok(true)
+}
+
+#[tauri::command]
+pub async fn set_fallback_to_remote_node(enabled: bool) -> Result<(), InvokeError> {
+    info!(target: LOG_TARGET, "[set_fallback_to_remote_node] called with {enabled}");
+    // Read when the local node takes over, no restart needed
+    ConfigCore::update_field(ConfigCoreContent::set_fallback_to_remote_node, enabled)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_network_usage(
+    state: tauri::State<'_, UniverseAppState>,
+) -> Result<NetworkUsage, InvokeError> {
+    let timer = Instant::now();
+    let config = ConfigCore::content().await;
+    let tor_status = state.tor_manager.get_status();
+    let networks = sysinfo::Networks::new_with_refreshed_list();
+    let (system_wide_bytes_received, system_wide_bytes_transmitted) = networks
+        .list()
+        .values()
+        .fold((0, 0), |(received, transmitted), data| {
+            (
+                received + data.total_received(),
+                transmitted + data.total_transmitted(),
+            )
+        });
+
+    let network_usage = NetworkUsage {
+        tor_bytes_read: tor_status.bytes_read,
+        tor_bytes_written: tor_status.bytes_written,
+        system_wide_bytes_received,
+        system_wide_bytes_transmitted,
+        bandwidth_limits: *config.bandwidth_limits(),
+        metered_connection: *config.metered_connection(),
+        is_local_sync_paused: !state
+            .node_manager
+            .is_local_sync_allowed(*config.metered_connection()),
+    };
+
+    if timer.elapsed() > MAX_ACCEPTABLE_COMMAND_TIME {
+        warn!(target: LOG_TARGET, "get_network_usage took too long: {:?}", timer.elapsed());
+    }
+    Ok(network_usage)
+}
+
+#[tauri::command]
+pub async fn set_bandwidth_limits(
+    bandwidth_limits: BandwidthLimits,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    info!(target: LOG_TARGET, "[set_bandwidth_limits] called with {bandwidth_limits:?}");
+    bandwidth_limits
+        .validate()
+        .map_err(InvokeError::from_anyhow)?;
+    // Tor and the node pick the limits up on start
+    ConfigCore::update_field_requires_restart(
+        ConfigCoreContent::set_bandwidth_limits,
+        bandwidth_limits,
+        vec![SetupPhase::Node, SetupPhase::Wallet, SetupPhase::Mining],
+    )
+    .await
+    .map_err(InvokeError::from_anyhow)?;
+
+    SetupManager::get_instance()
+        .restart_phases_from_queue(app_handle)
+        .await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_metered_connection(
+    enabled: bool,
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    info!(target: LOG_TARGET, "[set_metered_connection] called with {enabled}");
+    ConfigCore::update_field(ConfigCoreContent::set_metered_connection, enabled)
+        .await
+        .map_err(InvokeError::from_anyhow)?;
+
+    // Every switch starts paused again, and the node type may have been swapped to remote while metered
+    state.node_manager.set_metered_sync_triggered(false);
+    let node_type = ConfigCore::content().await.node_type().clone();
+    state.node_manager.set_node_type(node_type).await;
+
+    SetupManager::get_instance()
+        .restart_phases(
+            app_handle,
+            vec![SetupPhase::Node, SetupPhase::Wallet, SetupPhase::Mining],
+        )
+        .await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn trigger_metered_node_sync(
+    state: tauri::State<'_, UniverseAppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), InvokeError> {
+    info!(target: LOG_TARGET, "[trigger_metered_node_sync] called");
+    let config = ConfigCore::content().await;
+    if state
+        .node_manager
+        .is_local_sync_allowed(*config.metered_connection())
+    {
+        info!(target: LOG_TARGET, "[trigger_metered_node_sync] local node sync is not paused");
+        return Ok(());
+    }
+
+    state.node_manager.set_metered_sync_triggered(true);
+    state
+        .node_manager
+        .set_node_type(config.node_type().clone())
+        .await;
+
+    SetupManager::get_instance()
+        .restart_phases(
+            app_handle,
+            vec![SetupPhase::Node, SetupPhase::Wallet, SetupPhase::Mining],
+        )
+        .await;
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn create_pin(app_handle: tauri::AppHandle) -> Result<(), String> {
     InternalWallet::create_pin(&app_handle)
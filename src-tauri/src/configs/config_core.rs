@@ -23,14 +23,16 @@
 use getset::{Getters, Setters};
 use semver::Version;
 use serde::{Deserialize, Serialize};
-use std::{sync::LazyLock, time::SystemTime};
+use std::{collections::HashMap, sync::LazyLock, time::SystemTime};
 use tari_common::configuration::Network;
 use tauri::AppHandle;
 use tokio::sync::RwLock;
 
 use crate::ab_test_selector::ABTestSelector;
 use crate::app_in_memory_config::{MinerType, DEFAULT_EXCHANGE_ID};
-use crate::node::node_manager::NodeType;
+use crate::node::node_manager::{NodeStorageMode, NodeType};
+use crate::node::node_snapshot::NodeSnapshotSource;
+use crate::tor_adapter::BandwidthLimits;
 use crate::utils::rand_utils;
 
 use super::trait_config::{ConfigContentImpl, ConfigImpl};
@@ -68,7 +70,20 @@ pub struct ConfigCoreContent {
     last_changelog_version: Version,
     airdrop_tokens: Option<AirdropTokens>,
     remote_base_node_address: String,
+    // Fallback endpoints used when the primary remote base node is unhealthy
+    remote_base_node_fallback_addresses: Vec<String>,
     node_type: NodeType,
+    // Go back to the remote node when the local one becomes unhealthy after RemoteUntilLocal switched to it
+    fallback_to_remote_node: bool,
+    node_storage_mode: NodeStorageMode,
+    node_snapshot_source: Option<NodeSnapshotSource>,
+    node_config_overrides: HashMap<String, String>,
+    node_seed_peers: Vec<String>,
+    // Hosts of peers serving a stale chain or banned by the user, the local node doesn't dial them
+    node_banned_peers: Vec<String>,
+    bandwidth_limits: BandwidthLimits,
+    // Keep the local node from syncing until the user triggers it, to save data on capped links
+    metered_connection: bool,
     exchange_id: String,
 }
 
@@ -123,13 +138,34 @@ impl Default for ConfigCoreContent {
             last_changelog_version: Version::new(0, 0, 0),
             airdrop_tokens: None,
             remote_base_node_address,
+            remote_base_node_fallback_addresses: Vec::new(),
             node_type: NodeType::default(),
+            fallback_to_remote_node: true,
+            node_storage_mode: NodeStorageMode::default(),
+            node_snapshot_source: None,
+            node_config_overrides: HashMap::new(),
+            node_seed_peers: Vec::new(),
+            node_banned_peers: Vec::new(),
+            bandwidth_limits: BandwidthLimits::default(),
+            metered_connection: false,
             exchange_id: DEFAULT_EXCHANGE_ID.to_string(),
         }
     }
 }
 impl ConfigContentImpl for ConfigCoreContent {}
 impl ConfigCoreContent {
+    /// Primary remote node first, then the fallbacks. Peer bans only apply to the local node's
+    /// p2p connections, so they never remove a configured remote node.
+    pub fn remote_base_node_addresses(&self) -> Vec<String> {
+        let mut addresses = vec![self.remote_base_node_address.clone()];
+        for address in &self.remote_base_node_fallback_addresses {
+            if !addresses.contains(address) {
+                addresses.push(address.clone());
+            }
+        }
+        addresses
+    }
+
     pub fn is_on_exchange_specific_variant(&self) -> bool {
         MinerType::from_str(&self.exchange_id).is_exchange_mode()
     }
@@ -20,7 +20,9 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use crate::cpu_miner_backend::CpuMinerBackend;
 use crate::gpu_miner::EngineType;
+use crate::xmrig_adapter::XmrigTuning;
 use std::{collections::HashMap, sync::LazyLock, time::SystemTime};
 
 use getset::{Getters, Setters};
@@ -49,12 +51,41 @@ pub struct MiningMode {
     pub gpu_usage_percentage: u32,
 }
 
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
 pub struct GpuDeviceSettings {
     device_id: u32,
     is_excluded: bool,
+    /// Percentage overriding the mining mode gpu usage for this device
+    intensity: Option<u32>,
+    /// Explicit grid size, takes precedence over intensity
+    grid_size: Option<u32>,
 }
-#[derive(Serialize, Deserialize, Clone)]
+
+impl GpuDeviceSettings {
+    pub fn new(device_id: u32, intensity: Option<u32>, grid_size: Option<u32>) -> Self {
+        Self {
+            device_id,
+            is_excluded: false,
+            intensity,
+            grid_size,
+        }
+    }
+
+    pub fn is_excluded(&self) -> bool {
+        self.is_excluded
+    }
+
+    pub fn intensity(&self) -> Option<u32> {
+        self.intensity
+    }
+
+    pub fn grid_size(&self) -> Option<u32> {
+        self.grid_size
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct GpuDevicesSettings(HashMap<u32, GpuDeviceSettings>);
 
 impl GpuDevicesSettings {
@@ -63,13 +94,35 @@ impl GpuDevicesSettings {
     }
 
     pub fn add(&mut self, device_id: u32) {
-        self.0.entry(device_id).or_default();
+        self.0
+            .entry(device_id)
+            .or_insert_with(|| GpuDeviceSettings {
+                device_id,
+                ..Default::default()
+            });
+    }
+    pub fn get(&self, device_id: u32) -> Option<&GpuDeviceSettings> {
+        self.0.get(&device_id)
     }
     pub fn set_excluded(&mut self, device_id: u32, is_excluded: bool) {
         if let Some(settings) = self.0.get_mut(&device_id) {
             settings.is_excluded = is_excluded;
         }
     }
+    /// Updates the mining parameters of a device, keeping its exclusion state
+    pub fn set_mining_parameters(&mut self, settings: GpuDeviceSettings) {
+        let is_excluded = self
+            .0
+            .get(&settings.device_id)
+            .is_some_and(|current| current.is_excluded);
+        self.0.insert(
+            settings.device_id,
+            GpuDeviceSettings {
+                is_excluded,
+                ..settings
+            },
+        );
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -86,9 +139,14 @@ pub struct ConfigMiningContent {
     mine_on_app_start: bool,
     gpu_mining_enabled: bool,
     cpu_mining_enabled: bool,
+    cpu_miner_backend: CpuMinerBackend,
+    xmrig_tuning: XmrigTuning,
     gpu_engine: EngineType,
     gpu_devices_settings: GpuDevicesSettings,
     squad_override: Option<String>,
+    stratum_hub_enabled: bool,
+    stratum_hub_port: u16,
+    stratum_hub_sha3x_port: u16,
 }
 
 impl Default for ConfigMiningContent {
@@ -129,9 +187,14 @@ impl Default for ConfigMiningContent {
             ]),
             gpu_mining_enabled: true,
             cpu_mining_enabled: true,
+            cpu_miner_backend: CpuMinerBackend::default(),
+            xmrig_tuning: XmrigTuning::default(),
             gpu_engine: EngineType::OpenCL,
             gpu_devices_settings: GpuDevicesSettings::new(),
             squad_override: None,
+            stratum_hub_enabled: false,
+            stratum_hub_port: 3333,
+            stratum_hub_sha3x_port: 3334,
         }
     }
 }
@@ -171,6 +234,11 @@ impl ConfigMiningContent {
         self
     }
 
+    pub fn set_gpu_device_mining_parameters(&mut self, settings: GpuDeviceSettings) -> &mut Self {
+        self.gpu_devices_settings.set_mining_parameters(settings);
+        self
+    }
+
     pub fn get_selected_cpu_usage_percentage(&self) -> u32 {
         match self.mining_modes.get(&self.selected_mining_mode) {
             Some(mode) => mode.cpu_usage_percentage,
@@ -160,6 +160,9 @@ pub struct ConfigPoolsContent {
     // ======= Cpu Pool =======
     cpu_pool_enabled: bool,
     cpu_pool: CpuPool,
+    // ======= Auto switching between solo and pool =======
+    auto_switch_enabled: bool,
+    auto_switch_threshold_percent: u32,
 }
 
 impl Default for ConfigPoolsContent {
@@ -174,6 +177,9 @@ impl Default for ConfigPoolsContent {
             // ======= Cpu Pool =======
             cpu_pool_enabled: true,
             cpu_pool: CpuPool::GlobalTariPool(GlobalTariCpuPoolConfig::default()),
+            // ======= Auto switching between solo and pool =======
+            auto_switch_enabled: false,
+            auto_switch_threshold_percent: 25,
         }
     }
 }
@@ -42,6 +42,7 @@ pub enum WalletUIMode {
     Standard = 0,
     Seedless = 1,
     ExchangeSpecificMiner = 2,
+    WatchOnly = 3,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
@@ -128,6 +129,7 @@ impl ConfigUI {
             .is_on_exchange_specific_variant();
         let mode = match tari_address_type {
             TariAddressType::Internal => WalletUIMode::Standard,
+            TariAddressType::WatchOnly => WalletUIMode::WatchOnly,
             TariAddressType::External => {
                 if is_on_exchange_miner_specific_variant {
                     WalletUIMode::ExchangeSpecificMiner
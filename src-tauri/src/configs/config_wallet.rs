@@ -33,6 +33,9 @@ use crate::{
     configs::config_ui::{ConfigUI, ConfigUIContent},
     internal_wallet::TariWalletDetails,
     pin::PinLockerState,
+    wallet::payments_scheduler::{
+        ScheduledPayment, ScheduledPaymentRecord, MAX_PAYMENTS_HISTORY_LENGTH,
+    },
 };
 
 use super::trait_config::{ConfigContentImpl, ConfigImpl};
@@ -63,6 +66,15 @@ impl WalletId {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TariWalletRecord {
+    pub name: String,
+    pub details: TariWalletDetails,
+    /// Seed is still enciphered with a PIN that was forgotten, the seed words have to be imported again
+    #[serde(default)]
+    pub is_locked: bool,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "snake_case")]
 #[serde(default)]
@@ -89,12 +101,24 @@ pub struct ConfigWalletContent {
     selected_external_tari_address: Option<TariAddress>,
     #[getset(get = "pub", set = "pub")]
     tari_wallet_details: Option<TariWalletDetails>,
+    #[getset(get = "pub")]
+    tari_wallet_records: Vec<TariWalletRecord>,
+    #[getset(get = "pub", set = "pub")]
+    mining_rewards_wallet_id: Option<WalletId>,
+    #[getset(get = "pub", set = "pub")]
+    watch_only_wallet_details: Option<TariWalletDetails>,
     #[getset(get = "pub", set = "pub")]
     pin_locker_state: PinLockerState,
     #[getset(get = "pub", set = "pub")]
     seed_backed_up: bool,
     #[getset(get = "pub", set = "pub")]
     last_known_balance: MicroMinotari,
+    #[getset(get = "pub")]
+    scheduled_payments: Vec<ScheduledPayment>,
+    #[getset(get = "pub")]
+    scheduled_payments_history: Vec<ScheduledPaymentRecord>,
+    #[getset(get = "pub", set = "pub")]
+    payments_min_reserve: MicroMinotari,
 }
 
 impl Default for ConfigWalletContent {
@@ -110,10 +134,16 @@ impl Default for ConfigWalletContent {
             created_at: SystemTime::now(),
             selected_external_tari_address: None, // Takes precedence over an owned address
             external_tari_addresses_book: HashMap::new(),
-            tari_wallet_details: None, // Owned tari address details
+            tari_wallet_details: None,       // Owned tari address details
+            tari_wallet_records: Vec::new(), // Named details of every owned wallet
+            mining_rewards_wallet_id: None,  // Falls back to the selected wallet
+            watch_only_wallet_details: None, // Takes precedence over owned wallets
             pin_locker_state: PinLockerState::default(),
             seed_backed_up: false,
             last_known_balance: MicroMinotari(0),
+            scheduled_payments: Vec::new(),
+            scheduled_payments_history: Vec::new(), // Most recent first
+            payments_min_reserve: MicroMinotari(0),
         }
     }
 }
@@ -156,8 +186,26 @@ impl ConfigWalletContent {
     pub fn add_tari_wallet(&mut self, selected_wallet_details: TariWalletDetails) -> &mut Self {
         // Deselect the external Tari address because a new address is now selected by default
         self.selected_external_tari_address = None;
+        self.tari_wallets
+            .retain(|id| *id != selected_wallet_details.id);
         self.tari_wallets
             .insert(0, selected_wallet_details.id.clone());
+        if let Some(record) = self
+            .tari_wallet_records
+            .iter_mut()
+            .find(|r| r.details.id == selected_wallet_details.id)
+        {
+            // The same seed was imported again
+            record.details = selected_wallet_details.clone();
+            record.is_locked = false;
+        } else {
+            let name = format!("Wallet {}", self.tari_wallet_records.len() + 1);
+            self.tari_wallet_records.push(TariWalletRecord {
+                name,
+                details: selected_wallet_details.clone(),
+                is_locked: false,
+            });
+        }
         self.tari_wallet_details = Some(selected_wallet_details);
 
         // Remove when we decide not to autoselect
@@ -165,6 +213,107 @@ impl ConfigWalletContent {
 
         self
     }
+
+    // Backfills the record of a wallet created before wallets could be named
+    pub fn ensure_tari_wallet_record(&mut self, wallet_details: TariWalletDetails) -> &mut Self {
+        if !self
+            .tari_wallet_records
+            .iter()
+            .any(|r| r.details.id == wallet_details.id)
+        {
+            self.tari_wallet_records.push(TariWalletRecord {
+                name: "Main".to_string(),
+                details: wallet_details,
+                is_locked: false,
+            });
+        }
+        self
+    }
+
+    pub fn set_tari_wallet_name(&mut self, payload: (WalletId, String)) -> &mut Self {
+        let (wallet_id, name) = payload;
+        if let Some(record) = self
+            .tari_wallet_records
+            .iter_mut()
+            .find(|r| r.details.id == wallet_id)
+        {
+            record.name = name;
+        }
+        self
+    }
+
+    // The selected wallet is always kept first in `tari_wallets`
+    pub fn select_tari_wallet(&mut self, wallet_id: WalletId) -> &mut Self {
+        if let Some(record) = self
+            .tari_wallet_records
+            .iter()
+            .find(|r| r.details.id == wallet_id)
+        {
+            self.selected_external_tari_address = None;
+            self.tari_wallet_details = Some(record.details.clone());
+            self.tari_wallets.retain(|id| *id != wallet_id);
+            self.tari_wallets.insert(0, wallet_id);
+        }
+        self
+    }
+
+    pub fn lock_other_tari_wallets(&mut self, unlocked_wallet_id: WalletId) -> &mut Self {
+        for record in self
+            .tari_wallet_records
+            .iter_mut()
+            .filter(|r| r.details.id != unlocked_wallet_id)
+        {
+            record.is_locked = true;
+        }
+        self
+    }
+
+    pub fn remove_tari_wallet(&mut self, wallet_id: WalletId) -> &mut Self {
+        self.tari_wallets.retain(|id| *id != wallet_id);
+        self.tari_wallet_records
+            .retain(|r| r.details.id != wallet_id);
+        if self.mining_rewards_wallet_id.as_ref() == Some(&wallet_id) {
+            self.mining_rewards_wallet_id = None;
+        }
+        self
+    }
+
+    pub fn add_scheduled_payment(&mut self, payment: ScheduledPayment) -> &mut Self {
+        self.scheduled_payments.retain(|p| p.id != payment.id);
+        self.scheduled_payments.push(payment);
+        self
+    }
+
+    pub fn remove_scheduled_payment(&mut self, payment_id: String) -> &mut Self {
+        self.scheduled_payments.retain(|p| p.id != payment_id);
+        self
+    }
+
+    pub fn set_scheduled_payment_enabled(&mut self, payload: (String, bool)) -> &mut Self {
+        let (payment_id, enabled) = payload;
+        if let Some(payment) = self
+            .scheduled_payments
+            .iter_mut()
+            .find(|p| p.id == payment_id)
+        {
+            payment.enabled = enabled;
+        }
+        self
+    }
+
+    pub fn record_scheduled_payment(&mut self, record: ScheduledPaymentRecord) -> &mut Self {
+        if let Some(payment) = self
+            .scheduled_payments
+            .iter_mut()
+            .find(|p| p.id == record.scheduled_payment_id)
+        {
+            payment.last_run_at = Some(record.executed_at);
+        }
+        self.scheduled_payments_history.insert(0, record);
+        self.scheduled_payments_history
+            .truncate(MAX_PAYMENTS_HISTORY_LENGTH);
+        self
+    }
 }
 
 pub struct ConfigWallet {
@@ -20,18 +20,20 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use crate::binaries::Binaries;
 use crate::commands::{CpuMinerConnection, CpuMinerConnectionStatus, CpuMinerStatus};
+use crate::configs::config_mining::ConfigMining;
 use crate::configs::config_pools::{ConfigPoolsContent, CpuPool};
 use crate::configs::config_wallet::ConfigWalletContent;
+use crate::configs::trait_config::ConfigImpl;
+use crate::cpu_miner_backend::{
+    CpuMinerAdapter, CpuMinerBackend, CpuMinerNodeConnection, CpuMinerSummary,
+};
 use crate::events_emitter::EventsEmitter;
 use crate::pool_status_watcher::SupportXmrPoolAdapter;
 use crate::process_stats_collector::ProcessStatsCollectorBuilder;
 use crate::process_watcher::ProcessWatcher;
 use crate::tasks_tracker::TasksTrackers;
 use crate::utils::math_utils::estimate_earning;
-use crate::xmrig::http_api::models::Summary;
-use crate::xmrig_adapter::{XmrigAdapter, XmrigNodeConnection};
 use crate::{mm_proxy_manager, BaseNodeStatus, PoolStatusWatcher};
 use log::{debug, error, info, warn};
 use std::path::PathBuf;
@@ -95,9 +97,10 @@ impl CpuMinerConfig {
 }
 
 pub(crate) struct CpuMiner {
-    watcher: Arc<RwLock<ProcessWatcher<XmrigAdapter>>>,
+    watcher: Arc<RwLock<ProcessWatcher<CpuMinerAdapter>>>,
     cpu_miner_status_watch_tx: watch::Sender<CpuMinerStatus>,
-    summary_watch_rx: watch::Receiver<Option<Summary>>,
+    summary_watch_tx: watch::Sender<Option<CpuMinerSummary>>,
+    summary_watch_rx: watch::Receiver<Option<CpuMinerSummary>>,
     node_status_watch_rx: watch::Receiver<BaseNodeStatus>,
     pool_status_watcher: Option<PoolStatusWatcher<SupportXmrPoolAdapter>>,
     pub pool_status_shutdown_signal: Shutdown,
@@ -109,12 +112,13 @@ impl CpuMiner {
         cpu_miner_status_watch_tx: watch::Sender<CpuMinerStatus>,
         node_status_watch_rx: watch::Receiver<BaseNodeStatus>,
     ) -> Self {
-        let (summary_watch_tx, summary_watch_rx) = watch::channel::<Option<Summary>>(None);
-        let xmrig_adapter = XmrigAdapter::new(summary_watch_tx);
-        let process_watcher = ProcessWatcher::new(xmrig_adapter, stats_collector.take_cpu_miner());
+        let (summary_watch_tx, summary_watch_rx) = watch::channel::<Option<CpuMinerSummary>>(None);
+        let adapter = CpuMinerAdapter::new(CpuMinerBackend::default(), summary_watch_tx.clone());
+        let process_watcher = ProcessWatcher::new(adapter, stats_collector.take_cpu_miner());
         Self {
             watcher: Arc::new(RwLock::new(process_watcher)),
             cpu_miner_status_watch_tx,
+            summary_watch_tx,
             summary_watch_rx,
             node_status_watch_rx,
             pool_status_watcher: None,
@@ -137,9 +141,9 @@ impl CpuMiner {
     ) -> Result<(), anyhow::Error> {
         self.pool_status_shutdown_signal = Shutdown::new();
 
-        let (xmrig_node_connection, pool_watcher) = match cpu_miner_config.node_connection {
+        let (node_connection, pool_watcher) = match cpu_miner_config.node_connection {
             CpuMinerConnection::BuiltInProxy => (
-                XmrigNodeConnection::LocalMmproxy {
+                CpuMinerNodeConnection::LocalMmproxy {
                     host_name: "127.0.0.1".to_string(),
                     port: mm_proxy_manager.get_monero_port().await?,
                     monero_address: cpu_miner_config.monero_address.clone(),
@@ -166,7 +170,7 @@ impl CpuMiner {
                 });
 
                 (
-                    XmrigNodeConnection::Pool {
+                    CpuMinerNodeConnection::Pool {
                         host_name: pool_address,
                         port,
                         tari_address: tari_address.to_base58(),
@@ -193,7 +197,7 @@ impl CpuMiner {
                 });
 
                 (
-                    XmrigNodeConnection::MergeMinedPool {
+                    CpuMinerNodeConnection::MergeMinedPool {
                         host_name: pool_address,
                         port,
                         monero_address: cpu_miner_config.monero_address.clone(),
@@ -222,11 +226,19 @@ impl CpuMiner {
 
         info!(target: LOG_TARGET, "Using {cpu_cores_to_use} CPU cores for mining");
 
+        let mining_config = ConfigMining::content().await;
+        let backend = *mining_config.cpu_miner_backend();
         {
             let mut lock = self.watcher.write().await;
 
-            lock.adapter.node_connection = Some(xmrig_node_connection);
-            lock.adapter.cpu_threads = Some(cpu_cores_to_use);
+            if lock.adapter.backend() != backend {
+                info!(target: LOG_TARGET, "Switching CPU miner backend to {backend}");
+                lock.adapter = CpuMinerAdapter::new(backend, self.summary_watch_tx.clone());
+            }
+            lock.adapter
+                .configure(node_connection, Some(cpu_cores_to_use));
+            lock.adapter
+                .set_xmrig_tuning(mining_config.xmrig_tuning().clone());
 
             let shutdown_signal = TasksTrackers::current().hardware_phase.get_signal().await;
             let task_tracker = TasksTrackers::current()
@@ -238,7 +250,7 @@ impl CpuMiner {
                 base_path.clone(),
                 config_path.clone(),
                 log_dir.clone(),
-                Binaries::Xmrig,
+                backend.binary(),
                 shutdown_signal,
                 task_tracker,
             )
@@ -260,6 +272,10 @@ impl CpuMiner {
         Ok(())
     }
 
+    pub fn get_summary(&self) -> Option<CpuMinerSummary> {
+        self.summary_watch_rx.borrow().clone()
+    }
+
     pub async fn is_running(&self) -> bool {
         let lock = self.watcher.read().await;
         lock.is_running()
@@ -267,7 +283,7 @@ impl CpuMiner {
 
     pub async fn get_port(&self) -> u16 {
         let lock = self.watcher.read().await;
-        lock.adapter.http_api_port
+        lock.adapter.http_api_port()
     }
     #[allow(dead_code)]
     pub async fn is_pid_file_exists(&self, base_path: PathBuf) -> bool {
@@ -308,21 +324,15 @@ impl CpuMiner {
                     }
                     _ = summary_watch_rx.changed() => {
                         let node_status = *node_status_watch_rx.borrow();
-                        let xmrig_summary = summary_watch_rx.borrow().clone();
+                        let miner_summary = summary_watch_rx.borrow().clone();
 
-                        let cpu_status = match xmrig_summary {
-                            Some(xmrig_status) => {
-                                let hash_rate = xmrig_status.hashrate.total[0].unwrap_or_default();
+                        let cpu_status = match miner_summary {
+                            Some(summary) => {
+                                let hash_rate = summary.hash_rate;
                                 let estimated_earnings =
                                     estimate_earning(node_status.monero_randomx_network_hashrate, hash_rate, node_status.block_reward);
 
-                                // // UNUSED, commented for now
-                                // let hasrate_sum = xmrig_status
-                                //     .hashrate
-                                //     .total
-                                //     .iter()
-                                //     .fold(0.0, |acc, x| acc + x.unwrap_or(0.0));
-                                let is_connected = xmrig_status.connection.uptime > 0;
+                                let is_connected = summary.is_connected;
                                 // dbg!(&last_pool_status);
 
 
@@ -335,7 +345,7 @@ impl CpuMiner {
                                 }
                             }
                             None => {
-                                warn!(target: LOG_TARGET, "Failed to get xmrig summary");
+                                warn!(target: LOG_TARGET, "Failed to get CPU miner summary");
                                 CpuMinerStatus::default()
                             }
                         };
@@ -0,0 +1,195 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{fmt::Display, path::PathBuf, time::Duration};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+
+use crate::{
+    binaries::Binaries,
+    process_adapter::{HealthStatus, ProcessAdapter, ProcessInstance, StatusMonitor},
+    xmrig_adapter::{XmrigAdapter, XmrigStatusMonitor, XmrigTuning},
+};
+
+/// RandomX miners the CPU miner can drive. Every backend is a binary managed by the binaries
+/// resolver, so adding one means a `Binaries` entry, an adapter and a variant here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum CpuMinerBackend {
+    #[default]
+    Xmrig,
+}
+
+impl Display for CpuMinerBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CpuMinerBackend::Xmrig => write!(f, "xmrig"),
+        }
+    }
+}
+
+impl CpuMinerBackend {
+    pub fn from_string(backend: &str) -> Result<CpuMinerBackend, anyhow::Error> {
+        match backend {
+            "xmrig" => Ok(CpuMinerBackend::Xmrig),
+            _ => Err(anyhow::anyhow!("Invalid CPU miner backend")),
+        }
+    }
+
+    pub fn all() -> Vec<CpuMinerBackend> {
+        vec![CpuMinerBackend::Xmrig]
+    }
+
+    pub fn binary(self) -> Binaries {
+        match self {
+            CpuMinerBackend::Xmrig => Binaries::Xmrig,
+        }
+    }
+}
+
+/// Where the CPU miner sends its work. Each backend turns this into its own arguments.
+pub enum CpuMinerNodeConnection {
+    LocalMmproxy {
+        host_name: String,
+        port: u16,
+        monero_address: String,
+    },
+    Pool {
+        host_name: String,
+        port: u16,
+        tari_address: String,
+    },
+    MergeMinedPool {
+        host_name: String,
+        port: u16,
+        monero_address: String,
+        tari_address: String,
+    },
+}
+
+/// Backend independent view of the miner's status api
+#[derive(Debug, Clone, Default)]
+pub struct CpuMinerSummary {
+    pub hash_rate: f64,
+    pub is_connected: bool,
+    /// When mining to the local mm proxy every share is a block submission
+    pub shares_good: u64,
+    pub shares_total: u64,
+}
+
+pub enum CpuMinerAdapter {
+    Xmrig(XmrigAdapter),
+}
+
+impl CpuMinerAdapter {
+    pub fn new(
+        backend: CpuMinerBackend,
+        summary_broadcast: watch::Sender<Option<CpuMinerSummary>>,
+    ) -> Self {
+        match backend {
+            CpuMinerBackend::Xmrig => CpuMinerAdapter::Xmrig(XmrigAdapter::new(summary_broadcast)),
+        }
+    }
+
+    pub fn backend(&self) -> CpuMinerBackend {
+        match self {
+            CpuMinerAdapter::Xmrig(_) => CpuMinerBackend::Xmrig,
+        }
+    }
+
+    pub fn configure(&mut self, node_connection: CpuMinerNodeConnection, cpu_threads: Option<u32>) {
+        match self {
+            CpuMinerAdapter::Xmrig(adapter) => {
+                adapter.node_connection = Some(node_connection);
+                adapter.cpu_threads = cpu_threads;
+            }
+        }
+    }
+
+    pub fn set_xmrig_tuning(&mut self, tuning: XmrigTuning) {
+        match self {
+            CpuMinerAdapter::Xmrig(adapter) => adapter.tuning = tuning,
+        }
+    }
+
+    pub fn http_api_port(&self) -> u16 {
+        match self {
+            CpuMinerAdapter::Xmrig(adapter) => adapter.http_api_port,
+        }
+    }
+}
+
+impl ProcessAdapter for CpuMinerAdapter {
+    type StatusMonitor = CpuMinerStatusMonitor;
+    type ProcessInstance = ProcessInstance;
+
+    fn spawn_inner(
+        &self,
+        data_dir: PathBuf,
+        config_dir: PathBuf,
+        log_dir: PathBuf,
+        binary_version_path: PathBuf,
+        is_first_start: bool,
+    ) -> Result<(ProcessInstance, Self::StatusMonitor), anyhow::Error> {
+        match self {
+            CpuMinerAdapter::Xmrig(adapter) => {
+                let (instance, monitor) = adapter.spawn_inner(
+                    data_dir,
+                    config_dir,
+                    log_dir,
+                    binary_version_path,
+                    is_first_start,
+                )?;
+                Ok((instance, CpuMinerStatusMonitor::Xmrig(monitor)))
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            CpuMinerAdapter::Xmrig(adapter) => adapter.name(),
+        }
+    }
+
+    fn pid_file_name(&self) -> &str {
+        match self {
+            CpuMinerAdapter::Xmrig(adapter) => adapter.pid_file_name(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum CpuMinerStatusMonitor {
+    Xmrig(XmrigStatusMonitor),
+}
+
+#[async_trait]
+impl StatusMonitor for CpuMinerStatusMonitor {
+    async fn check_health(&self, uptime: Duration, timeout_duration: Duration) -> HealthStatus {
+        match self {
+            CpuMinerStatusMonitor::Xmrig(monitor) => {
+                monitor.check_health(uptime, timeout_duration).await
+            }
+        }
+    }
+}
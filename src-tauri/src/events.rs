@@ -55,6 +55,7 @@ pub enum EventType {
     #[cfg(target_os = "windows")]
     MissingApplications,
     StuckOnOrphanChain,
+    OrphanChainResyncRequired,
     NetworkStatus,
     CorePhaseFinished,
     WalletPhaseFinished,
@@ -88,6 +89,8 @@ pub enum EventType {
     CreatePin,
     EnterPin,
     UpdateGpuDevicesSettings,
+    ScheduledPaymentExecuted,
+    ScheduledPaymentPendingPin,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -97,6 +100,7 @@ pub enum ProgressEvents {
     Hardware,
     Node,
     Mining,
+    NodeMaintenance,
 }
 #[derive(Clone, Debug, Serialize)]
 pub struct ProgressTrackerUpdatePayload {
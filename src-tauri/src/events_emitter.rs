@@ -30,6 +30,7 @@ use crate::external_dependencies::RequiredExternalDependency;
 use crate::gpu_devices::GpuDeviceInformation;
 use crate::internal_wallet::TariAddressType;
 use crate::pool_status_watcher::PoolStatus;
+use crate::wallet::payments_scheduler::ScheduledPaymentRecord;
 use crate::wallet::wallet_types::{TransactionInfo, WalletBalance};
 use crate::{
     commands::CpuMinerStatus,
@@ -119,6 +120,21 @@ impl EventsEmitter {
         }
     }
 
+    /// The node can't rewind off the orphan chain, the payload is the fork depth if known
+    pub async fn emit_orphan_chain_resync_required(reorg_depth: Option<u64>) {
+        let _unused = FrontendReadyChannel::current().wait_for_ready().await;
+        let event = Event {
+            event_type: EventType::OrphanChainResyncRequired,
+            payload: reorg_depth,
+        };
+        if let Err(e) = Self::get_app_handle()
+            .await
+            .emit(BACKEND_STATE_UPDATE, event)
+        {
+            error!(target: LOG_TARGET, "Failed to emit OrphanChainResyncRequired event: {e:?}");
+        }
+    }
+
     pub async fn emit_show_release_notes(payload: ShowReleaseNotesPayload) {
         let _unused = FrontendReadyChannel::current().wait_for_ready().await;
         let event = Event {
@@ -857,4 +873,32 @@ impl EventsEmitter {
             error!(target: LOG_TARGET, "Failed to emit UpdateDevicesSettings event: {e:?}");
         }
     }
+
+    pub async fn emit_scheduled_payment_executed(payload: ScheduledPaymentRecord) {
+        let _unused = FrontendReadyChannel::current().wait_for_ready().await;
+        let event = Event {
+            event_type: EventType::ScheduledPaymentExecuted,
+            payload,
+        };
+        if let Err(e) = Self::get_app_handle()
+            .await
+            .emit(BACKEND_STATE_UPDATE, event)
+        {
+            error!(target: LOG_TARGET, "Failed to emit ScheduledPaymentExecuted event: {e:?}");
+        }
+    }
+
+    pub async fn emit_scheduled_payment_pending_pin(payload: ScheduledPaymentRecord) {
+        let _unused = FrontendReadyChannel::current().wait_for_ready().await;
+        let event = Event {
+            event_type: EventType::ScheduledPaymentPendingPin,
+            payload,
+        };
+        if let Err(e) = Self::get_app_handle()
+            .await
+            .emit(BACKEND_STATE_UPDATE, event)
+        {
+            error!(target: LOG_TARGET, "Failed to emit ScheduledPaymentPendingPin event: {e:?}");
+        }
+    }
 }
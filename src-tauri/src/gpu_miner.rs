@@ -34,6 +34,8 @@ use tokio::select;
 use tokio::sync::{watch, RwLock};
 
 use crate::binaries::{Binaries, BinaryResolver};
+use crate::configs::config_mining::ConfigMining;
+use crate::configs::trait_config::ConfigImpl;
 use crate::events_emitter::EventsEmitter;
 use crate::gpu_miner_adapter::GpuNodeSource;
 use crate::gpu_status_file::{GpuDevice, GpuStatusFile};
@@ -136,6 +138,8 @@ impl GpuMiner {
         process_watcher.adapter.node_source = Some(node_source);
         process_watcher.adapter.coinbase_extra = coinbase_extra;
         process_watcher.adapter.gpu_usage_percentage = gpu_usage_percentage;
+        process_watcher.adapter.gpu_devices_settings =
+            ConfigMining::content().await.gpu_devices_settings().clone();
         info!(target: LOG_TARGET, "Starting xtrgpuminer");
         process_watcher
             .start(
@@ -20,8 +20,9 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use crate::configs::config_mining::{GpuDeviceSettings, GpuDevicesSettings};
 use crate::gpu_miner::EngineType;
-use crate::gpu_status_file::GpuDevice;
+use crate::gpu_status_file::{GpuDevice, GpuStatusFile};
 use crate::port_allocator::PortAllocator;
 use crate::process_adapter::HealthStatus;
 use crate::process_adapter::ProcessStartupSpec;
@@ -32,7 +33,7 @@ use log::{info, warn};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::ops::Div;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tari_common::configuration::Network;
 use tari_common_types::tari_address::TariAddress;
@@ -56,6 +57,7 @@ pub(crate) struct GpuMinerAdapter {
     pub(crate) coinbase_extra: String,
     pub(crate) gpu_devices: Vec<GpuDevice>,
     pub(crate) gpu_usage_percentage: u32,
+    pub(crate) gpu_devices_settings: GpuDevicesSettings,
     pub(crate) gpu_raw_status_broadcast: watch::Sender<Option<GpuMinerStatus>>,
     pub(crate) curent_selected_engine: EngineType,
     pub http_api_port: u16,
@@ -73,10 +75,51 @@ impl GpuMinerAdapter {
             gpu_devices,
             gpu_raw_status_broadcast,
             gpu_usage_percentage: 0,
+            gpu_devices_settings: GpuDevicesSettings::new(),
             curent_selected_engine: EngineType::OpenCL,
             http_api_port: PortAllocator::new().assign_port_with_fallback(),
         }
     }
+
+    fn is_device_excluded(&self, device: &GpuDevice) -> bool {
+        self.gpu_devices_settings
+            .get(device.device_index)
+            .is_some_and(GpuDeviceSettings::is_excluded)
+    }
+
+    fn device_grid_size(&self, device: &GpuDevice) -> u32 {
+        let settings = self.gpu_devices_settings.get(device.device_index);
+        let grid_size = match settings.and_then(|s| s.grid_size()) {
+            Some(grid_size) => grid_size,
+            None => {
+                let intensity = settings
+                    .and_then(|s| s.intensity())
+                    .unwrap_or(self.gpu_usage_percentage);
+                DEFAULT_GPU_THREADS.saturating_mul(intensity).div(100)
+            }
+        };
+        if device.status.max_grid_size > 0 {
+            grid_size.min(device.status.max_grid_size)
+        } else {
+            grid_size
+        }
+    }
+
+    /// Glytex reads device exclusions from the status file of the engine it runs with
+    fn write_gpu_status_file(&self, gpu_engine_statuses: &Path) -> Result<(), Error> {
+        let gpu_devices = self
+            .gpu_devices
+            .iter()
+            .map(|device| {
+                let mut device = device.clone();
+                device.settings.is_excluded = self.is_device_excluded(&device);
+                device
+            })
+            .collect();
+        let gpu_status_file_path =
+            gpu_engine_statuses.join(format!("{}_gpu_status.json", self.curent_selected_engine));
+        GpuStatusFile::save(GpuStatusFile { gpu_devices }, &gpu_status_file_path)
+    }
 }
 
 impl ProcessAdapter for GpuMinerAdapter {
@@ -111,23 +154,16 @@ impl ProcessAdapter for GpuMinerAdapter {
             }
         };
 
-        let gpu_engine_statuses = config_dir
-            .join("gpuminer")
-            .join("engine_statuses")
-            .clone()
-            .to_string_lossy()
-            .to_string();
+        let gpu_engine_statuses_dir = config_dir.join("gpuminer").join("engine_statuses");
+        if !self.gpu_devices.is_empty() {
+            self.write_gpu_status_file(&gpu_engine_statuses_dir)?;
+        }
+        let gpu_engine_statuses = gpu_engine_statuses_dir.to_string_lossy().to_string();
 
         let grid_size = self
             .gpu_devices
-            .clone()
             .iter()
-            .map(|_| {
-                DEFAULT_GPU_THREADS
-                    .saturating_mul(self.gpu_usage_percentage)
-                    .div(100)
-                    .to_string()
-            })
+            .map(|device| self.device_grid_size(device).to_string())
             .collect::<Vec<String>>()
             .join(",");
 
@@ -332,3 +368,65 @@ pub(crate) struct GpuMinerStatus {
     pub hash_rate: f64,
     pub estimated_earnings: u64,
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::gpu_status_file::{GpuSettings, GpuStatus};
+
+    fn device(device_index: u32, max_grid_size: u32) -> GpuDevice {
+        GpuDevice {
+            device_name: format!("gpu{device_index}"),
+            device_index,
+            status: GpuStatus {
+                recommended_grid_size: 0,
+                recommended_block_size: 0,
+                max_grid_size,
+            },
+            settings: GpuSettings::default(),
+        }
+    }
+
+    fn adapter(settings: Vec<GpuDeviceSettings>) -> GpuMinerAdapter {
+        let mut adapter = GpuMinerAdapter::new(vec![], watch::channel(None).0);
+        adapter.gpu_usage_percentage = 50;
+        for device_settings in settings {
+            adapter
+                .gpu_devices_settings
+                .set_mining_parameters(device_settings);
+        }
+        adapter
+    }
+
+    #[test]
+    fn grid_size_takes_precedence_over_intensity() {
+        let adapter = adapter(vec![
+            GpuDeviceSettings::new(0, Some(10), Some(1000)),
+            GpuDeviceSettings::new(1, Some(10), None),
+        ]);
+
+        assert_eq!(adapter.device_grid_size(&device(0, 0)), 1000);
+        assert_eq!(
+            adapter.device_grid_size(&device(1, 0)),
+            DEFAULT_GPU_THREADS / 10
+        );
+        // No settings falls back to the mining mode gpu usage
+        assert_eq!(
+            adapter.device_grid_size(&device(2, 0)),
+            DEFAULT_GPU_THREADS / 2
+        );
+    }
+
+    #[test]
+    fn grid_size_is_clamped_to_max_grid_size() {
+        let adapter = adapter(vec![
+            GpuDeviceSettings::new(0, None, Some(5000)),
+            GpuDeviceSettings::new(1, Some(100), None),
+        ]);
+
+        assert_eq!(adapter.device_grid_size(&device(0, 2048)), 2048);
+        assert_eq!(adapter.device_grid_size(&device(1, 1024)), 1024);
+        assert_eq!(adapter.device_grid_size(&device(0, 8192)), 5000);
+    }
+}
@@ -72,7 +72,6 @@ impl GpuStatusFile {
         Ok(config)
     }
 
-    #[allow(dead_code)]
     pub fn save(new_content: GpuStatusFile, path: &Path) -> Result<(), anyhow::Error> {
         debug!("Updating gpu status file with {new_content:?}, at path: {path:?}");
         let content = serde_json::to_string_pretty(&new_content)?;
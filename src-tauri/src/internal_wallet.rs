@@ -29,7 +29,7 @@ use std::io::Read;
 use std::path::{Path, PathBuf};
 use tari_common::configuration::Network;
 use tari_common_types::tari_address::{TariAddress, TariAddressFeatures};
-use tari_common_types::types::CompressedPublicKey;
+use tari_common_types::types::{CompressedPublicKey, PrivateKey};
 use tari_crypto::ristretto::RistrettoPublicKey;
 use tari_key_manager::cipher_seed::CipherSeed;
 use tari_key_manager::key_manager::KeyManager;
@@ -50,7 +50,9 @@ use tari_core::transactions::transaction_key_manager::{
 use tari_utilities::hex::Hex;
 
 use crate::configs::config_ui::ConfigUI;
-use crate::configs::config_wallet::{ConfigWallet, ConfigWalletContent, WalletId, WALLET_VERSION};
+use crate::configs::config_wallet::{
+    ConfigWallet, ConfigWalletContent, TariWalletRecord, WalletId, WALLET_VERSION,
+};
 use crate::configs::trait_config::ConfigImpl;
 use crate::consts::DEFAULT_MONERO_ADDRESS;
 use crate::credential_manager::{
@@ -63,6 +65,7 @@ use crate::utils::{cryptography, rand_utils};
 use crate::UniverseAppState;
 
 const KEY_MANAGER_COMMS_SECRET_KEY_BRANCH_KEY: &str = "comms";
+const WATCH_ONLY_WALLET_ID: &str = "watch_only";
 const LOG_TARGET: &str = "tari::universe::internal_wallet";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -118,6 +121,14 @@ impl InternalWallet {
         )
     }
 
+    pub async fn is_watch_only() -> bool {
+        let internal_wallet_guard = InternalWallet::current().read().await;
+        matches!(
+            internal_wallet_guard.tari_address_type,
+            TariAddressType::WatchOnly
+        )
+    }
+
     pub async fn initialize_seedless(
         app_handle: &tauri::AppHandle,
         new_external_tari_address: Option<TariAddress>,
@@ -128,6 +139,8 @@ impl InternalWallet {
                 external_tari_address.clone(),
             )
             .await?;
+            ConfigWallet::update_field(ConfigWalletContent::set_watch_only_wallet_details, None)
+                .await?;
         }
 
         let wallet_config = ConfigWallet::content().await;
@@ -157,6 +170,76 @@ impl InternalWallet {
         internal_wallet.post_init(app_handle).await
     }
 
+    /** Watch-only wallet scans with the view key but has no seed, so it can never spend */
+    pub async fn initialize_watch_only(
+        app_handle: &tauri::AppHandle,
+        new_watch_only_wallet_details: Option<TariWalletDetails>,
+    ) -> Result<(), anyhow::Error> {
+        if let Some(watch_only_wallet_details) = new_watch_only_wallet_details {
+            ConfigWallet::update_field(
+                ConfigWalletContent::set_watch_only_wallet_details,
+                Some(watch_only_wallet_details),
+            )
+            .await?;
+        }
+        ConfigWallet::update_field(
+            ConfigWalletContent::set_selected_external_tari_address,
+            None,
+        )
+        .await?;
+
+        let wallet_config = ConfigWallet::content().await;
+        let watch_only_wallet_details = wallet_config
+            .watch_only_wallet_details()
+            .clone()
+            .ok_or_else(|| anyhow!("Watch-only wallet details not defined"))?;
+
+        let mut monero_seed_binary = None;
+        if wallet_config.monero_address().is_empty() {
+            let monero_seed = MoneroSeed::generate()?;
+            monero_seed_binary = Some(InternalWallet::add_monero_wallet(monero_seed).await?);
+        };
+
+        let internal_wallet = InternalWallet {
+            tari_address_type: TariAddressType::WatchOnly,
+            encrypted_tari_seed: Hidden::hide(None),
+            encrypted_monero_seed: Hidden::hide(monero_seed_binary),
+            monero_address: ConfigWallet::content().await.monero_address().clone(),
+            external_tari_address: None,
+            tari_wallet_details: Some(watch_only_wallet_details),
+        };
+
+        internal_wallet.post_init(app_handle).await
+    }
+
+    pub fn get_watch_only_wallet_details(
+        view_private_key_hex: &str,
+        spend_public_key_hex: &str,
+        wallet_birthday: u16,
+    ) -> Result<TariWalletDetails, anyhow::Error> {
+        let view_private_key = PrivateKey::from_hex(view_private_key_hex.trim())
+            .map_err(|_| anyhow!("Invalid view private key"))?;
+        let spend_public_key = CompressedPublicKey::from_hex(spend_public_key_hex.trim())
+            .map_err(|_| anyhow!("Invalid spend public key"))?;
+
+        let tari_address = TariAddress::new_dual_address(
+            CompressedPublicKey::from_secret_key(&view_private_key),
+            spend_public_key.clone(),
+            Network::default(),
+            TariAddressFeatures::create_one_sided_only(),
+            None,
+        )
+        .map_err(|e| anyhow!(e.to_string()))?;
+
+        Ok(TariWalletDetails {
+            id: WalletId::new(WATCH_ONLY_WALLET_ID.to_string()),
+            tari_address,
+            wallet_birthday,
+            view_private_key_hex: view_private_key.to_hex(),
+            spend_public_key_hex: spend_public_key.to_hex(),
+        })
+    }
+
     /** Ensures wallet config contains everything needed to initialize the wallet - returns false when impossible */
     pub async fn validate_wallet_config_for_seed(
         app_handle: &AppHandle,
@@ -216,6 +299,8 @@ impl InternalWallet {
             None,
         )
         .await?;
+        ConfigWallet::update_field(ConfigWalletContent::set_watch_only_wallet_details, None)
+            .await?;
         let wallet_config = ConfigWallet::content().await;
         let internal_wallet =
             if InternalWallet::validate_wallet_config_for_seed(app_handle, &wallet_config).await? {
@@ -281,7 +366,14 @@ impl InternalWallet {
 
         let state = app_handle.state::<UniverseAppState>();
         if let Some(ref wallet_details) = self.tari_wallet_details {
-            // Internal(Seed)
+            // Internal(Seed) or WatchOnly
+            if matches!(self.tari_address_type, TariAddressType::Internal) {
+                ConfigWallet::update_field(
+                    ConfigWalletContent::ensure_tari_wallet_record,
+                    wallet_details.clone(),
+                )
+                .await?;
+            }
             state
                 .wallet_manager
                 .set_view_private_key_and_spend_key(
@@ -330,10 +422,28 @@ impl InternalWallet {
         let internal_wallet_guard = InternalWallet::current().read().await;
         internal_wallet_guard.tari_wallet_details.clone()
     }
+
+    /** Address mining rewards are paid to - the selected wallet unless another owned wallet is chosen */
+    pub async fn mining_rewards_address() -> TariAddress {
+        if InternalWallet::is_internal().await {
+            let wallet_config = ConfigWallet::content().await;
+            if let Some(wallet_id) = wallet_config.mining_rewards_wallet_id() {
+                if let Some(record) = wallet_config
+                    .tari_wallet_records()
+                    .iter()
+                    .find(|r| r.details.id == *wallet_id)
+                {
+                    return record.details.tari_address.clone();
+                }
+            }
+        }
+        InternalWallet::tari_address().await
+    }
     // **
 
     pub async fn import_tari_seed_words(
         seed_words: Vec<String>,
+        wallet_name: Option<String>,
         app_handle: &AppHandle,
     ) -> Result<(WalletId, Vec<u8>), anyhow::Error> {
         let tari_cipher_seed = mnemonic_to_tari_cipher_seed(seed_words).await?;
@@ -341,23 +451,123 @@ impl InternalWallet {
 
         let (tari_wallet_details, tari_seed_binary) =
             InternalWallet::add_tari_wallet(app_handle, tari_cipher_seed, pin_password).await?;
+        if let Some(name) = wallet_name {
+            ConfigWallet::update_field(
+                ConfigWalletContent::set_tari_wallet_name,
+                (tari_wallet_details.id.clone(), name),
+            )
+            .await?;
+        }
 
         InternalWallet::initialize_with_seed(app_handle).await?;
 
         Ok((tari_wallet_details.id, tari_seed_binary))
     }
 
-    // Internal method
-    //
-    // Support only one wallet fow now
-    // * Define if we want to have one PIN for all wallets
-    async fn add_tari_wallet(
+    pub async fn create_tari_wallet(
+        app_handle: &AppHandle,
+        wallet_name: String,
+    ) -> Result<WalletId, anyhow::Error> {
+        let pin_password = PinManager::get_validated_pin_if_defined(app_handle).await?;
+
+        let (tari_wallet_details, _tari_seed_binary) =
+            InternalWallet::add_tari_wallet(app_handle, CipherSeed::new(), pin_password).await?;
+        ConfigWallet::update_field(
+            ConfigWalletContent::set_tari_wallet_name,
+            (tari_wallet_details.id.clone(), wallet_name),
+        )
+        .await?;
+
+        InternalWallet::initialize_with_seed(app_handle).await?;
+
+        Ok(tari_wallet_details.id)
+    }
+
+    pub async fn list_tari_wallets() -> Vec<TariWalletRecord> {
+        ConfigWallet::content().await.tari_wallet_records().clone()
+    }
+
+    pub async fn switch_tari_wallet(
+        app_handle: &AppHandle,
+        wallet_id: WalletId,
+    ) -> Result<(), anyhow::Error> {
+        let wallet_config = ConfigWallet::content().await;
+        let record = wallet_config
+            .tari_wallet_records()
+            .iter()
+            .find(|r| r.details.id == wallet_id)
+            .ok_or_else(|| anyhow!("Tari wallet {} not found", wallet_id.as_str()))?;
+        if record.is_locked {
+            return Err(anyhow!(
+                "Tari wallet {} is locked with a forgotten PIN, import its seed words again to unlock it",
+                record.name
+            ));
+        }
+        log::info!(target: LOG_TARGET, "Switching to Tari Wallet with id: {wallet_id:?}");
+
+        ConfigWallet::update_field(ConfigWalletContent::select_tari_wallet, wallet_id).await?;
+        if INSTANCE.get().is_some() {
+            // Seed of the newly selected wallet is loaded from the credential manager when needed
+            let mut internal_wallet_guard = InternalWallet::current().write().await;
+            internal_wallet_guard.encrypted_tari_seed = Hidden::hide(None);
+        }
+
+        InternalWallet::initialize_with_seed(app_handle).await
+    }
+
+    pub async fn delete_tari_wallet(
+        app_handle: &AppHandle,
+        wallet_id: WalletId,
+    ) -> Result<(), anyhow::Error> {
+        let wallet_config = ConfigWallet::content().await;
+        if wallet_config.tari_wallets().first() == Some(&wallet_id) {
+            return Err(anyhow!("The selected Tari wallet can't be removed"));
+        }
+        if !wallet_config.tari_wallets().contains(&wallet_id) {
+            return Err(anyhow!("Tari wallet {} not found", wallet_id.as_str()));
+        }
+        // Confirm ownership before the seed is irreversibly deleted
+        let _unused = PinManager::get_validated_pin_if_defined(app_handle).await?;
+
+        InternalWallet::remove_tari_wallet(wallet_id.clone())?;
+        ConfigWallet::update_field(ConfigWalletContent::remove_tari_wallet, wallet_id).await?;
+        Ok(())
+    }
+
+    /** Identifies the wallet database of the loaded wallet, the same seed always maps to the same key */
+    pub async fn wallet_data_key() -> Option<String> {
+        InternalWallet::tari_wallet_details()
+            .await
+            .map(|details| details.spend_public_key_hex)
+    }
+
+    // All owned wallets share the same PIN
+    // Importing a seed that is already owned reuses its wallet id instead of adding a duplicate
+    pub async fn add_tari_wallet(
         app_handle: &AppHandle,
         tari_seed: CipherSeed, // decrypted seed
         pin_password_provided: Option<SafePassword>,
     ) -> Result<(TariWalletDetails, Vec<u8>), anyhow::Error> {
-        let wallet_id = rand_utils::get_rand_string(6);
-        log::info!(target: LOG_TARGET, "Adding Tari Wallet with id: {wallet_id}");
+        // We always load the first index
+        let new_wallet_details = InternalWallet::get_tari_wallet_details(
+            WalletId::new(rand_utils::get_rand_string(6)),
+            tari_seed.clone(),
+        )
+        .await?;
+        let wallet_id = ConfigWallet::content()
+            .await
+            .tari_wallet_records()
+            .iter()
+            .find(|r| r.details.spend_public_key_hex == new_wallet_details.spend_public_key_hex)
+            .map_or_else(
+                || new_wallet_details.id.clone(),
+                |record| record.details.id.clone(),
+            );
+        let wallet_details = TariWalletDetails {
+            id: wallet_id.clone(),
+            ..new_wallet_details
+        };
+        log::info!(target: LOG_TARGET, "Adding Tari Wallet with id: {wallet_id:?}");
 
         let encrypted_seed = if PinManager::pin_locked().await {
             let pin_password = match pin_password_provided {
@@ -375,17 +585,8 @@ impl InternalWallet {
             encrypted_seed: encrypted_seed.clone(),
         };
 
-        InternalWallet::set_credentials(
-            app_handle,
-            WalletId::new(wallet_id.clone()),
-            &credentials,
-            true,
-        )
-        .await?;
+        InternalWallet::set_credentials(app_handle, wallet_id, &credentials, true).await?;
 
-        // We always load the first index
-        let wallet_details =
-            InternalWallet::get_tari_wallet_details(WalletId::new(wallet_id), tari_seed).await?;
         ConfigWallet::update_field(ConfigWalletContent::add_tari_wallet, wallet_details.clone())
             .await?;
 
@@ -399,6 +600,13 @@ impl InternalWallet {
         Ok((wallet_details, encrypted_seed))
     }
 
+    /** Drops a wallet added by an import that could not be completed */
+    pub async fn discard_tari_wallet(wallet_id: WalletId) -> Result<(), anyhow::Error> {
+        InternalWallet::remove_tari_wallet(wallet_id.clone())?;
+        ConfigWallet::update_field(ConfigWalletContent::remove_tari_wallet, wallet_id).await?;
+        Ok(())
+    }
+
     fn remove_tari_wallet(wallet_id: WalletId) -> Result<(), anyhow::Error> {
         log::info!(target: LOG_TARGET, "Removing Tari Wallet with id: {wallet_id:?}");
         let cm = CredentialManager::new_default(wallet_id);
@@ -431,6 +639,44 @@ impl InternalWallet {
         Ok(monero_seed_binary)
     }
 
+    pub async fn restore_monero_wallet(
+        app_handle: &AppHandle,
+        monero_seed_bytes: [u8; 32],
+        pin_password: Option<SafePassword>,
+    ) -> Result<(), anyhow::Error> {
+        log::info!(target: LOG_TARGET, "Restoring Monero Wallet");
+        let monero_seed = MoneroSeed::new(monero_seed_bytes);
+        let encrypted_monero_seed = match pin_password {
+            Some(ref pin_password) => cryptography::encrypt(&monero_seed_bytes, pin_password)?,
+            None => monero_seed_bytes.to_vec(),
+        };
+        InternalWallet::set_credentials(
+            app_handle,
+            WalletId::new("monero".to_string()),
+            &Credential {
+                encrypted_seed: encrypted_monero_seed.clone(),
+            },
+            true,
+        )
+        .await?;
+
+        let monero_address = monero_seed
+            .to_address::<Mainnet>()
+            .unwrap_or(DEFAULT_MONERO_ADDRESS.to_string());
+        ConfigWallet::update_field(
+            ConfigWalletContent::set_generated_monero_address,
+            monero_address.clone(),
+        )
+        .await?;
+
+        if INSTANCE.get().is_some() {
+            let mut internal_wallet_guard = InternalWallet::current().write().await;
+            internal_wallet_guard.encrypted_monero_seed = Hidden::hide(Some(encrypted_monero_seed));
+            internal_wallet_guard.monero_address = monero_address;
+        }
+        Ok(())
+    }
+
     fn remove_monero_wallet() -> Result<(), anyhow::Error> {
         log::info!(target: LOG_TARGET, "Removing Monero Wallet");
         let cm = CredentialManager::new_default(WalletId::new("monero".to_string()));
@@ -480,6 +726,13 @@ impl InternalWallet {
                 .await
                 .ok_or_else(|| anyhow!("Seedless Wallet does not support PIN enciphering"))?
                 .id;
+            // Seeds of the other owned wallets are still enciphered with the forgotten PIN,
+            // they stay locked until they are imported again from their seed words
+            ConfigWallet::update_field(
+                ConfigWalletContent::lock_other_tari_wallets,
+                wallet_id.clone(),
+            )
+            .await?;
             let encrypted_tari_seed = tari_seed.encipher(Some(pin_password))?;
             InternalWallet::set_credentials(
                 app_handle,
@@ -505,13 +758,67 @@ impl InternalWallet {
     }
 
     pub async fn create_pin(app_handle: &AppHandle) -> Result<(), anyhow::Error> {
+        if InternalWallet::is_watch_only().await {
+            return Err(anyhow!(
+                "Watch-only wallet has no seed to protect with a PIN"
+            ));
+        }
         let pin_password = PinManager::create_pin(app_handle).await?;
 
+        // Every seed is enciphered before any credential is written, so a failure can't leave
+        // the credentials half migrated to the PIN
         let encrypted_monero_seed = if *ConfigWallet::content().await.monero_address_is_generated()
         {
-            // Encrypt Monero Seed with PIN
             let monero_seed = InternalWallet::get_monero_seed(None).await?;
-            let encrypted_monero_seed = cryptography::encrypt(monero_seed.inner(), &pin_password)?;
+            Some(cryptography::encrypt(monero_seed.inner(), &pin_password)?)
+        } else {
+            // External Monero address is used, no seed to encrypt
+            None
+        };
+        let wallet_id = InternalWallet::tari_wallet_details()
+            .await
+            .ok_or_else(|| anyhow!("Seedless Wallet does not support PIN enciphering"))?
+            .id;
+        let encrypted_tari_seed = InternalWallet::get_tari_seed(None)
+            .await?
+            .encipher(Some(pin_password.clone()))?;
+        let mut encrypted_other_tari_seeds = Vec::new();
+        let wallet_config = ConfigWallet::content().await;
+        for other_wallet_id in wallet_config
+            .tari_wallets()
+            .iter()
+            .filter(|id| **id != wallet_id)
+        {
+            let is_locked = wallet_config
+                .tari_wallet_records()
+                .iter()
+                .any(|r| r.details.id == *other_wallet_id && r.is_locked);
+            if is_locked {
+                // Still enciphered with a forgotten PIN, it is unlocked by importing its seed words
+                continue;
+            }
+            let other_tari_seed =
+                InternalWallet::get_credentials(app_handle, other_wallet_id.clone(), false)
+                    .await
+                    .and_then(|credential| {
+                        CipherSeed::from_binary(&credential.encrypted_seed)
+                            .map_err(|_| anyhow!("Could not parse Tari Seed from binary"))
+                    })
+                    .and_then(|seed| {
+                        seed.encipher(Some(pin_password.clone()))
+                            .map_err(anyhow::Error::from)
+                    });
+            match other_tari_seed {
+                Ok(encrypted_seed) => {
+                    encrypted_other_tari_seeds.push((other_wallet_id.clone(), encrypted_seed));
+                }
+                Err(e) => {
+                    log::warn!(target: LOG_TARGET, "Not protecting Tari wallet {} with the PIN: {e}", other_wallet_id.as_str());
+                }
+            }
+        }
+
+        if let Some(encrypted_monero_seed) = &encrypted_monero_seed {
             InternalWallet::set_credentials(
                 app_handle,
                 WalletId::new("monero".to_string()),
@@ -521,42 +828,31 @@ impl InternalWallet {
                 false,
             )
             .await?;
-            if InternalWallet::is_initialized() {
-                let mut internal_wallet_guard = InternalWallet::current().write().await;
-                internal_wallet_guard.encrypted_monero_seed =
-                    Hidden::hide(Some(encrypted_monero_seed.clone()));
-            }
-            Some(encrypted_monero_seed)
-        } else {
-            // External Monero address is used, no seed to encrypt
-            None
-        };
-        let encrypted_tari_seed = {
-            // Encrypt Tari Seed with PIN
-            let tari_seed = InternalWallet::get_tari_seed(None).await?;
-            let wallet_id = InternalWallet::tari_wallet_details()
-                .await
-                .ok_or_else(|| anyhow!("Seedless Wallet does not support PIN enciphering"))?
-                .id;
-            let encrypted_tari_seed = tari_seed.encipher(Some(pin_password))?;
+        }
+        for (other_wallet_id, encrypted_seed) in encrypted_other_tari_seeds {
             InternalWallet::set_credentials(
                 app_handle,
-                wallet_id,
-                &Credential {
-                    encrypted_seed: encrypted_tari_seed.clone(),
-                },
+                other_wallet_id,
+                &Credential { encrypted_seed },
                 false,
             )
             .await?;
-            encrypted_tari_seed
-        };
+        }
+        InternalWallet::set_credentials(
+            app_handle,
+            wallet_id,
+            &Credential {
+                encrypted_seed: encrypted_tari_seed.clone(),
+            },
+            false,
+        )
+        .await?;
         PinManager::set_pin_locked().await?;
 
         if InternalWallet::is_initialized() {
             let mut internal_wallet_guard = InternalWallet::current().write().await;
             internal_wallet_guard.encrypted_monero_seed = Hidden::hide(encrypted_monero_seed);
-            internal_wallet_guard.encrypted_tari_seed =
-                Hidden::hide(Some(encrypted_tari_seed.clone()));
+            internal_wallet_guard.encrypted_tari_seed = Hidden::hide(Some(encrypted_tari_seed));
         }
 
         Ok(())
@@ -848,6 +1144,25 @@ impl InternalWallet {
         }
     }
 
+    /** Reads the seed of any owned wallet straight from the credential manager */
+    pub async fn get_tari_seed_for_wallet(
+        wallet_id: WalletId,
+        pin_password: Option<SafePassword>,
+    ) -> Result<CipherSeed, anyhow::Error> {
+        let encrypted_tari_seed = CredentialManager::new_default(wallet_id)
+            .get_credentials()
+            .await?
+            .encrypted_seed;
+
+        if let Some(pin_password) = pin_password {
+            CipherSeed::from_enciphered_bytes(&encrypted_tari_seed, Some(pin_password))
+                .map_err(|_| anyhow!("Wrong PIN entered!"))
+        } else {
+            CipherSeed::from_binary(&encrypted_tari_seed)
+                .map_err(|_| anyhow!("Could not parse Tari Seed from binary"))
+        }
+    }
+
     /** Method safe to use before init - fallbacks to the credential manager */
     pub async fn get_monero_seed(
         pin_password: Option<SafePassword>,
@@ -938,6 +1253,7 @@ impl InternalWallet {
 pub enum TariAddressType {
     Internal = 0,
     External = 1,
+    WatchOnly = 2,
 }
 impl From<TariAddressType> for u8 {
     fn from(val: TariAddressType) -> Self {
@@ -949,6 +1265,7 @@ impl std::fmt::Display for TariAddressType {
         match self {
             TariAddressType::Internal => write!(f, "Internal"),
             TariAddressType::External => write!(f, "External"),
+            TariAddressType::WatchOnly => write!(f, "WatchOnly"),
         }
     }
 }
@@ -79,6 +79,7 @@ use crate::mm_proxy_manager::{MmProxyManager, StartConfig};
 use crate::node::node_manager::NodeManager;
 use crate::p2pool::models::P2poolStats;
 use crate::p2pool_manager::P2poolManager;
+use crate::stratum_hub::stratum_hub_manager::StratumHubManager;
 use crate::tor_manager::TorManager;
 use crate::wallet::wallet_manager::WalletManager;
 use crate::wallet::wallet_types::WalletState;
@@ -92,6 +93,7 @@ mod commands;
 mod configs;
 mod consts;
 mod cpu_miner;
+mod cpu_miner_backend;
 mod credential_manager;
 mod download_utils;
 mod events;
@@ -108,9 +110,12 @@ mod gpu_miner_sha_websocket;
 mod gpu_status_file;
 mod hardware;
 mod internal_wallet;
+mod merge_mining_stats;
+mod mining_mode_advisor;
 mod mining_status_manager;
 mod mm_proxy_adapter;
 mod mm_proxy_manager;
+mod monero_node_prober;
 mod network_utils;
 mod node;
 mod p2pool;
@@ -129,6 +134,7 @@ mod progress_trackers;
 mod release_notes;
 mod requests;
 mod setup;
+mod stratum_hub;
 mod systemtray_manager;
 mod tapplets;
 mod tasks_tracker;
@@ -187,6 +193,7 @@ struct UniverseAppState {
     telemetry_service: Arc<RwLock<TelemetryService>>,
     feedback: Arc<RwLock<Feedback>>,
     p2pool_manager: P2poolManager,
+    stratum_hub_manager: StratumHubManager,
     tor_manager: TorManager,
     updates_manager: UpdatesManager,
     cached_p2pool_connections: Arc<RwLock<Option<Option<Connections>>>>,
@@ -372,6 +379,7 @@ fn main() {
         node_manager,
         wallet_manager,
         p2pool_manager,
+        stratum_hub_manager: StratumHubManager::new(),
         telemetry_manager: Arc::new(RwLock::new(telemetry_manager)),
         telemetry_service: Arc::new(RwLock::new(telemetry_service)),
         feedback: Arc::new(RwLock::new(feedback)),
@@ -556,13 +564,26 @@ fn main() {
             commands::get_monero_seed_words,
             commands::get_network,
             commands::get_p2pool_stats,
+            commands::get_p2pool_miner_stats,
+            commands::get_p2pool_stats_history,
+            commands::get_p2pool_diagnostics,
             commands::get_paper_wallet_details,
             commands::get_seed_words,
+            commands::get_stratum_hub_status,
             commands::get_tor_config,
             commands::get_tor_entry_guards,
             commands::get_transactions,
             commands::import_seed_words,
             commands::revert_to_internal_wallet,
+            commands::import_watch_only_wallet,
+            commands::get_tari_wallets,
+            commands::create_tari_wallet,
+            commands::switch_tari_wallet,
+            commands::rename_tari_wallet,
+            commands::remove_tari_wallet,
+            commands::set_mining_rewards_wallet,
+            commands::export_wallet_backup,
+            commands::import_wallet_backup,
             commands::log_web_message,
             commands::open_log_dir,
             commands::reset_settings,
@@ -577,6 +598,12 @@ fn main() {
             commands::set_gpu_mining_enabled,
             commands::set_mine_on_app_start,
             commands::set_monero_address,
+            commands::get_merge_mining_stats,
+            commands::get_mining_mode_recommendation,
+            commands::get_monero_nodes_status,
+            commands::refresh_monero_nodes_status,
+            commands::test_monero_node,
+            commands::set_mining_mode_auto_switch,
             commands::set_monerod_config,
             commands::set_external_tari_address,
             commands::confirm_exchange_address,
@@ -585,6 +612,7 @@ fn main() {
             commands::set_show_experimental_settings,
             commands::set_should_always_use_system_language,
             commands::set_should_auto_launch,
+            commands::set_stratum_hub_config,
             commands::set_tor_config,
             commands::set_use_tor,
             commands::set_visual_mode,
@@ -602,11 +630,16 @@ fn main() {
             commands::check_for_updates,
             commands::try_update,
             commands::toggle_device_exclusion,
+            commands::set_gpu_device_settings,
             commands::get_network,
             commands::sign_ws_data, // TODO: Unused
             commands::set_airdrop_tokens,
             commands::get_airdrop_tokens,
             commands::set_selected_engine,
+            commands::get_cpu_miner_backends,
+            commands::set_cpu_miner_backend,
+            commands::get_xmrig_tuning,
+            commands::set_xmrig_tuning,
             commands::frontend_ready,
             commands::start_mining_status,
             commands::stop_mining_status,
@@ -614,10 +647,49 @@ fn main() {
             commands::websocket_close,
             commands::reconnect,
             commands::send_one_sided_to_stealth_address,
+            commands::get_unspent_outputs,
+            commands::preview_sweep_to_self,
+            commands::sweep_to_self,
+            commands::get_scheduled_payments,
+            commands::get_scheduled_payments_history,
+            commands::add_scheduled_payment,
+            commands::remove_scheduled_payment,
+            commands::set_scheduled_payment_enabled,
+            commands::set_payments_min_reserve,
             commands::verify_address_for_send,
             commands::validate_minotari_amount,
             commands::trigger_phases_restart,
             commands::set_node_type,
+            commands::set_fallback_to_remote_node,
+            commands::get_network_usage,
+            commands::set_bandwidth_limits,
+            commands::set_metered_connection,
+            commands::trigger_metered_node_sync,
+            commands::verify_node_database,
+            commands::trim_node_database,
+            commands::backup_node_database,
+            commands::get_node_database_backup,
+            commands::repair_node_database,
+            commands::resync_node_database,
+            commands::get_node_storage_mode,
+            commands::set_node_storage_mode,
+            commands::get_remote_base_node_statuses,
+            commands::set_remote_base_node_fallback_addresses,
+            commands::set_node_snapshot_source,
+            commands::bootstrap_node_from_snapshot,
+            commands::get_node_effective_config,
+            commands::set_node_config_overrides,
+            commands::get_node_peers,
+            commands::add_node_seed_peer,
+            commands::remove_node_seed_peer,
+            commands::get_node_banned_peers,
+            commands::ban_node_peer,
+            commands::unban_node_peer,
+            commands::get_block_headers,
+            commands::get_block_header_by_hash,
+            commands::get_difficulty_history,
+            commands::get_mempool_stats,
+            commands::get_recent_block_rewards,
             commands::set_allow_notifications,
             commands::launch_builtin_tapplet,
             commands::get_bridge_envs,
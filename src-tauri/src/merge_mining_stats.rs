@@ -0,0 +1,205 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{io::SeekFrom, path::PathBuf, time::SystemTime};
+
+use anyhow::Error;
+use serde::Serialize;
+use tokio::{
+    fs::File,
+    io::{AsyncReadExt, AsyncSeekExt},
+};
+
+/// Logged by the proxy for every block template it builds for a miner.
+const TEMPLATE_DIFFICULTIES_MARKER: &str = "Difficulties: Minotari (";
+const TEMPLATE_HEIGHTS_MARKER: &str = "Monero height = #";
+const TARI_HEIGHT_MARKER: &str = "base node height = #";
+/// Logged by the proxy when a merge mined block was accepted by the Tari node.
+const TARI_BLOCK_ACCEPTED_MARKER: &str = "Submitted block #";
+/// Logged by the proxy when the Tari node rejected a merge mined block.
+const TARI_BLOCK_REJECTED_MARKER: &str = "Problem submitting block #";
+const TARI_NODE_MARKER: &str = "Minotari node";
+
+/// Merge mining activity, parsed from the proxy log by the proxy status monitor. Miner share
+/// counts are filled in from xmrig when requested.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct MergeMiningStats {
+    pub templates_served: u64,
+    pub template_errors: u64,
+    pub monero_height: Option<u64>,
+    pub monero_difficulty: Option<u64>,
+    pub tari_height: Option<u64>,
+    pub tari_difficulty: Option<u64>,
+    pub monerod_errors: u64,
+    pub last_monerod_error: Option<String>,
+    /// Aux chain blocks the proxy submitted to the Tari node
+    pub blocks_submitted: u64,
+    /// Aux chain blocks the Tari node accepted, i.e. Tari blocks merged
+    pub blocks_accepted: u64,
+    /// xmrig shares sent to the proxy, only set when xmrig mines through the built-in proxy
+    pub shares_submitted: Option<u64>,
+    pub shares_accepted: Option<u64>,
+    pub hub_blocks_found: u64,
+    pub last_updated_at: Option<SystemTime>,
+}
+
+impl MergeMiningStats {
+    pub fn record_log_lines(&mut self, lines: &[String]) {
+        for line in lines {
+            let is_problem = line.contains(" ERROR ") || line.contains(" WARN ");
+            let lowercase = line.to_lowercase();
+            if is_problem && lowercase.contains("monerod") {
+                self.monerod_errors += 1;
+                self.last_monerod_error = Some(line.clone());
+            }
+            if is_problem && lowercase.contains("block template") {
+                self.template_errors += 1;
+            }
+
+            if let Some(tari_difficulty) = number_after(line, TEMPLATE_DIFFICULTIES_MARKER) {
+                self.templates_served += 1;
+                self.tari_difficulty = Some(tari_difficulty);
+                self.monero_difficulty = number_after(line, "Monero(");
+            }
+            if let Some(monero_height) = number_after(line, TEMPLATE_HEIGHTS_MARKER) {
+                self.monero_height = Some(monero_height);
+                self.tari_height = number_after(line, TARI_HEIGHT_MARKER).or(self.tari_height);
+            }
+            if line.contains(TARI_NODE_MARKER) {
+                if !is_problem && line.contains(TARI_BLOCK_ACCEPTED_MARKER) {
+                    self.blocks_submitted += 1;
+                    self.blocks_accepted += 1;
+                } else if line.contains(TARI_BLOCK_REJECTED_MARKER) {
+                    self.blocks_submitted += 1;
+                }
+            }
+        }
+        if !lines.is_empty() {
+            self.last_updated_at = Some(SystemTime::now());
+        }
+    }
+}
+
+/// Parses the digits directly following `marker`, e.g. `123` in `height = #123,`.
+fn number_after(line: &str, marker: &str) -> Option<u64> {
+    let rest = &line[line.find(marker)? + marker.len()..];
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
+
+/// Follows the proxy log file, returning lines written since the last read. Starts at the end
+/// of the file so old runs are not counted, and rewinds when the log is rolled over.
+pub struct ProxyLogTail {
+    path: PathBuf,
+    offset: Option<u64>,
+}
+
+impl ProxyLogTail {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, offset: None }
+    }
+
+    pub async fn read_new_lines(&mut self) -> Result<Vec<String>, Error> {
+        let mut file = File::open(&self.path).await?;
+        let len = file.metadata().await?.len();
+        let offset = match self.offset {
+            Some(offset) if offset <= len => offset,
+            Some(_) => 0,
+            None => len,
+        };
+        file.seek(SeekFrom::Start(offset)).await?;
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).await?;
+
+        // Keep a partially written last line for the next read
+        let complete = buffer
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+            .map_or(0, |position| position + 1);
+        self.offset = Some(offset + complete as u64);
+        Ok(String::from_utf8_lossy(&buffer[..complete])
+            .lines()
+            .map(str::to_string)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn temp_log_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("{name}-{}.log", std::process::id()))
+    }
+
+    #[test]
+    fn parses_templates_submissions_and_errors() {
+        let lines: Vec<String> = [
+            "2025-01-01 10:00:00.000 [minotari_mm_proxy::proxy] INFO  Monero height = #3300000, Minotari base node height = #45000",
+            "2025-01-01 10:00:00.100 [minotari_mm_proxy::block_template_protocol] INFO  Difficulties: Minotari (123456), Monero(654321)",
+            "2025-01-01 10:00:05.000 [minotari_mm_proxy::proxy] INFO  Submitted block #45001 to Minotari node in 120ms (SubmitBlock)",
+            "2025-01-01 10:00:05.500 [minotari_mm_proxy::proxy] DEBUG Problem submitting block #45001 to Minotari node, responded in 80ms (SubmitBlock): orphan",
+            "2025-01-01 10:00:06.000 [minotari_mm_proxy::proxy] WARN  Failed to get block template from monerod: timed out",
+        ]
+        .map(str::to_string)
+        .to_vec();
+
+        let mut stats = MergeMiningStats::default();
+        stats.record_log_lines(&lines);
+        assert_eq!(stats.templates_served, 1);
+        assert_eq!(stats.monero_height, Some(3_300_000));
+        assert_eq!(stats.tari_height, Some(45_000));
+        assert_eq!(stats.tari_difficulty, Some(123_456));
+        assert_eq!(stats.monero_difficulty, Some(654_321));
+        assert_eq!(stats.blocks_submitted, 2);
+        assert_eq!(stats.blocks_accepted, 1);
+        assert_eq!(stats.monerod_errors, 1);
+        assert_eq!(stats.template_errors, 1);
+        assert!(stats.last_updated_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn log_tail_keeps_partial_lines_and_follows_rotation() {
+        let path = temp_log_path("proxy-log-tail");
+        tokio::fs::write(&path, "old line\n").await.unwrap();
+        let mut tail = ProxyLogTail::new(path.clone());
+        assert!(tail.read_new_lines().await.unwrap().is_empty());
+
+        tokio::fs::write(&path, "old line\nfirst\nsec")
+            .await
+            .unwrap();
+        assert_eq!(tail.read_new_lines().await.unwrap(), vec!["first"]);
+        tokio::fs::write(&path, "old line\nfirst\nsecond\n")
+            .await
+            .unwrap();
+        assert_eq!(tail.read_new_lines().await.unwrap(), vec!["second"]);
+
+        // Rolled over: the new file is shorter than what was already read
+        tokio::fs::write(&path, "rotated\n").await.unwrap();
+        assert_eq!(tail.read_new_lines().await.unwrap(), vec!["rotated"]);
+
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+}
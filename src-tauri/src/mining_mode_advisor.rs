@@ -0,0 +1,412 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::time::Duration;
+
+use log::{info, warn};
+use serde::Serialize;
+use tari_core::transactions::tari_amount::MicroMinotari;
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    commands::{start_cpu_mining, start_gpu_mining, stop_cpu_mining, stop_gpu_mining},
+    configs::{
+        config_pools::{ConfigPools, ConfigPoolsContent, CpuPool, GpuPool},
+        trait_config::ConfigImpl,
+    },
+    internal_wallet::InternalWallet,
+    pool_status_watcher::{
+        LuckyPoolAdapter, PoolApiAdapters, PoolStatus, PoolStatusWatcher, SupportXmrPoolAdapter,
+    },
+    setup::setup_manager::{SetupManager, SetupPhase},
+    tasks_tracker::TasksTrackers,
+    utils::math_utils::estimate_earning,
+    UniverseAppState,
+};
+
+const LOG_TARGET: &str = "tari::universe::mining_mode_advisor";
+
+const BLOCKS_PER_DAY: f64 = 360.0;
+// Typical fee of the built-in pools, their stats apis do not report it
+const POOL_FEE_PERCENT: f64 = 1.0;
+// Earnings are compared over this many days, which is where solo variance shows up
+const COMPARISON_HORIZON_DAYS: f64 = 7.0;
+pub const AUTO_SWITCH_CHECK_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum MiningAlgorithm {
+    RandomX,
+    Sha3x,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum MiningModeOption {
+    Solo,
+    Pool,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct MiningModeEstimate {
+    pub mode: MiningModeOption,
+    pub pool_name: Option<String>,
+    /// Expected reward per day in µT
+    pub expected_daily_reward: u64,
+    /// Median reward over the comparison horizon in µT, the figure the recommendation uses
+    pub median_horizon_reward: u64,
+    pub expected_blocks_per_day: Option<f64>,
+    pub probability_of_no_reward_per_day: Option<f64>,
+    /// Standard deviation of the daily reward relative to its mean
+    pub daily_reward_variation: Option<f64>,
+    pub days_to_next_payout: Option<f64>,
+    pub pool_status: Option<PoolStatus>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AlgorithmComparison {
+    pub algorithm: MiningAlgorithm,
+    pub hash_rate: f64,
+    pub network_hash_rate: u64,
+    pub current_mode: MiningModeOption,
+    pub solo: MiningModeEstimate,
+    pub pool: MiningModeEstimate,
+    pub recommended_mode: MiningModeOption,
+    /// How much better the recommended mode is, as a percentage of its median reward
+    pub advantage_percent: f64,
+}
+
+impl AlgorithmComparison {
+    pub fn new(
+        algorithm: MiningAlgorithm,
+        hash_rate: f64,
+        network_hash_rate: u64,
+        block_reward: MicroMinotari,
+        current_mode: MiningModeOption,
+        pool_name: String,
+        pool_status: Option<PoolStatus>,
+    ) -> Self {
+        let solo = solo_estimate(hash_rate, network_hash_rate, block_reward);
+        let pool = pool_estimate(&solo, pool_name, pool_status);
+
+        let (recommended_mode, best, other) =
+            if solo.median_horizon_reward >= pool.median_horizon_reward {
+                (
+                    MiningModeOption::Solo,
+                    solo.median_horizon_reward,
+                    pool.median_horizon_reward,
+                )
+            } else {
+                (
+                    MiningModeOption::Pool,
+                    pool.median_horizon_reward,
+                    solo.median_horizon_reward,
+                )
+            };
+        let advantage_percent = if best == 0 {
+            0.0
+        } else {
+            (best - other) as f64 / best as f64 * 100.0
+        };
+
+        Self {
+            algorithm,
+            hash_rate,
+            network_hash_rate,
+            current_mode,
+            solo,
+            pool,
+            recommended_mode,
+            advantage_percent,
+        }
+    }
+
+    /// Mode to switch to, if the recommendation beats the current mode by at least the threshold
+    pub fn switch_target(&self, threshold_percent: f64) -> Option<MiningModeOption> {
+        (self.hash_rate > 0.0
+            && self.recommended_mode != self.current_mode
+            && self.advantage_percent >= threshold_percent)
+            .then_some(self.recommended_mode)
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct MiningModeRecommendation {
+    pub block_reward: u64,
+    pub randomx: AlgorithmComparison,
+    pub sha3x: AlgorithmComparison,
+}
+
+fn solo_estimate(
+    hash_rate: f64,
+    network_hash_rate: u64,
+    block_reward: MicroMinotari,
+) -> MiningModeEstimate {
+    let expected_daily_reward = estimate_earning(network_hash_rate, hash_rate, block_reward);
+    let blocks_per_day = if network_hash_rate == 0 {
+        0.0
+    } else {
+        (hash_rate / network_hash_rate as f64 * BLOCKS_PER_DAY).min(BLOCKS_PER_DAY)
+    };
+    let median_blocks = poisson_median(blocks_per_day * COMPARISON_HORIZON_DAYS);
+
+    MiningModeEstimate {
+        mode: MiningModeOption::Solo,
+        pool_name: None,
+        expected_daily_reward,
+        median_horizon_reward: block_reward.as_u64().saturating_mul(median_blocks),
+        expected_blocks_per_day: Some(blocks_per_day),
+        probability_of_no_reward_per_day: Some((-blocks_per_day).exp()),
+        daily_reward_variation: (blocks_per_day > 0.0).then(|| 1.0 / blocks_per_day.sqrt()),
+        days_to_next_payout: (blocks_per_day > 0.0).then(|| 1.0 / blocks_per_day),
+        pool_status: None,
+    }
+}
+
+/// Pools pay out the solo expectation minus the fee, smoothed over all of the pool's miners.
+fn pool_estimate(
+    solo: &MiningModeEstimate,
+    pool_name: String,
+    pool_status: Option<PoolStatus>,
+) -> MiningModeEstimate {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let expected_daily_reward =
+        (solo.expected_daily_reward as f64 * (1.0 - POOL_FEE_PERCENT / 100.0)).floor() as u64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let median_horizon_reward =
+        (expected_daily_reward as f64 * COMPARISON_HORIZON_DAYS).floor() as u64;
+    let days_to_next_payout = pool_status.as_ref().and_then(|status| {
+        (expected_daily_reward > 0).then(|| {
+            status.min_payout.saturating_sub(status.unpaid) as f64 / expected_daily_reward as f64
+        })
+    });
+
+    MiningModeEstimate {
+        mode: MiningModeOption::Pool,
+        pool_name: Some(pool_name),
+        expected_daily_reward,
+        median_horizon_reward,
+        expected_blocks_per_day: None,
+        probability_of_no_reward_per_day: None,
+        daily_reward_variation: None,
+        days_to_next_payout,
+        pool_status,
+    }
+}
+
+/// Median of a Poisson distribution with mean `mean`, using the floor(mean + 1/3 - 0.02/mean)
+/// approximation which is exact for all but a few edge values.
+fn poisson_median(mean: f64) -> u64 {
+    if mean <= std::f64::consts::LN_2 {
+        return 0;
+    }
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let median = (mean + 1.0 / 3.0 - 0.02 / mean).floor() as u64;
+    median
+}
+
+async fn fetch_pool_status(
+    url: String,
+    adapter: PoolApiAdapters,
+    pool_name: &str,
+) -> Option<PoolStatus> {
+    match PoolStatusWatcher::new(url, adapter).get_pool_status().await {
+        Ok(status) => Some(status),
+        Err(e) => {
+            warn!(target: LOG_TARGET, "Failed to fetch {pool_name} pool status: {e}");
+            None
+        }
+    }
+}
+
+pub async fn get_recommendation(app_handle: &AppHandle) -> MiningModeRecommendation {
+    let state = app_handle.state::<UniverseAppState>();
+    let node_status = *state.node_status_watch_rx.borrow();
+    let cpu_hash_rate = state.cpu_miner_status_watch_rx.borrow().hash_rate;
+    let gpu_hash_rate = state.gpu_latest_status.borrow().hash_rate;
+    let tari_address = InternalWallet::mining_rewards_address().await.to_base58();
+    let pools_config = ConfigPools::content().await;
+
+    let (cpu_pool_name, cpu_pool_status) = match pools_config.cpu_pool() {
+        CpuPool::GlobalTariPool(pool) => {
+            let name = "GlobalTariPool";
+            let status = fetch_pool_status(
+                pool.get_stats_url(&tari_address),
+                PoolApiAdapters::SupportXmrPool(SupportXmrPoolAdapter {}),
+                name,
+            )
+            .await;
+            (name, status)
+        }
+    };
+    let (gpu_pool_name, gpu_pool_status) = match pools_config.gpu_pool() {
+        GpuPool::LuckyPool(pool) => {
+            let name = "LuckyPool";
+            let status = fetch_pool_status(
+                pool.get_stats_url(&tari_address),
+                PoolApiAdapters::LuckyPool(LuckyPoolAdapter {}),
+                name,
+            )
+            .await;
+            (name, status)
+        }
+        GpuPool::SupportXTMPool(pool) => {
+            let name = "SupportXTM";
+            let status = fetch_pool_status(
+                pool.get_stats_url(&tari_address),
+                PoolApiAdapters::SupportXmrPool(SupportXmrPoolAdapter {}),
+                name,
+            )
+            .await;
+            (name, status)
+        }
+    };
+
+    let current_mode = |pool_enabled: bool| {
+        if pool_enabled {
+            MiningModeOption::Pool
+        } else {
+            MiningModeOption::Solo
+        }
+    };
+
+    MiningModeRecommendation {
+        block_reward: node_status.block_reward.as_u64(),
+        randomx: AlgorithmComparison::new(
+            MiningAlgorithm::RandomX,
+            cpu_hash_rate,
+            node_status.monero_randomx_network_hashrate,
+            node_status.block_reward,
+            current_mode(*pools_config.cpu_pool_enabled()),
+            cpu_pool_name.to_string(),
+            cpu_pool_status,
+        ),
+        sha3x: AlgorithmComparison::new(
+            MiningAlgorithm::Sha3x,
+            gpu_hash_rate,
+            node_status.sha_network_hashrate,
+            node_status.block_reward,
+            current_mode(*pools_config.gpu_pool_enabled()),
+            gpu_pool_name.to_string(),
+            gpu_pool_status,
+        ),
+    }
+}
+
+/// Checks the recommendation and switches modes whose advantage exceeds the configured threshold.
+/// Returns true when the mining phase is being restarted, so the caller's task should end.
+pub async fn auto_switch_if_needed(app_handle: &AppHandle) -> bool {
+    let pools_config = ConfigPools::content().await;
+    if !*pools_config.auto_switch_enabled() {
+        return false;
+    }
+    let threshold_percent = f64::from(*pools_config.auto_switch_threshold_percent());
+    let recommendation = get_recommendation(app_handle).await;
+
+    if let Some(mode) = recommendation.sha3x.switch_target(threshold_percent) {
+        info!(target: LOG_TARGET, "Switching SHA3x mining to {mode:?}, {:.1}% better", recommendation.sha3x.advantage_percent);
+        let state = app_handle.state::<UniverseAppState>();
+        let was_mining = state.gpu_latest_status.borrow().is_mining;
+        if was_mining {
+            let _unused = stop_gpu_mining(state.clone()).await;
+        }
+        let _unused = ConfigPools::update_field(
+            ConfigPoolsContent::set_gpu_pool_enabled,
+            mode == MiningModeOption::Pool,
+        )
+        .await;
+        if was_mining {
+            if let Err(e) = start_gpu_mining(state, app_handle.clone()).await {
+                warn!(target: LOG_TARGET, "Failed to restart GPU mining after switch: {e}");
+            }
+        }
+    }
+
+    if let Some(mode) = recommendation.randomx.switch_target(threshold_percent) {
+        info!(target: LOG_TARGET, "Switching RandomX mining to {mode:?}, {:.1}% better", recommendation.randomx.advantage_percent);
+        if let Err(e) = ConfigPools::update_field(
+            ConfigPoolsContent::set_cpu_pool_enabled,
+            mode == MiningModeOption::Pool,
+        )
+        .await
+        {
+            warn!(target: LOG_TARGET, "Failed to switch RandomX mining mode: {e}");
+            return false;
+        }
+        // Restart from the common tracker, the caller runs on the mining phase tracker
+        let app_handle = app_handle.clone();
+        TasksTrackers::current()
+            .common
+            .get_task_tracker()
+            .await
+            .spawn(async move {
+                let state = app_handle.state::<UniverseAppState>();
+                let was_mining = state.cpu_miner_status_watch_rx.borrow().is_mining;
+                if was_mining {
+                    let _unused = stop_cpu_mining(state.clone()).await;
+                }
+                SetupManager::get_instance()
+                    .restart_phases(app_handle.clone(), vec![SetupPhase::Mining])
+                    .await;
+                if was_mining {
+                    if let Err(e) = start_cpu_mining(state, app_handle.clone()).await {
+                        warn!(target: LOG_TARGET, "Failed to restart CPU mining after switch: {e}");
+                    }
+                }
+            });
+        return true;
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_miner_is_recommended_pool() {
+        // One block every ~100 days solo: the median week earns nothing
+        let small = AlgorithmComparison::new(
+            MiningAlgorithm::RandomX,
+            1.0,
+            36_000,
+            MicroMinotari(1_000_000),
+            MiningModeOption::Solo,
+            "pool".to_string(),
+            None,
+        );
+        assert_eq!(small.recommended_mode, MiningModeOption::Pool);
+        assert_eq!(small.switch_target(25.0), Some(MiningModeOption::Pool));
+
+        // Several blocks a day solo: the pool fee is the only difference
+        let large = AlgorithmComparison::new(
+            MiningAlgorithm::RandomX,
+            1_000.0,
+            36_000,
+            MicroMinotari(1_000_000),
+            MiningModeOption::Solo,
+            "pool".to_string(),
+            None,
+        );
+        assert_eq!(large.recommended_mode, MiningModeOption::Solo);
+        assert_eq!(large.switch_target(25.0), None);
+    }
+}
@@ -21,8 +21,10 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 
+use crate::merge_mining_stats::{MergeMiningStats, ProxyLogTail};
 use crate::process_adapter::{
     HealthStatus, ProcessAdapter, ProcessInstance, ProcessStartupSpec, StatusMonitor,
 };
@@ -31,13 +33,12 @@ use crate::utils::logging_utils::setup_logging;
 use anyhow::{anyhow, Error};
 use async_trait::async_trait;
 use log::warn;
-use rand::seq::SliceRandom;
-use rand::thread_rng;
 // use log::warn;
 use reqwest::Client;
 use serde_json::json;
 use tari_common_types::tari_address::TariAddress;
 use tari_shutdown::Shutdown;
+use tokio::sync::{Mutex, RwLock};
 
 const LOG_TARGET: &str = "tari::universe::mm_proxy_adapter";
 
@@ -47,6 +48,7 @@ pub(crate) struct MergeMiningProxyConfig {
     pub base_node_grpc_address: String,
     pub coinbase_extra: String,
     pub tari_address: TariAddress,
+    pub monero_address: String,
     pub use_monero_fail: bool,
     pub monero_nodes: Vec<String>,
 }
@@ -60,11 +62,15 @@ impl MergeMiningProxyConfig {
 
 pub struct MergeMiningProxyAdapter {
     pub config: Option<MergeMiningProxyConfig>,
+    pub(crate) stats: Arc<RwLock<MergeMiningStats>>,
 }
 
 impl MergeMiningProxyAdapter {
     pub fn new() -> Self {
-        Self { config: None }
+        Self {
+            config: None,
+            stats: Arc::new(RwLock::new(MergeMiningStats::default())),
+        }
     }
 }
 
@@ -145,12 +151,11 @@ impl ProcessAdapter for MergeMiningProxyAdapter {
             ),
         ];
 
-        let shuffled_nodes = &mut config.monero_nodes.clone();
-        shuffled_nodes.shuffle(&mut thread_rng());
+        // Nodes are already ranked by the manager, the proxy tries them in order.
         args.push("-p".to_string());
         args.push(format!(
             "merge_mining_proxy.monerod_url={}",
-            shuffled_nodes.join(",")
+            config.monero_nodes.join(",")
         ));
 
         Ok((
@@ -168,6 +173,10 @@ impl ProcessAdapter for MergeMiningProxyAdapter {
             },
             MergeMiningProxyStatusMonitor {
                 json_rpc_port: config.port,
+                stats: self.stats.clone(),
+                log_tail: Arc::new(Mutex::new(ProxyLogTail::new(
+                    log_dir.join("proxy").join("log").join("proxy.log"),
+                ))),
             },
         ))
     }
@@ -184,6 +193,8 @@ impl ProcessAdapter for MergeMiningProxyAdapter {
 #[derive(Clone)]
 pub struct MergeMiningProxyStatusMonitor {
     json_rpc_port: u16,
+    stats: Arc<RwLock<MergeMiningStats>>,
+    log_tail: Arc<Mutex<ProxyLogTail>>,
 }
 
 #[async_trait]
@@ -191,7 +202,15 @@ impl StatusMonitor for MergeMiningProxyStatusMonitor {
     async fn check_health(&self, _uptime: Duration, timeout_duration: Duration) -> HealthStatus {
         match tokio::time::timeout(timeout_duration, self.get_version()).await {
             Ok(result) => match result {
-                Ok(_) => HealthStatus::Healthy,
+                Ok(_) => {
+                    if tokio::time::timeout(timeout_duration, self.update_stats())
+                        .await
+                        .is_err()
+                    {
+                        warn!(target: LOG_TARGET, "Merge mining stats update timed out");
+                    }
+                    HealthStatus::Healthy
+                }
                 Err(e) => {
                     warn!(
                         target: LOG_TARGET,
@@ -212,6 +231,18 @@ impl StatusMonitor for MergeMiningProxyStatusMonitor {
 }
 
 impl MergeMiningProxyStatusMonitor {
+    async fn update_stats(&self) {
+        let new_log_lines = match self.log_tail.lock().await.read_new_lines().await {
+            Ok(lines) => lines,
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Failed to read mmproxy log: {e}");
+                Vec::new()
+            }
+        };
+
+        self.stats.write().await.record_log_lines(&new_log_lines);
+    }
+
     #[allow(dead_code)]
     pub async fn get_version(&self) -> Result<String, Error> {
         let rpc_url = format!("http://127.0.0.1:{}/json_rpc", self.json_rpc_port);
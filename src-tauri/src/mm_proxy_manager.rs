@@ -30,7 +30,9 @@ use tari_common_types::tari_address::TariAddress;
 use tokio::sync::RwLock;
 use tokio::time::sleep;
 
+use crate::merge_mining_stats::MergeMiningStats;
 use crate::mm_proxy_adapter::{MergeMiningProxyAdapter, MergeMiningProxyConfig};
+use crate::monero_node_prober::{probe_monero_nodes, ranked_monero_node_urls, MoneroNodeStatus};
 use crate::port_allocator::PortAllocator;
 use crate::process_adapter::{HealthStatus, StatusMonitor};
 use crate::process_stats_collector::ProcessStatsCollectorBuilder;
@@ -45,6 +47,7 @@ pub(crate) struct StartConfig {
     pub config_path: PathBuf,
     pub log_path: PathBuf,
     pub tari_address: TariAddress,
+    pub monero_address: String,
     pub base_node_grpc_address: String,
     pub coinbase_extra: String,
     pub monero_nodes: Vec<String>,
@@ -69,6 +72,7 @@ impl StartConfig {
 pub struct MmProxyManager {
     watcher: Arc<RwLock<ProcessWatcher<MergeMiningProxyAdapter>>>,
     start_config: Arc<RwLock<Option<StartConfig>>>,
+    monero_nodes_status: Arc<RwLock<Vec<MoneroNodeStatus>>>,
 }
 
 impl Clone for MmProxyManager {
@@ -76,6 +80,7 @@ impl Clone for MmProxyManager {
         Self {
             watcher: self.watcher.clone(),
             start_config: self.start_config.clone(),
+            monero_nodes_status: self.monero_nodes_status.clone(),
         }
     }
 }
@@ -92,6 +97,7 @@ impl MmProxyManager {
         Self {
             watcher: Arc::new(RwLock::new(process_watcher)),
             start_config: Arc::new(RwLock::new(None)),
+            monero_nodes_status: Arc::new(RwLock::new(Vec::new())),
         }
     }
 
@@ -104,14 +110,20 @@ impl MmProxyManager {
 
         let mut current_start_config = self.start_config.write().await;
         *current_start_config = Some(config.clone());
+
+        let monero_nodes_status = probe_monero_nodes(&config.monero_nodes).await;
+        let monero_nodes = ranked_monero_node_urls(&monero_nodes_status);
+        *self.monero_nodes_status.write().await = monero_nodes_status;
+
         let mut process_watcher = self.watcher.write().await;
 
         let new_config = MergeMiningProxyConfig {
             tari_address: config.tari_address.clone(),
+            monero_address: config.monero_address.clone(),
             base_node_grpc_address: config.base_node_grpc_address.clone(),
             coinbase_extra: config.coinbase_extra.clone(),
             port: PortAllocator::new().assign_port_with_fallback(),
-            monero_nodes: config.monero_nodes.clone(),
+            monero_nodes,
             use_monero_fail: config.use_monero_fail,
         };
         process_watcher.adapter.config = Some(new_config.clone());
@@ -163,6 +175,16 @@ impl MmProxyManager {
             .map(|c| c.port)
             .unwrap_or_default()
     }
+    pub async fn get_merge_mining_stats(&self) -> MergeMiningStats {
+        let stats = self.watcher.read().await.adapter.stats.clone();
+        let stats = stats.read().await;
+        stats.clone()
+    }
+
+    /// Result of the last probe of the configured monerod nodes, in the order handed to the proxy.
+    pub async fn get_monero_nodes_status(&self) -> Vec<MoneroNodeStatus> {
+        self.monero_nodes_status.read().await.clone()
+    }
     pub async fn get_monero_port(&self) -> Result<u16, anyhow::Error> {
         let lock = self.watcher.read().await;
         match lock.adapter.config.clone() {
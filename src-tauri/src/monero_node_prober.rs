@@ -0,0 +1,207 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::time::{Duration, Instant, SystemTime};
+
+use anyhow::{anyhow, Error};
+use futures::future::join_all;
+use log::{info, warn};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+const LOG_TARGET: &str = "tari::universe::monero_node_prober";
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_BLOCKS_BEHIND: u64 = 3;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct MoneroNodeStatus {
+    pub url: String,
+    pub is_reachable: bool,
+    pub is_synced: bool,
+    pub height: u64,
+    pub nettype: Option<String>,
+    pub latency_ms: Option<u64>,
+    pub last_error: Option<String>,
+    pub last_checked_at: Option<SystemTime>,
+}
+
+#[derive(Deserialize)]
+struct GetInfoResponse {
+    result: GetInfoResult,
+}
+
+#[derive(Deserialize)]
+struct GetInfoResult {
+    height: u64,
+    #[serde(default)]
+    synchronized: bool,
+    #[serde(default)]
+    nettype: Option<String>,
+}
+
+impl MoneroNodeStatus {
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            is_reachable: false,
+            is_synced: false,
+            height: 0,
+            nettype: None,
+            latency_ms: None,
+            last_error: None,
+            last_checked_at: None,
+        }
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.is_reachable && self.last_error.is_none()
+    }
+
+    async fn probe(&mut self, client: &Client) {
+        let started_at = Instant::now();
+        let request = client
+            .post(format!("{}/json_rpc", self.url.trim_end_matches('/')))
+            .timeout(PROBE_TIMEOUT)
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "id": "0",
+                "method": "get_info"
+            }))
+            .send();
+        let result = match request.await.and_then(|r| r.error_for_status()) {
+            Ok(response) => response
+                .json::<GetInfoResponse>()
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+
+        self.last_checked_at = Some(SystemTime::now());
+        match result {
+            Ok(response) => {
+                self.is_reachable = true;
+                self.is_synced = response.result.synchronized;
+                self.height = response.result.height;
+                self.nettype = response.result.nettype;
+                self.latency_ms = u64::try_from(started_at.elapsed().as_millis()).ok();
+                self.last_error = None;
+            }
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Monero node {} probe failed: {e}", self.url);
+                self.is_reachable = false;
+                self.is_synced = false;
+                self.latency_ms = None;
+                self.last_error = Some(e);
+            }
+        }
+    }
+}
+
+/// Accepts `host:port` as well as full urls, so a LAN monerod can be entered as `192.168.1.10:18081`.
+pub fn normalize_monero_node_url(url: &str) -> Result<String, Error> {
+    let url = url.trim().trim_end_matches('/');
+    if url.is_empty() {
+        return Err(anyhow!("Monero node url is empty"));
+    }
+    let url = if url.starts_with("http://") || url.starts_with("https://") {
+        url.to_string()
+    } else {
+        format!("http://{url}")
+    };
+    reqwest::Url::parse(&url).map_err(|e| anyhow!("Invalid Monero node url {url}: {e}"))?;
+    Ok(url)
+}
+
+pub async fn probe_monero_node(url: &str) -> MoneroNodeStatus {
+    let mut status = MoneroNodeStatus::new(url.to_string());
+    status.probe(&Client::new()).await;
+    status
+}
+
+/// Probes all nodes concurrently. Nodes that are unreachable, not synced or lagging behind the
+/// best known height are marked unhealthy. The result is ranked healthy first, by latency.
+pub async fn probe_monero_nodes(urls: &[String]) -> Vec<MoneroNodeStatus> {
+    let client = Client::new();
+    let mut nodes: Vec<MoneroNodeStatus> = urls
+        .iter()
+        .map(|url| MoneroNodeStatus::new(url.clone()))
+        .collect();
+    join_all(nodes.iter_mut().map(|node| node.probe(&client))).await;
+
+    let best_height = nodes
+        .iter()
+        .filter(|node| node.is_reachable)
+        .map(|node| node.height)
+        .max()
+        .unwrap_or(0);
+    for node in &mut nodes {
+        if node.is_reachable && !node.is_synced {
+            node.last_error = Some("Node is not synced".to_string());
+        }
+        if node.is_reachable && node.height + MAX_BLOCKS_BEHIND < best_height {
+            node.last_error = Some(format!(
+                "Behind best Monero node by {} blocks",
+                best_height - node.height
+            ));
+        }
+    }
+
+    nodes.sort_by_key(|node| (!node.is_healthy(), node.latency_ms.unwrap_or(u64::MAX)));
+    nodes
+}
+
+/// Urls of the healthy nodes in ranked order. Falls back to every configured node when none
+/// pass the probe, so a flaky probe never leaves the mm proxy without a monerod.
+pub fn ranked_monero_node_urls(nodes: &[MoneroNodeStatus]) -> Vec<String> {
+    let healthy: Vec<String> = nodes
+        .iter()
+        .filter(|node| node.is_healthy())
+        .map(|node| node.url.clone())
+        .collect();
+    if healthy.is_empty() {
+        warn!(target: LOG_TARGET, "No healthy Monero nodes found, using all configured nodes");
+        return nodes.iter().map(|node| node.url.clone()).collect();
+    }
+    info!(target: LOG_TARGET, "Using Monero nodes in order: {healthy:?}");
+    healthy
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_normalize_monero_node_url() {
+        assert_eq!(
+            normalize_monero_node_url("192.168.1.10:18081/").unwrap(),
+            "http://192.168.1.10:18081"
+        );
+        assert_eq!(
+            normalize_monero_node_url("https://xmr-01.tari.com").unwrap(),
+            "https://xmr-01.tari.com"
+        );
+        assert!(normalize_monero_node_url(" ").is_err());
+    }
+}
@@ -24,15 +24,18 @@ use crate::ab_test_selector::ABTestSelector;
 use crate::node::node_adapter::{
     BaseNodeStatus, NodeAdapter, NodeAdapterService, NodeStatusMonitor,
 };
-use crate::node::node_manager::NodeType;
+use crate::node::node_manager::{NodeStorageMode, NodeType};
 use crate::port_allocator::PortAllocator;
 use crate::process_adapter::{ProcessAdapter, ProcessInstance, ProcessStartupSpec};
+use crate::tor_adapter::BandwidthLimits;
 use crate::utils::file_utils::convert_to_string;
 use crate::utils::logging_utils::setup_logging;
 use async_trait::async_trait;
 use log::{info, warn};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
+use std::net::IpAddr;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
@@ -45,10 +48,23 @@ use tokio::sync::watch;
 use crate::utils::windows_setup_utils::add_firewall_rule;
 
 const LOG_TARGET: &str = "tari::universe::local_node_adapter";
+const NODE_MIGRATION_VERSION: u32 = 2;
+/// The node has no byte rate limit, with a bandwidth cap it keeps fewer peers and sync sessions
+const LOW_BANDWIDTH_NODE_CONFIG: [&str; 5] = [
+    "base_node.p2p.max_concurrent_inbound_tasks=4",
+    "base_node.p2p.max_concurrent_outbound_tasks=4",
+    "base_node.p2p.rpc_max_simultaneous_sessions=10",
+    "base_node.p2p.rpc_max_sessions_per_peer=2",
+    "base_node.p2p.dht.num_random_nodes=2",
+];
+const MIN_DESIRED_PEERS: u32 = 12;
+const LOW_BANDWIDTH_MIN_DESIRED_PEERS: u32 = 4;
 
 #[derive(Serialize, Deserialize, Default)]
 struct MinotariNodeMigrationInfo {
     version: u32,
+    #[serde(default)]
+    storage_mode: Option<NodeStorageMode>,
 }
 
 impl MinotariNodeMigrationInfo {
@@ -68,13 +84,94 @@ impl MinotariNodeMigrationInfo {
     }
 }
 
+/// Storage mode the existing node database was created with, `None` when there is no database yet
+pub(crate) fn read_database_storage_mode(
+    network_dir: &Path,
+) -> Result<Option<NodeStorageMode>, anyhow::Error> {
+    if !network_dir.join("data").exists() {
+        return Ok(None);
+    }
+    let migration_info =
+        MinotariNodeMigrationInfo::load_or_create(&network_dir.join("migrations.json"))?;
+    // Databases created before the storage mode was recorded were never pruned
+    Ok(Some(
+        migration_info
+            .storage_mode
+            .unwrap_or(NodeStorageMode::Archival),
+    ))
+}
+
+// Config keys users may override, `{network}` is replaced with the current network key.
+// Keys the app relies on (gRPC, wallet query service, storage) are managed by the app itself,
+// as are the peer seeds which come from the user's seed peer list.
+const NODE_CONFIG_OVERRIDE_KEYS: [&str; 13] = [
+    "base_node.p2p.dht.num_neighbouring_nodes",
+    "base_node.p2p.dht.num_random_nodes",
+    "base_node.p2p.dht.minimize_connections",
+    "base_node.p2p.dht.network_discovery.min_desired_peers",
+    "base_node.p2p.allow_test_addresses",
+    "base_node.p2p.transport.type",
+    "base_node.p2p.transport.tcp.listener_address",
+    "base_node.p2p.transport.tor.proxy_bypass_for_outbound_tcp",
+    "base_node.p2p.auxiliary_tcp_listener_address",
+    "base_node.p2p.public_addresses",
+    "base_node.state_machine.initial_sync_peer_count",
+    "use_libtor",
+    "{network}.p2p.seeds.dns_seeds",
+];
+
+fn is_overridable_key(key: &str) -> bool {
+    let network_key = Network::get_current_or_user_setting_or_default().as_key_str();
+    NODE_CONFIG_OVERRIDE_KEYS
+        .iter()
+        .any(|known_key| known_key.replace("{network}", network_key) == key)
+}
+
+pub fn validate_node_config_override(key: &str, value: &str) -> Result<(), anyhow::Error> {
+    if !is_overridable_key(key) {
+        return Err(anyhow::anyhow!("Unsupported node config key: {key}"));
+    }
+    if value.trim().is_empty() || value.contains(['\n', '\r']) {
+        return Err(anyhow::anyhow!("Invalid value for node config key {key}"));
+    }
+    Ok(())
+}
+
+/// Seed peers use the node's `<public key hex>::<multiaddr>` format
+pub fn validate_seed_peer(peer: &str) -> Result<(), anyhow::Error> {
+    let (public_key, address) = peer
+        .split_once("::")
+        .ok_or_else(|| anyhow::anyhow!("Seed peer must be in the form <public key>::<address>"))?;
+    if public_key.len() != 64 || !public_key.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(anyhow::anyhow!(
+            "Invalid seed peer public key: {public_key}"
+        ));
+    }
+    if !address.starts_with('/') || address.contains([',', ' ']) {
+        return Err(anyhow::anyhow!("Invalid seed peer address: {address}"));
+    }
+    Ok(())
+}
+
+/// Marks a database placed from outside the node (e.g. a snapshot) as not needing the startup migrations
+pub(crate) fn mark_database_migrated(network_dir: &Path) -> Result<(), anyhow::Error> {
+    let migration_file = network_dir.join("migrations.json");
+    let mut migration_info = MinotariNodeMigrationInfo::load_or_create(&migration_file)?;
+    migration_info.version = migration_info.version.max(NODE_MIGRATION_VERSION);
+    migration_info.save(&migration_file)
+}
+
 #[derive(Clone)]
 pub(crate) struct LocalNodeAdapter {
     pub(crate) grpc_address: Option<(String, u16)>,
     status_broadcast: watch::Sender<BaseNodeStatus>,
     pub(crate) use_tor: bool,
     pub(crate) tcp_listener_port: u16,
-    pub(crate) use_pruned_mode: bool,
+    pub(crate) storage_mode: NodeStorageMode,
+    pub(crate) config_overrides: HashMap<String, String>,
+    pub(crate) seed_peers: Vec<String>,
+    pub(crate) banned_peers: Vec<String>,
+    pub(crate) bandwidth_limits: BandwidthLimits,
     pub(crate) tor_control_port: Option<u16>,
     required_initial_peers: u32,
     pub(crate) ab_test_group: ABTestSelector,
@@ -91,7 +188,11 @@ impl LocalNodeAdapter {
             grpc_address: Some(("127.0.0.1".to_string(), grpc_port)),
             status_broadcast,
             tcp_listener_port,
-            use_pruned_mode: false,
+            storage_mode: NodeStorageMode::default(),
+            config_overrides: HashMap::new(),
+            seed_peers: Vec::new(),
+            banned_peers: Vec::new(),
+            bandwidth_limits: BandwidthLimits::default(),
             required_initial_peers: 3,
             use_tor: false,
             tor_control_port: None,
@@ -118,126 +219,14 @@ impl LocalNodeAdapter {
             None
         }
     }
-}
-
-#[async_trait]
-impl NodeAdapter for LocalNodeAdapter {
-    fn get_grpc_address(&self) -> Option<(String, u16)> {
-        self.get_grpc_address()
-    }
-
-    fn set_grpc_address(&mut self, _grpc_address: String) -> Result<(), anyhow::Error> {
-        log::error!(target: LOG_TARGET, "Attempted to set gRPC address for local node, which is fixed to localhost.");
-        Ok(())
-    }
-
-    fn get_service(&self) -> Option<NodeAdapterService> {
-        self.get_service()
-    }
-
-    async fn get_connection_details(&self) -> Result<(RistrettoPublicKey, String), anyhow::Error> {
-        let node_service = self.get_service();
-        if let Some(node_service) = node_service {
-            let node_identity = node_service.get_identity().await?;
-            let public_key = node_identity.public_key.clone();
-            Ok((public_key, self.tcp_address()))
-        } else {
-            Err(anyhow::anyhow!("Remote node service is not available"))
-        }
-    }
-
-    fn get_http_api_url(&self) -> String {
-        format!("http://127.0.0.1:{}", self.http_api_port)
-    }
-
-    fn use_tor(&mut self, use_tor: bool) {
-        self.use_tor = use_tor;
-    }
-
-    fn set_tor_control_port(&mut self, tor_control_port: Option<u16>) {
-        self.tor_control_port = tor_control_port;
-    }
-
-    fn set_ab_group(&mut self, ab_test_group: ABTestSelector) {
-        self.ab_test_group = ab_test_group;
-    }
-}
-
-impl ProcessAdapter for LocalNodeAdapter {
-    type StatusMonitor = NodeStatusMonitor;
-    type ProcessInstance = ProcessInstance;
 
     #[allow(clippy::too_many_lines)]
-    fn spawn_inner(
+    fn build_args(
         &self,
-        data_dir: PathBuf,
-        _config_dir: PathBuf,
-        log_dir: PathBuf,
-        binary_version_path: PathBuf,
-        _is_first_start: bool,
-    ) -> Result<(ProcessInstance, Self::StatusMonitor), anyhow::Error> {
-        let inner_shutdown = Shutdown::new();
-
-        info!(target: LOG_TARGET, "Starting minotari node");
-        let working_dir: PathBuf = data_dir.join("node");
-        let network_dir = working_dir.join(Network::get_current().to_string().to_lowercase());
-        fs::create_dir_all(&network_dir)?;
-        let migration_file = network_dir.join("migrations.json");
-        let mut migration_info = MinotariNodeMigrationInfo::load_or_create(&migration_file)?;
-
-        if migration_info.version < 2 {
-            // Delete the peer info db.
-            let peer_db_dir = network_dir.join("peer_db");
-            let node_db_dir = network_dir.join("data");
-            let config_dir = network_dir.join("config");
-            let libtor_dir = network_dir.join("libtor");
-
-            let dirs = vec![
-                peer_db_dir.clone(),
-                node_db_dir.clone(),
-                config_dir.clone(),
-                libtor_dir.clone(),
-            ];
-
-            for dir in dirs {
-                if dir.exists() {
-                    info!(target: LOG_TARGET, "Node migration v2: removing directory at {dir:?}");
-                    let _unused = fs::remove_dir_all(dir).inspect_err(|e| {
-                        warn!(target: LOG_TARGET, "Failed to remove directory: {e:?}");
-                    });
-                }
-            }
-
-            info!(target: LOG_TARGET, "Node Migration v2 complete");
-            migration_info.version = 2;
-        }
-        migration_info.save(&migration_file)?;
-
-        // Remove peerdb on every restart as requested by Protocol team
-        let peer_db_dir = network_dir.join("peer_db");
-        if peer_db_dir.exists() {
-            info!(target: LOG_TARGET, "Removing peer db at {peer_db_dir:?}");
-            let _unused = fs::remove_dir_all(peer_db_dir).inspect_err(|e| {
-                warn!(target: LOG_TARGET, "Failed to remove peer db: {e:?}");
-            });
-        }
-
-        let config_dir = log_dir
-            .clone()
-            .join("base_node")
-            .join("configs")
-            .join("log4rs_config_base_node.yml");
-        setup_logging(
-            &config_dir.clone(),
-            &log_dir,
-            include_str!("../../log4rs/base_node_sample.yml"),
-        )?;
-        let working_dir_string = convert_to_string(working_dir)?;
-        let config_dir_string = convert_to_string(config_dir)?;
-        let grpc_address = self
-            .get_grpc_address()
-            .expect("Local node grpc address not defined");
-
+        working_dir_string: String,
+        config_dir_string: String,
+        grpc_address: &(String, u16),
+    ) -> Vec<String> {
         let mut args: Vec<String> = vec![
             "-b".to_string(),
             working_dir_string,
@@ -259,20 +248,33 @@ impl ProcessAdapter for LocalNodeAdapter {
                 self.required_initial_peers
             ),
             "-p".to_string(),
-            "base_node.grpc_server_allow_methods=\"list_connected_peers, get_blocks\"".to_string(),
+            "base_node.grpc_server_allow_methods=\"list_connected_peers, get_blocks, list_headers, get_header_by_hash, get_network_difficulty, get_mempool_stats, get_mempool_transactions\"".to_string(),
             "-p".to_string(),
             "base_node.p2p.allow_test_addresses=true".to_string(),
             "-p".to_string(),
-            "base_node.p2p.dht.network_discovery.min_desired_peers=12".to_string(),
+            format!(
+                "base_node.p2p.dht.network_discovery.min_desired_peers={}",
+                if self.bandwidth_limits.is_limited() {
+                    LOW_BANDWIDTH_MIN_DESIRED_PEERS
+                } else {
+                    MIN_DESIRED_PEERS
+                }
+            ),
             "-p".to_string(),
             format!(
                 "base_node.http_wallet_query_service.port={}",
                 self.http_api_port
             ),
         ];
-        if self.use_pruned_mode {
+        if self.bandwidth_limits.is_limited() {
+            for setting in LOW_BANDWIDTH_NODE_CONFIG {
+                args.push("-p".to_string());
+                args.push(setting.to_string());
+            }
+        }
+        if let NodeStorageMode::Pruned { horizon } = self.storage_mode {
             args.push("-p".to_string());
-            args.push("base_node.storage.pruning_horizon=100".to_string());
+            args.push(format!("base_node.storage.pruning_horizon={horizon}"));
         }
         // Uncomment to test winning blocks
         // if cfg!(debug_assertions) {
@@ -294,8 +296,12 @@ impl ProcessAdapter for LocalNodeAdapter {
                 "base_node.p2p.auxiliary_tcp_listener_address=/ip4/0.0.0.0/tcp/{0}",
                 self.tcp_listener_port
             ));
+            // The node has no throttling of its own, so route everything through tor when capped
             args.push("-p".to_string());
-            args.push("base_node.p2p.transport.tor.proxy_bypass_for_outbound_tcp=true".to_string());
+            args.push(format!(
+                "base_node.p2p.transport.tor.proxy_bypass_for_outbound_tcp={}",
+                !self.bandwidth_limits.is_limited()
+            ));
             if let Some(mut tor_control_port) = self.tor_control_port {
                 // macos uses libtor, so will be 9051
                 if cfg!(target_os = "macos") {
@@ -353,6 +359,34 @@ impl ProcessAdapter for LocalNodeAdapter {
             }
         }
 
+        if !self.seed_peers.is_empty() {
+            let network = Network::get_current_or_user_setting_or_default();
+            args.push("-p".to_string());
+            args.push(format!(
+                "{key}.p2p.seeds.peer_seeds={peers}",
+                key = network.as_key_str(),
+                peers = self.seed_peers.join(","),
+            ));
+        }
+
+        // Only peers with an IP address can be excluded from dialing
+        let excluded_dial_addresses: Vec<String> = self
+            .banned_peers
+            .iter()
+            .filter_map(|host| match host.parse::<IpAddr>() {
+                Ok(IpAddr::V4(ip)) => Some(format!("/ip4/{ip}/tcp/*")),
+                Ok(IpAddr::V6(ip)) => Some(format!("/ip6/{ip}/tcp/*")),
+                Err(_) => None,
+            })
+            .collect();
+        if !excluded_dial_addresses.is_empty() {
+            args.push("-p".to_string());
+            args.push(format!(
+                "base_node.p2p.dht.excluded_dial_addresses={}",
+                excluded_dial_addresses.join(",")
+            ));
+        }
+
         // AB testing
         if self.ab_test_group == ABTestSelector::GroupB {
             info!(target: LOG_TARGET, "Using AB test group B");
@@ -369,6 +403,193 @@ impl ProcessAdapter for LocalNodeAdapter {
             args.push("base_node.p2p.dht.minimize_connections=false".to_string());
         }
 
+        self.apply_config_overrides(&mut args);
+        args
+    }
+
+    /// User overrides take precedence over the values we set, keys we don't set are appended.
+    /// Overrides saved before a key stopped being overridable are ignored.
+    fn apply_config_overrides(&self, args: &mut Vec<String>) {
+        let overrides: HashMap<&String, &String> = self
+            .config_overrides
+            .iter()
+            .filter(|(key, _)| is_overridable_key(key))
+            .collect();
+        let mut applied_keys = Vec::new();
+        for index in 1..args.len() {
+            if args[index - 1] != "-p" {
+                continue;
+            }
+            let key = args[index]
+                .split_once('=')
+                .map(|(key, _)| key.to_string())
+                .unwrap_or_default();
+            if let Some(value) = overrides.get(&key) {
+                args[index] = format!("{key}={value}");
+                applied_keys.push(key);
+            }
+        }
+
+        let mut remaining: Vec<(&String, &String)> = overrides
+            .into_iter()
+            .filter(|(key, _)| !applied_keys.contains(key))
+            .collect();
+        remaining.sort();
+        for (key, value) in remaining {
+            args.push("-p".to_string());
+            args.push(format!("{key}={value}"));
+        }
+    }
+
+    /// `-p` config values the node is started with
+    pub fn effective_config(&self) -> Vec<(String, String)> {
+        let grpc_address = self
+            .get_grpc_address()
+            .unwrap_or_else(|| ("127.0.0.1".to_string(), 0));
+        let args = self.build_args(String::new(), String::new(), &grpc_address);
+        args.iter()
+            .zip(args.iter().skip(1))
+            .filter(|(flag, _)| *flag == "-p")
+            .filter_map(|(_, entry)| entry.split_once('='))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl NodeAdapter for LocalNodeAdapter {
+    fn get_grpc_address(&self) -> Option<(String, u16)> {
+        self.get_grpc_address()
+    }
+
+    fn set_grpc_address(&mut self, _grpc_address: String) -> Result<(), anyhow::Error> {
+        log::error!(target: LOG_TARGET, "Attempted to set gRPC address for local node, which is fixed to localhost.");
+        Ok(())
+    }
+
+    fn get_service(&self) -> Option<NodeAdapterService> {
+        self.get_service()
+    }
+
+    async fn get_connection_details(&self) -> Result<(RistrettoPublicKey, String), anyhow::Error> {
+        let node_service = self.get_service();
+        if let Some(node_service) = node_service {
+            let node_identity = node_service.get_identity().await?;
+            let public_key = node_identity.public_key.clone();
+            Ok((public_key, self.tcp_address()))
+        } else {
+            Err(anyhow::anyhow!("Remote node service is not available"))
+        }
+    }
+
+    fn get_http_api_url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.http_api_port)
+    }
+
+    fn use_tor(&mut self, use_tor: bool) {
+        self.use_tor = use_tor;
+    }
+
+    fn set_tor_control_port(&mut self, tor_control_port: Option<u16>) {
+        self.tor_control_port = tor_control_port;
+    }
+
+    fn set_ab_group(&mut self, ab_test_group: ABTestSelector) {
+        self.ab_test_group = ab_test_group;
+    }
+}
+
+impl ProcessAdapter for LocalNodeAdapter {
+    type StatusMonitor = NodeStatusMonitor;
+    type ProcessInstance = ProcessInstance;
+
+    #[allow(clippy::too_many_lines)]
+    fn spawn_inner(
+        &self,
+        data_dir: PathBuf,
+        _config_dir: PathBuf,
+        log_dir: PathBuf,
+        binary_version_path: PathBuf,
+        _is_first_start: bool,
+    ) -> Result<(ProcessInstance, Self::StatusMonitor), anyhow::Error> {
+        let inner_shutdown = Shutdown::new();
+
+        info!(target: LOG_TARGET, "Starting minotari node");
+        let working_dir: PathBuf = data_dir.join("node");
+        let network_dir = working_dir.join(Network::get_current().to_string().to_lowercase());
+        fs::create_dir_all(&network_dir)?;
+        let migration_file = network_dir.join("migrations.json");
+        let mut migration_info = MinotariNodeMigrationInfo::load_or_create(&migration_file)?;
+
+        if migration_info.version < NODE_MIGRATION_VERSION {
+            // Delete the peer info db.
+            let peer_db_dir = network_dir.join("peer_db");
+            let node_db_dir = network_dir.join("data");
+            let config_dir = network_dir.join("config");
+            let libtor_dir = network_dir.join("libtor");
+
+            let dirs = vec![
+                peer_db_dir.clone(),
+                node_db_dir.clone(),
+                config_dir.clone(),
+                libtor_dir.clone(),
+            ];
+
+            for dir in dirs {
+                if dir.exists() {
+                    info!(target: LOG_TARGET, "Node migration v2: removing directory at {dir:?}");
+                    let _unused = fs::remove_dir_all(dir).inspect_err(|e| {
+                        warn!(target: LOG_TARGET, "Failed to remove directory: {e:?}");
+                    });
+                }
+            }
+
+            info!(target: LOG_TARGET, "Node Migration v2 complete");
+            migration_info.version = NODE_MIGRATION_VERSION;
+        }
+
+        // An incompatible database is only removed once the user confirms the resync through
+        // `set_node_storage_mode`, until then the node keeps running in the database's own mode
+        let mut node = self.clone();
+        if let Some(database_mode) = read_database_storage_mode(&network_dir)? {
+            if !self
+                .storage_mode
+                .is_compatible_with_database(&database_mode)
+            {
+                warn!(target: LOG_TARGET, "Node database was created in {database_mode:?} mode, keeping it instead of {:?} until a resync is confirmed", self.storage_mode);
+                node.storage_mode = database_mode;
+            }
+        }
+        migration_info.storage_mode = Some(node.storage_mode.clone());
+        migration_info.save(&migration_file)?;
+
+        // Remove peerdb on every restart as requested by Protocol team
+        let peer_db_dir = network_dir.join("peer_db");
+        if peer_db_dir.exists() {
+            info!(target: LOG_TARGET, "Removing peer db at {peer_db_dir:?}");
+            let _unused = fs::remove_dir_all(peer_db_dir).inspect_err(|e| {
+                warn!(target: LOG_TARGET, "Failed to remove peer db: {e:?}");
+            });
+        }
+
+        let config_dir = log_dir
+            .clone()
+            .join("base_node")
+            .join("configs")
+            .join("log4rs_config_base_node.yml");
+        setup_logging(
+            &config_dir.clone(),
+            &log_dir,
+            include_str!("../../log4rs/base_node_sample.yml"),
+        )?;
+        let working_dir_string = convert_to_string(working_dir)?;
+        let config_dir_string = convert_to_string(config_dir)?;
+        let grpc_address = self
+            .get_grpc_address()
+            .expect("Local node grpc address not defined");
+
+        let args = node.build_args(working_dir_string, config_dir_string, &grpc_address);
+
         #[cfg(target_os = "windows")]
         add_firewall_rule("minotari_node.exe".to_string(), binary_version_path.clone())?;
 
@@ -406,3 +627,122 @@ impl ProcessAdapter for LocalNodeAdapter {
         "node_pid"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn adapter_with_overrides(overrides: &[(&str, &str)]) -> LocalNodeAdapter {
+        let (status_broadcast, _status_receiver) = watch::channel(BaseNodeStatus::default());
+        let mut adapter = LocalNodeAdapter::new(status_broadcast);
+        adapter.use_tor = false;
+        adapter.config_overrides = overrides
+            .iter()
+            .map(|(key, value)| ((*key).to_string(), (*value).to_string()))
+            .collect();
+        adapter
+    }
+
+    fn config_values(adapter: &LocalNodeAdapter, key: &str) -> Vec<String> {
+        adapter
+            .effective_config()
+            .into_iter()
+            .filter(|(config_key, _)| config_key == key)
+            .map(|(_, value)| value)
+            .collect()
+    }
+
+    #[test]
+    fn overrides_replace_app_values_in_place_and_append_the_rest() {
+        let adapter = adapter_with_overrides(&[
+            (
+                "base_node.p2p.dht.network_discovery.min_desired_peers",
+                "20",
+            ),
+            ("use_libtor", "false"),
+            (
+                "base_node.p2p.transport.tor.proxy_bypass_for_outbound_tcp",
+                "true",
+            ),
+        ]);
+        let config = adapter.effective_config();
+        let position = |key: &str| config.iter().position(|(config_key, _)| config_key == key);
+
+        assert_eq!(
+            config_values(
+                &adapter,
+                "base_node.p2p.dht.network_discovery.min_desired_peers"
+            ),
+            vec!["20".to_string()]
+        );
+        assert!(
+            position("base_node.p2p.dht.network_discovery.min_desired_peers")
+                < position("base_node.p2p.transport.type")
+        );
+        let appended: Vec<&str> = config
+            .iter()
+            .rev()
+            .take(2)
+            .map(|(key, _)| key.as_str())
+            .collect();
+        assert!(appended.contains(&"use_libtor"));
+        assert!(appended.contains(&"base_node.p2p.transport.tor.proxy_bypass_for_outbound_tcp"));
+    }
+
+    #[test]
+    fn peer_seeds_only_come_from_the_seed_peer_list() {
+        let network_key = Network::get_current_or_user_setting_or_default().as_key_str();
+        let peer_seeds_key = format!("{network_key}.p2p.seeds.peer_seeds");
+        assert!(validate_node_config_override(&peer_seeds_key, "a::/ip4/1.2.3.4/tcp/1").is_err());
+
+        let seed_peer = format!("{}::/ip4/10.0.0.5/tcp/18189", "a".repeat(64));
+        let mut adapter = adapter_with_overrides(&[(&peer_seeds_key, "stale")]);
+        adapter.seed_peers = vec![seed_peer.clone()];
+        assert_eq!(config_values(&adapter, &peer_seeds_key), vec![seed_peer]);
+    }
+
+    #[test]
+    fn validates_overrides_and_seed_peers() {
+        assert!(validate_node_config_override("use_libtor", "true").is_ok());
+        assert!(validate_node_config_override("base_node.grpc_address", "x").is_err());
+        assert!(validate_node_config_override("use_libtor", "true\nuse_tor=false").is_err());
+
+        assert!(
+            validate_seed_peer(&format!("{}::/ip4/10.0.0.5/tcp/18189", "a".repeat(64))).is_ok()
+        );
+        assert!(validate_seed_peer("abc::/ip4/10.0.0.5/tcp/18189").is_err());
+        assert!(validate_seed_peer(&format!("{}::/ip4/1.2.3.4/tcp/1,x", "a".repeat(64))).is_err());
+    }
+
+    #[test]
+    fn detects_database_storage_mode() {
+        let network_dir = std::env::temp_dir().join(format!(
+            "local_node_adapter_storage_mode_{}",
+            std::process::id()
+        ));
+        let _unused = fs::remove_dir_all(&network_dir);
+        fs::create_dir_all(&network_dir).unwrap();
+        assert_eq!(read_database_storage_mode(&network_dir).unwrap(), None);
+
+        fs::create_dir_all(network_dir.join("data")).unwrap();
+        assert_eq!(
+            read_database_storage_mode(&network_dir).unwrap(),
+            Some(NodeStorageMode::Archival)
+        );
+
+        let migration_info = MinotariNodeMigrationInfo {
+            version: NODE_MIGRATION_VERSION,
+            storage_mode: Some(NodeStorageMode::Pruned { horizon: 1000 }),
+        };
+        migration_info
+            .save(&network_dir.join("migrations.json"))
+            .unwrap();
+        assert_eq!(
+            read_database_storage_mode(&network_dir).unwrap(),
+            Some(NodeStorageMode::Pruned { horizon: 1000 })
+        );
+
+        fs::remove_dir_all(&network_dir).unwrap();
+    }
+}
@@ -22,5 +22,8 @@
 
 pub mod local_node_adapter;
 pub mod node_adapter;
+pub mod node_database_maintenance;
 pub mod node_manager;
+pub mod node_snapshot;
 pub mod remote_node_adapter;
+pub mod remote_node_prober;
@@ -26,7 +26,9 @@ use crate::process_adapter::{HealthStatus, StatusMonitor};
 use anyhow::{anyhow, Error};
 use async_trait::async_trait;
 use minotari_node_grpc_client::grpc::{
-    BlockHeader, Empty, GetBlocksRequest, GetNetworkStateRequest, SyncState,
+    BlockHeader, BlockHeaderResponse, Empty, GetBlocksRequest, GetHeaderByHashRequest,
+    GetMempoolTransactionsRequest, GetNetworkStateRequest, HeightRequest, ListHeadersRequest,
+    SyncState,
 };
 use std::path::PathBuf;
 use std::time::{Duration, SystemTime};
@@ -34,6 +36,7 @@ use tari_utilities::epoch_time::EpochTime;
 use tokio::fs;
 
 use chrono::{NaiveDateTime, TimeZone, Utc};
+use futures::future::join_all;
 use log::{error, info, warn};
 use minotari_node_grpc_client::BaseNodeGrpcClient;
 use serde::Serialize;
@@ -53,6 +56,15 @@ use tokio::time::timeout;
 use crate::network_utils::{get_best_block_from_block_scan, get_block_info_from_block_scan};
 
 const LOG_TARGET: &str = "tari::universe::minotari_node_adapter";
+// Depths below the tip compared with other nodes, the deepest one bounds how far back a fork is found
+const CHAIN_CONSENSUS_SAMPLE_DEPTHS: [u64; 6] = [50, 100, 200, 500, 1000, 2000];
+// Multiaddr protocol codes, all encoded as single byte varints
+const MULTIADDR_IP4: u8 = 4;
+const MULTIADDR_TCP: u8 = 6;
+const MULTIADDR_IP6: u8 = 41;
+const MULTIADDR_DNS: u8 = 53;
+const MULTIADDR_DNS4: u8 = 54;
+const MULTIADDR_DNS6: u8 = 55;
 
 #[async_trait]
 pub trait NodeAdapter {
@@ -310,13 +322,219 @@ impl NodeAdapterService {
         Ok(connected_peers)
     }
 
-    pub async fn check_if_is_orphan_chain(&self) -> Result<bool, anyhow::Error> {
-        let BaseNodeStatus { is_synced, .. } = self.get_network_state().await?;
+    pub async fn list_connected_peers_details(&self) -> Result<Vec<PeerDetails>, anyhow::Error> {
+        let mut client = BaseNodeGrpcClient::connect(self.connection_address.clone()).await?;
+        let peers_list = client
+            .list_connected_peers(Empty {})
+            .await
+            .map_err(|e| anyhow::anyhow!("Error list_connected_peers: {}", e))?
+            .into_inner()
+            .connected_peers;
+
+        let peers = peers_list
+            .into_iter()
+            .map(|peer| {
+                let address = peer.addresses.first();
+                PeerDetails {
+                    public_key: peer.public_key.to_hex(),
+                    node_id: peer.node_id.to_hex(),
+                    address: address.map(|a| {
+                        multiaddr_to_string(&a.address).unwrap_or_else(|| a.address.to_hex())
+                    }),
+                    last_seen: address.map(|a| a.last_seen.clone()),
+                    latency_ms: address
+                        .and_then(|a| a.avg_latency.as_ref())
+                        .map(|latency| latency.latency),
+                    user_agent: peer.user_agent,
+                    banned_until: (peer.banned_until > 0).then_some(peer.banned_until),
+                    banned_reason: (!peer.banned_reason.is_empty()).then_some(peer.banned_reason),
+                }
+            })
+            .collect();
+
+        Ok(peers)
+    }
+
+    pub async fn get_block_headers(
+        &self,
+        from_height: u64,
+        num_headers: u64,
+    ) -> Result<Vec<BlockHeaderInfo>, anyhow::Error> {
+        let mut client = BaseNodeGrpcClient::connect(self.connection_address.clone()).await?;
+        let mut stream = client
+            .list_headers(ListHeadersRequest {
+                from_height,
+                num_headers,
+                // Descending, newest first
+                sorting: 0,
+            })
+            .await?
+            .into_inner();
+
+        let mut headers = Vec::new();
+        while let Some(response) = stream.message().await? {
+            headers.push(BlockHeaderInfo::try_from(response)?);
+        }
+        Ok(headers)
+    }
+
+    pub async fn get_block_header_by_hash(
+        &self,
+        hash: Vec<u8>,
+    ) -> Result<BlockHeaderInfo, anyhow::Error> {
+        let mut client = BaseNodeGrpcClient::connect(self.connection_address.clone()).await?;
+        let response = client
+            .get_header_by_hash(GetHeaderByHashRequest { hash })
+            .await?
+            .into_inner();
+        BlockHeaderInfo::try_from(response)
+    }
+
+    /// Difficulty of the last `num_blocks` blocks, each entry is tagged with the block's PoW algorithm
+    pub async fn get_difficulty_history(
+        &self,
+        num_blocks: u64,
+    ) -> Result<Vec<DifficultySample>, anyhow::Error> {
+        let mut client = BaseNodeGrpcClient::connect(self.connection_address.clone()).await?;
+        let mut stream = client
+            .get_network_difficulty(HeightRequest {
+                from_tip: num_blocks,
+                start_height: 0,
+                end_height: 0,
+            })
+            .await?
+            .into_inner();
+
+        let mut samples = Vec::new();
+        while let Some(response) = stream.message().await? {
+            samples.push(DifficultySample {
+                height: response.height,
+                timestamp: response.timestamp,
+                pow_algo: PowAlgorithm::from(response.pow_algo),
+                difficulty: response.difficulty,
+                estimated_hash_rate: response.estimated_hash_rate,
+            });
+        }
+        Ok(samples)
+    }
+
+    pub async fn get_mempool_stats(&self) -> Result<MempoolStats, anyhow::Error> {
+        let mut client = BaseNodeGrpcClient::connect(self.connection_address.clone()).await?;
+        let stats = client.get_mempool_stats(Empty {}).await?.into_inner();
+
+        let mut stream = client
+            .get_mempool_transactions(GetMempoolTransactionsRequest {})
+            .await?
+            .into_inner();
+        let mut fees: Vec<u64> = Vec::new();
+        while let Some(response) = stream.message().await? {
+            let fee = response
+                .transaction
+                .and_then(|transaction| transaction.body)
+                .map(|body| body.kernels.iter().map(|kernel| kernel.fee).sum())
+                .unwrap_or(0);
+            fees.push(fee);
+        }
+
+        Ok(MempoolStats {
+            unconfirmed_txs: stats.unconfirmed_txs,
+            reorg_txs: stats.reorg_txs,
+            unconfirmed_weight: stats.unconfirmed_weight,
+            fee_distribution: FeeDistribution::from_fees(fees),
+        })
+    }
+
+    /// Compares our chain with the reference nodes we can query over gRPC and only falls back
+    /// to the block explorer when none of them respond. The node's gRPC can't read headers from
+    /// its p2p peers, so the configured remote nodes are the only other chains we can sample.
+    pub async fn check_if_is_orphan_chain(
+        &self,
+        reference_nodes: Vec<NodeAdapterService>,
+    ) -> Result<ChainConsensus, anyhow::Error> {
+        let BaseNodeStatus {
+            is_synced,
+            block_height,
+            ..
+        } = self.get_network_state().await?;
         if !is_synced {
             info!(target: LOG_TARGET, "Node is not synced, skipping orphan chain check");
-            return Ok(false);
+            return Ok(ChainConsensus::default());
+        }
+        if self.list_connected_peers().await?.is_empty() {
+            info!(target: LOG_TARGET, "Node has no connected peers, skipping orphan chain check");
+            return Ok(ChainConsensus::default());
         }
 
+        match self
+            .check_chain_consensus(block_height, reference_nodes)
+            .await?
+        {
+            Some(consensus) => Ok(consensus),
+            None => {
+                info!(target: LOG_TARGET, "No reference nodes available, checking chain against block explorer");
+                Ok(ChainConsensus {
+                    is_orphan: self.check_chain_against_block_scan().await?,
+                    ..ChainConsensus::default()
+                })
+            }
+        }
+    }
+
+    /// `None` when no reference node could answer
+    async fn check_chain_consensus(
+        &self,
+        tip_height: u64,
+        reference_nodes: Vec<NodeAdapterService>,
+    ) -> Result<Option<ChainConsensus>, anyhow::Error> {
+        if reference_nodes.is_empty() {
+            return Ok(None);
+        }
+        let mut heights: Vec<u64> = CHAIN_CONSENSUS_SAMPLE_DEPTHS
+            .iter()
+            .map(|depth| tip_height.saturating_sub(*depth))
+            .collect();
+        heights.dedup();
+        let local_blocks = self.get_historical_blocks(heights.clone()).await?;
+
+        let reference_blocks = join_all(reference_nodes.iter().map(|reference_node| {
+            let heights = heights.clone();
+            async move {
+                let result = timeout(
+                    Duration::from_secs(10),
+                    reference_node.get_historical_blocks(heights),
+                )
+                .await;
+                (reference_node.connection_address.clone(), result)
+            }
+        }))
+        .await;
+
+        let mut peer_blocks = Vec::new();
+        for (address, result) in reference_blocks {
+            match result {
+                Ok(Ok(blocks)) => peer_blocks.push((address, blocks)),
+                Ok(Err(e)) => {
+                    warn!(target: LOG_TARGET, "Reference node {address} failed to return blocks: {e}");
+                }
+                Err(_) => {
+                    warn!(target: LOG_TARGET, "Reference node {address} timed out returning blocks");
+                }
+            }
+        }
+
+        let consensus = decide_chain_consensus(tip_height, &local_blocks, &peer_blocks);
+        if let Some(consensus) = &consensus {
+            for peer in &consensus.stale_reference_nodes {
+                warn!(target: LOG_TARGET, "Reference node {peer} does not follow the majority chain");
+            }
+            if consensus.is_orphan {
+                error!(target: LOG_TARGET, "Miner is stuck on orphan chain. Chain left the majority chain {:?} blocks below the tip", consensus.reorg_depth);
+            }
+        }
+        Ok(consensus)
+    }
+
+    async fn check_chain_against_block_scan(&self) -> Result<bool, anyhow::Error> {
         let network = Network::get_current_or_user_setting_or_default();
         let block_scan_tip = get_best_block_from_block_scan(network).await?;
         let heights: Vec<u64> = vec![
@@ -481,6 +699,230 @@ impl StatusMonitor for NodeStatusMonitor {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub enum PowAlgorithm {
+    RandomXMonero,
+    Sha3x,
+    RandomXTari,
+    Unknown,
+}
+
+impl From<u64> for PowAlgorithm {
+    fn from(value: u64) -> Self {
+        match value {
+            0 => PowAlgorithm::RandomXMonero,
+            1 => PowAlgorithm::Sha3x,
+            2 => PowAlgorithm::RandomXTari,
+            _ => PowAlgorithm::Unknown,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BlockHeaderInfo {
+    pub height: u64,
+    pub hash: String,
+    pub prev_hash: String,
+    pub timestamp: u64,
+    pub pow_algo: PowAlgorithm,
+    pub difficulty: u64,
+    pub reward: MicroMinotari,
+    pub num_transactions: u32,
+    pub confirmations: u64,
+}
+
+impl TryFrom<BlockHeaderResponse> for BlockHeaderInfo {
+    type Error = anyhow::Error;
+
+    fn try_from(response: BlockHeaderResponse) -> Result<Self, Self::Error> {
+        let header = response
+            .header
+            .ok_or_else(|| anyhow!("Block header missing from response"))?;
+        Ok(Self {
+            height: header.height,
+            hash: header.hash.to_hex(),
+            prev_hash: header.prev_hash.to_hex(),
+            timestamp: header.timestamp,
+            pow_algo: header
+                .pow
+                .map(|pow| PowAlgorithm::from(pow.pow_algo))
+                .unwrap_or(PowAlgorithm::Unknown),
+            difficulty: response.difficulty,
+            reward: MicroMinotari(response.reward),
+            num_transactions: response.num_transactions,
+            confirmations: response.confirmations,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DifficultySample {
+    pub height: u64,
+    pub timestamp: u64,
+    pub pow_algo: PowAlgorithm,
+    pub difficulty: u64,
+    pub estimated_hash_rate: u64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct FeeDistribution {
+    pub min: MicroMinotari,
+    pub p25: MicroMinotari,
+    pub median: MicroMinotari,
+    pub p75: MicroMinotari,
+    pub max: MicroMinotari,
+}
+
+impl FeeDistribution {
+    pub fn from_fees(mut fees: Vec<u64>) -> Option<Self> {
+        if fees.is_empty() {
+            return None;
+        }
+        fees.sort_unstable();
+        let percentile = |p: usize| MicroMinotari(fees[(fees.len() - 1) * p / 100]);
+        Some(Self {
+            min: percentile(0),
+            p25: percentile(25),
+            median: percentile(50),
+            p75: percentile(75),
+            max: percentile(100),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct MempoolStats {
+    pub unconfirmed_txs: u64,
+    pub reorg_txs: u64,
+    pub unconfirmed_weight: u64,
+    pub fee_distribution: Option<FeeDistribution>,
+}
+
+/// Outcome of comparing our chain with the chains other nodes report
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChainConsensus {
+    pub is_orphan: bool,
+    /// Blocks the node has to rewind to get back to the majority chain, `None` when the fork
+    /// is older than the blocks we sampled
+    pub reorg_depth: Option<u64>,
+    /// Reference nodes whose chain disagrees with the majority, only logged and never banned
+    pub stale_reference_nodes: Vec<String>,
+}
+
+/// Sampled heights are compared one by one, the majority hash at a height wins and ties go to
+/// our own block. `None` when no peer returned any blocks.
+pub fn decide_chain_consensus(
+    tip_height: u64,
+    local_blocks: &[(u64, String)],
+    peer_blocks: &[(String, Vec<(u64, String)>)],
+) -> Option<ChainConsensus> {
+    if peer_blocks.iter().all(|(_, blocks)| blocks.is_empty()) {
+        return None;
+    }
+
+    let mut majority_blocks: HashMap<u64, String> = HashMap::new();
+    for (height, local_hash) in local_blocks {
+        let mut votes: HashMap<&String, usize> = HashMap::new();
+        for (_, blocks) in peer_blocks {
+            for (_, hash) in blocks
+                .iter()
+                .filter(|(peer_height, _)| peer_height == height)
+            {
+                *votes.entry(hash).or_default() += 1;
+            }
+        }
+        let local_votes = votes.get(local_hash).copied().unwrap_or_default();
+        let majority = votes
+            .into_iter()
+            .filter(|(_, count)| *count > local_votes)
+            .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+            .map_or_else(|| local_hash.clone(), |(hash, _)| hash.clone());
+        majority_blocks.insert(*height, majority);
+    }
+
+    let stale_reference_nodes = peer_blocks
+        .iter()
+        .filter(|(_, blocks)| {
+            blocks.iter().any(|(height, hash)| {
+                majority_blocks
+                    .get(height)
+                    .is_some_and(|majority| majority != hash)
+            })
+        })
+        .map(|(address, _)| address.clone())
+        .collect();
+
+    let lowest_fork = local_blocks
+        .iter()
+        .filter(|(height, hash)| majority_blocks.get(height) != Some(hash))
+        .map(|(height, _)| *height)
+        .min();
+    let reorg_depth = lowest_fork.and_then(|fork_height| {
+        local_blocks
+            .iter()
+            .map(|(height, _)| *height)
+            .filter(|height| *height < fork_height)
+            .max()
+            .map(|common_height| tip_height.saturating_sub(common_height))
+    });
+
+    Some(ChainConsensus {
+        is_orphan: lowest_fork.is_some(),
+        reorg_depth,
+        stale_reference_nodes,
+    })
+}
+
+/// Readable form of a binary multiaddr, `None` for protocols we don't decode (e.g. onion)
+fn multiaddr_to_string(bytes: &[u8]) -> Option<String> {
+    let mut address = String::new();
+    let mut rest = bytes;
+    while let Some((&code, tail)) = rest.split_first() {
+        rest = match code {
+            MULTIADDR_IP4 => {
+                let (ip, tail) = tail.split_first_chunk::<4>()?;
+                write!(address, "/ip4/{}", std::net::Ipv4Addr::from(*ip)).ok()?;
+                tail
+            }
+            MULTIADDR_IP6 => {
+                let (ip, tail) = tail.split_first_chunk::<16>()?;
+                write!(address, "/ip6/{}", std::net::Ipv6Addr::from(*ip)).ok()?;
+                tail
+            }
+            MULTIADDR_TCP => {
+                let (port, tail) = tail.split_first_chunk::<2>()?;
+                write!(address, "/tcp/{}", u16::from_be_bytes(*port)).ok()?;
+                tail
+            }
+            MULTIADDR_DNS | MULTIADDR_DNS4 | MULTIADDR_DNS6 => {
+                let (&len, tail) = tail.split_first()?;
+                let (name, tail) = tail.split_at_checked(usize::from(len))?;
+                let protocol = match code {
+                    MULTIADDR_DNS4 => "dns4",
+                    MULTIADDR_DNS6 => "dns6",
+                    _ => "dns",
+                };
+                write!(address, "/{protocol}/{}", std::str::from_utf8(name).ok()?).ok()?;
+                tail
+            }
+            _ => return None,
+        };
+    }
+    (!address.is_empty()).then_some(address)
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PeerDetails {
+    pub public_key: String,
+    pub node_id: String,
+    pub address: Option<String>,
+    pub last_seen: Option<String>,
+    pub latency_ms: Option<u64>,
+    pub user_agent: String,
+    pub banned_until: Option<u64>,
+    pub banned_reason: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct NodeIdentity {
     pub public_key: RistrettoPublicKey,
@@ -684,3 +1126,86 @@ pub enum NodeStatusMonitorError {
     #[error("Node not started")]
     NodeNotStarted,
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn blocks(hashes: &[(u64, &str)]) -> Vec<(u64, String)> {
+        hashes
+            .iter()
+            .map(|(height, hash)| (*height, (*hash).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn decodes_peer_multiaddr() {
+        assert_eq!(
+            multiaddr_to_string(&[4, 10, 0, 0, 5, 6, 0x46, 0xfd]),
+            Some("/ip4/10.0.0.5/tcp/18173".to_string())
+        );
+        assert_eq!(
+            multiaddr_to_string(&[54, 4, b'n', b'o', b'd', b'e', 6, 0, 80]),
+            Some("/dns4/node/tcp/80".to_string())
+        );
+        assert_eq!(multiaddr_to_string(&[4, 10, 0]), None);
+        assert_eq!(multiaddr_to_string(&[0xbd, 0x03, 1, 2]), None);
+    }
+
+    #[test]
+    fn following_the_majority_bans_the_stale_peer() {
+        let local = blocks(&[(900, "a"), (800, "b")]);
+        let peers = vec![
+            ("node-1".to_string(), blocks(&[(900, "a"), (800, "b")])),
+            ("node-2".to_string(), blocks(&[(900, "a"), (800, "b")])),
+            ("node-3".to_string(), blocks(&[(900, "x"), (800, "b")])),
+        ];
+        let consensus = decide_chain_consensus(1000, &local, &peers).unwrap();
+        assert!(!consensus.is_orphan);
+        assert_eq!(consensus.reorg_depth, None);
+        assert_eq!(consensus.stale_reference_nodes, vec!["node-3".to_string()]);
+    }
+
+    #[test]
+    fn orphaned_chain_rewinds_to_the_last_common_sample() {
+        let local = blocks(&[(950, "a"), (900, "b"), (800, "c")]);
+        let peers = vec![
+            (
+                "node-1".to_string(),
+                blocks(&[(950, "x"), (900, "y"), (800, "c")]),
+            ),
+            (
+                "node-2".to_string(),
+                blocks(&[(950, "x"), (900, "y"), (800, "c")]),
+            ),
+            (
+                "node-3".to_string(),
+                blocks(&[(950, "a"), (900, "b"), (800, "c")]),
+            ),
+        ];
+        let consensus = decide_chain_consensus(1000, &local, &peers).unwrap();
+        assert!(consensus.is_orphan);
+        assert_eq!(consensus.reorg_depth, Some(200));
+        assert_eq!(consensus.stale_reference_nodes, vec!["node-3".to_string()]);
+    }
+
+    #[test]
+    fn fork_below_the_samples_and_ties_keep_our_chain() {
+        let local = blocks(&[(950, "a"), (900, "b")]);
+        let peers = vec![("node-1".to_string(), blocks(&[(950, "x"), (900, "y")]))];
+        let consensus = decide_chain_consensus(1000, &local, &peers).unwrap();
+        assert!(consensus.is_orphan);
+        assert_eq!(consensus.reorg_depth, None);
+
+        let peers = vec![
+            ("node-1".to_string(), blocks(&[(950, "x"), (900, "b")])),
+            ("node-2".to_string(), blocks(&[(950, "a"), (900, "b")])),
+        ];
+        let consensus = decide_chain_consensus(1000, &local, &peers).unwrap();
+        assert!(!consensus.is_orphan);
+        assert_eq!(consensus.stale_reference_nodes, vec!["node-1".to_string()]);
+
+        assert_eq!(decide_chain_consensus(1000, &local, &[]), None);
+    }
+}
@@ -0,0 +1,458 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::anyhow;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::Network;
+use tauri::AppHandle;
+use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio::sync::watch;
+
+use crate::progress_trackers::progress_plans::{ProgressNodeMaintenancePlan, ProgressPlans};
+use crate::progress_trackers::progress_stepper::{
+    ChanneledStepUpdate, ProgressStepper, ProgressStepperBuilder,
+};
+
+const LOG_TARGET: &str = "tari::universe::node_database_maintenance";
+
+const LMDB_DATA_FILE: &str = "data.mdb";
+const LMDB_LOCK_FILE: &str = "lock.mdb";
+const BACKUP_INFO_FILE: &str = "backup.json";
+const LMDB_MAGIC: u32 = 0xBEEF_C0DE;
+const LMDB_DATA_VERSION: u32 = 1;
+// Offsets into a meta page for the 64-bit layout of MDB_meta (see lmdb's mdb.c)
+const META_MAGIC_OFFSET: usize = 16;
+const META_VERSION_OFFSET: usize = 20;
+// mm_dbs[FREE_DBI].md_pad holds the page size
+const META_PAGE_SIZE_OFFSET: usize = 40;
+const META_LAST_PAGE_OFFSET: usize = 136;
+const META_TXN_ID_OFFSET: usize = 144;
+const META_LEN: usize = 152;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LmdbFileReport {
+    pub path: String,
+    pub size_bytes: u64,
+    /// Bytes covered by the pages of the latest committed transaction
+    pub used_bytes: u64,
+    pub last_txn_id: u64,
+    pub issues: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeDatabaseReport {
+    pub files: Vec<LmdbFileReport>,
+    pub is_healthy: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeDatabaseBackupInfo {
+    pub block_height: u64,
+    pub created_at: u64,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub enum NodeDatabaseRepairOutcome {
+    AlreadyHealthy,
+    /// The whole backup replaced the database, the node resyncs from `block_height`
+    RestoredBackup {
+        block_height: u64,
+    },
+    Resync,
+}
+
+struct LmdbMeta {
+    page_size: u64,
+    last_page: u64,
+    txn_id: u64,
+}
+
+impl LmdbMeta {
+    fn parse(page: &[u8]) -> Result<Self, anyhow::Error> {
+        let read_u32 = |offset: usize| -> Result<u32, anyhow::Error> {
+            Ok(u32::from_le_bytes(
+                page.get(offset..offset + 4)
+                    .ok_or_else(|| anyhow!("Meta page is truncated"))?
+                    .try_into()?,
+            ))
+        };
+        let read_u64 = |offset: usize| -> Result<u64, anyhow::Error> {
+            Ok(u64::from_le_bytes(
+                page.get(offset..offset + 8)
+                    .ok_or_else(|| anyhow!("Meta page is truncated"))?
+                    .try_into()?,
+            ))
+        };
+
+        if read_u32(META_MAGIC_OFFSET)? != LMDB_MAGIC {
+            return Err(anyhow!("Invalid LMDB magic"));
+        }
+        let version = read_u32(META_VERSION_OFFSET)?;
+        if version != LMDB_DATA_VERSION {
+            return Err(anyhow!("Unsupported LMDB data version {version}"));
+        }
+        let page_size = u64::from(read_u32(META_PAGE_SIZE_OFFSET)?);
+        if !page_size.is_power_of_two() || !(512..=65536).contains(&page_size) {
+            return Err(anyhow!("Invalid LMDB page size {page_size}"));
+        }
+
+        Ok(Self {
+            page_size,
+            last_page: read_u64(META_LAST_PAGE_OFFSET)?,
+            txn_id: read_u64(META_TXN_ID_OFFSET)?,
+        })
+    }
+}
+
+/// Offline checks and repairs for the local node's LMDB databases. The node must be stopped.
+pub struct NodeDatabaseMaintenance;
+
+impl NodeDatabaseMaintenance {
+    pub fn network_dir(base_path: &Path) -> PathBuf {
+        base_path
+            .join("node")
+            .join(Network::get_current().to_string().to_lowercase())
+    }
+
+    pub fn backup_dir(base_path: &Path) -> PathBuf {
+        base_path
+            .join("node")
+            .join("backups")
+            .join(Network::get_current().to_string().to_lowercase())
+    }
+
+    pub fn create_progress_stepper(
+        app_handle: AppHandle,
+        steps: &[ProgressNodeMaintenancePlan],
+        timeout_watcher_sender: watch::Sender<u64>,
+    ) -> ProgressStepper {
+        let mut builder = ProgressStepperBuilder::new();
+        for step in steps {
+            builder.add_step(ProgressPlans::NodeMaintenance(step.clone()));
+        }
+        builder.build(app_handle, timeout_watcher_sender)
+    }
+
+    /// Checks both meta pages of every LMDB file and that the file covers all pages in use,
+    /// which catches the truncated writes a power cut usually leaves behind
+    pub async fn verify(network_dir: &Path) -> Result<NodeDatabaseReport, anyhow::Error> {
+        let data_dir = network_dir.join("data");
+        if !data_dir.exists() {
+            return Err(anyhow!("Node database does not exist"));
+        }
+
+        let mut files = Vec::new();
+        for path in Self::list_files(&data_dir).await? {
+            if path.file_name().is_some_and(|name| name == LMDB_DATA_FILE) {
+                files.push(Self::verify_file(&path).await?);
+            }
+        }
+        if files.is_empty() {
+            return Err(anyhow!("No LMDB database found in {}", data_dir.display()));
+        }
+
+        let is_healthy = files.iter().all(|f| f.issues.is_empty());
+        info!(target: LOG_TARGET, "Verified {} node database files, healthy: {is_healthy}", files.len());
+        Ok(NodeDatabaseReport { files, is_healthy })
+    }
+
+    async fn verify_file(path: &Path) -> Result<LmdbFileReport, anyhow::Error> {
+        let size_bytes = fs::metadata(path).await?.len();
+        let mut report = LmdbFileReport {
+            path: path.display().to_string(),
+            size_bytes,
+            used_bytes: 0,
+            last_txn_id: 0,
+            issues: Vec::new(),
+        };
+
+        let mut file = fs::File::open(path).await?;
+        let mut first_page = vec![0u8; META_LEN];
+        if file.read_exact(&mut first_page).await.is_err() {
+            report
+                .issues
+                .push("File is too small to be an LMDB database".to_string());
+            return Ok(report);
+        }
+        let first_meta = match LmdbMeta::parse(&first_page) {
+            Ok(meta) => meta,
+            Err(e) => {
+                report.issues.push(format!("First meta page: {e}"));
+                return Ok(report);
+            }
+        };
+
+        let mut second_page = vec![0u8; META_LEN];
+        let second_meta = match Self::read_at(&mut file, first_meta.page_size, &mut second_page)
+            .await
+            .and_then(|_| LmdbMeta::parse(&second_page))
+        {
+            Ok(meta) => Some(meta),
+            Err(e) => {
+                report.issues.push(format!("Second meta page: {e}"));
+                None
+            }
+        };
+
+        // LMDB alternates commits between the two meta pages, the newest one is current
+        let current = match second_meta {
+            Some(second) if second.txn_id > first_meta.txn_id => second,
+            _ => first_meta,
+        };
+        report.last_txn_id = current.txn_id;
+        report.used_bytes = (current.last_page + 1) * current.page_size;
+        if report.used_bytes > size_bytes {
+            report.issues.push(format!(
+                "File is truncated: {} bytes in use but only {size_bytes} on disk",
+                report.used_bytes
+            ));
+        }
+
+        Ok(report)
+    }
+
+    async fn read_at(
+        file: &mut fs::File,
+        offset: u64,
+        buf: &mut [u8],
+    ) -> Result<(), anyhow::Error> {
+        file.seek(std::io::SeekFrom::Start(offset)).await?;
+        file.read_exact(buf).await?;
+        Ok(())
+    }
+
+    /// Truncates the space past the last page in use, returns the number of bytes reclaimed.
+    /// This is not a compacting copy: free pages inside the file stay allocated and are
+    /// reused by LMDB, only the map growth at the end is given back.
+    pub async fn trim(network_dir: &Path) -> Result<u64, anyhow::Error> {
+        let report = Self::verify(network_dir).await?;
+        if !report.is_healthy {
+            return Err(anyhow!(
+                "Node database is corrupt, repair it before trimming"
+            ));
+        }
+
+        let mut reclaimed = 0;
+        for file_report in &report.files {
+            if file_report.size_bytes > file_report.used_bytes {
+                let file = fs::OpenOptions::new()
+                    .write(true)
+                    .open(&file_report.path)
+                    .await?;
+                file.set_len(file_report.used_bytes).await?;
+                file.sync_all().await?;
+                reclaimed += file_report.size_bytes - file_report.used_bytes;
+            }
+        }
+        info!(target: LOG_TARGET, "Trimmed node database, reclaimed {reclaimed} bytes");
+        Ok(reclaimed)
+    }
+
+    /// Copies the database to `backup_dir`, replacing the previous backup once the copy completes
+    pub async fn backup(
+        network_dir: &Path,
+        backup_dir: &Path,
+        block_height: u64,
+        progress_tracker: Option<ChanneledStepUpdate>,
+    ) -> Result<NodeDatabaseBackupInfo, anyhow::Error> {
+        let report = Self::verify(network_dir).await?;
+        if !report.is_healthy {
+            return Err(anyhow!("Node database is corrupt, refusing to back it up"));
+        }
+
+        let staging_dir = backup_dir.with_extension("tmp");
+        if staging_dir.exists() {
+            fs::remove_dir_all(&staging_dir).await?;
+        }
+        let size_bytes = Self::copy_database(network_dir, &staging_dir, progress_tracker).await?;
+
+        let backup_info = NodeDatabaseBackupInfo {
+            block_height,
+            created_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            size_bytes,
+        };
+        fs::write(
+            staging_dir.join(BACKUP_INFO_FILE),
+            serde_json::to_string(&backup_info)?,
+        )
+        .await?;
+
+        if backup_dir.exists() {
+            fs::remove_dir_all(backup_dir).await?;
+        }
+        fs::rename(&staging_dir, backup_dir).await?;
+        info!(target: LOG_TARGET, "Backed up node database at height {block_height} to {}", backup_dir.display());
+        Ok(backup_info)
+    }
+
+    pub async fn read_backup_info(backup_dir: &Path) -> Option<NodeDatabaseBackupInfo> {
+        let content = fs::read_to_string(backup_dir.join(BACKUP_INFO_FILE))
+            .await
+            .ok()?;
+        serde_json::from_str(&content)
+            .inspect_err(|e| warn!(target: LOG_TARGET, "Invalid node database backup info: {e}"))
+            .ok()
+    }
+
+    /// Replaces a corrupt database with the last good backup as a whole, so the node goes back
+    /// to the backup's height and syncs from there. There is no partial, height based rewind.
+    /// Without a usable backup the database is cleared for a full resync.
+    pub async fn repair(
+        network_dir: &Path,
+        backup_dir: &Path,
+        progress_tracker: Option<ChanneledStepUpdate>,
+    ) -> Result<NodeDatabaseRepairOutcome, anyhow::Error> {
+        match Self::verify(network_dir).await {
+            Ok(report) if report.is_healthy => {
+                return Ok(NodeDatabaseRepairOutcome::AlreadyHealthy);
+            }
+            Ok(report) => {
+                warn!(target: LOG_TARGET, "Node database is corrupt: {:?}", report.files);
+            }
+            Err(e) => warn!(target: LOG_TARGET, "Could not verify node database: {e}"),
+        }
+
+        let data_dir = network_dir.join("data");
+        if data_dir.exists() {
+            fs::remove_dir_all(&data_dir).await?;
+        }
+
+        if let Some(backup_info) = Self::read_backup_info(backup_dir).await {
+            match Self::verify(backup_dir).await {
+                Ok(report) if report.is_healthy => {
+                    match Self::copy_database(backup_dir, network_dir, progress_tracker).await {
+                        Ok(_) => {
+                            info!(target: LOG_TARGET, "Restored node database backup from height {}", backup_info.block_height);
+                            return Ok(NodeDatabaseRepairOutcome::RestoredBackup {
+                                block_height: backup_info.block_height,
+                            });
+                        }
+                        Err(e) => {
+                            warn!(target: LOG_TARGET, "Failed to restore node database backup: {e}");
+                        }
+                    }
+                }
+                _ => {
+                    warn!(target: LOG_TARGET, "Node database backup is not usable, falling back to a resync")
+                }
+            }
+        }
+
+        // Leftovers from a failed restore would be picked up as the database
+        if data_dir.exists() {
+            fs::remove_dir_all(&data_dir).await?;
+        }
+        Ok(NodeDatabaseRepairOutcome::Resync)
+    }
+
+    /// Copies the `data` folder and migration info between node folders, returns the bytes copied
+    async fn copy_database(
+        from: &Path,
+        to: &Path,
+        progress_tracker: Option<ChanneledStepUpdate>,
+    ) -> Result<u64, anyhow::Error> {
+        let source_data_dir = from.join("data");
+        let files: Vec<PathBuf> = Self::list_files(&source_data_dir)
+            .await?
+            .into_iter()
+            .filter(|path| path.file_name().is_none_or(|name| name != LMDB_LOCK_FILE))
+            .collect();
+        let mut total_bytes = 0;
+        for file in &files {
+            total_bytes += fs::metadata(file).await?.len();
+        }
+
+        let mut copied_bytes = 0;
+        for file in &files {
+            let target = to.join("data").join(file.strip_prefix(&source_data_dir)?);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).await?;
+            }
+            copied_bytes += fs::copy(file, &target).await?;
+            if let Some(progress_tracker) = &progress_tracker {
+                let progress = copied_bytes as f64 / total_bytes.max(1) as f64;
+                progress_tracker
+                    .send_update(std::collections::HashMap::new(), progress)
+                    .await;
+            }
+        }
+
+        let migration_file = from.join("migrations.json");
+        if migration_file.exists() {
+            fs::copy(migration_file, to.join("migrations.json")).await?;
+        }
+        Ok(copied_bytes)
+    }
+
+    async fn list_files(dir: &Path) -> Result<Vec<PathBuf>, anyhow::Error> {
+        let mut files = Vec::new();
+        let mut pending = vec![dir.to_path_buf()];
+        while let Some(current) = pending.pop() {
+            let mut entries = fs::read_dir(&current).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                if entry.file_type().await?.is_dir() {
+                    pending.push(entry.path());
+                } else {
+                    files.push(entry.path());
+                }
+            }
+        }
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn meta_page(txn_id: u64, last_page: u64) -> Vec<u8> {
+        let mut page = vec![0u8; META_LEN];
+        page[META_MAGIC_OFFSET..META_MAGIC_OFFSET + 4].copy_from_slice(&LMDB_MAGIC.to_le_bytes());
+        page[META_VERSION_OFFSET..META_VERSION_OFFSET + 4]
+            .copy_from_slice(&LMDB_DATA_VERSION.to_le_bytes());
+        page[META_PAGE_SIZE_OFFSET..META_PAGE_SIZE_OFFSET + 4]
+            .copy_from_slice(&4096u32.to_le_bytes());
+        page[META_LAST_PAGE_OFFSET..META_LAST_PAGE_OFFSET + 8]
+            .copy_from_slice(&last_page.to_le_bytes());
+        page[META_TXN_ID_OFFSET..META_TXN_ID_OFFSET + 8].copy_from_slice(&txn_id.to_le_bytes());
+        page
+    }
+
+    #[test]
+    fn parses_meta_page_and_rejects_bad_magic() {
+        let meta = LmdbMeta::parse(&meta_page(7, 9)).unwrap();
+        assert_eq!(meta.page_size, 4096);
+        assert_eq!(meta.last_page, 9);
+        assert_eq!(meta.txn_id, 7);
+
+        let mut corrupt = meta_page(7, 9);
+        corrupt[META_MAGIC_OFFSET] = 0;
+        assert!(LmdbMeta::parse(&corrupt).is_err());
+    }
+}
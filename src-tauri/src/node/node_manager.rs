@@ -22,7 +22,7 @@
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicU32};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -31,6 +31,7 @@ use serde::{Deserialize, Serialize};
 use tari_common::configuration::Network;
 use tari_crypto::ristretto::RistrettoPublicKey;
 use tari_shutdown::ShutdownSignal;
+use tauri::AppHandle;
 use tokio::sync::watch::{self, Sender};
 use tokio::sync::RwLock;
 use tokio::time::sleep;
@@ -40,15 +41,21 @@ use tokio_util::task::TaskTracker;
 use crate::configs::config_core::ConfigCore;
 use crate::configs::trait_config::ConfigImpl;
 use crate::events_emitter::EventsEmitter;
+use crate::node::local_node_adapter::read_database_storage_mode;
 use crate::node::node_adapter::{
-    NodeAdapter, NodeAdapterService, NodeIdentity, NodeStatusMonitorError, ReadinessStatus,
+    BlockHeaderInfo, ChainConsensus, DifficultySample, MempoolStats, NodeAdapter,
+    NodeAdapterService, NodeIdentity, NodeStatusMonitorError, PeerDetails, ReadinessStatus,
+};
+use crate::node::remote_node_adapter::{grpc_connection_url, parse_grpc_address};
+use crate::node::remote_node_prober::{
+    find_failover_target, find_healthiest, probe_endpoints, RemoteNodeEndpointStatus,
 };
 use crate::process_adapter::ProcessAdapter;
 use crate::process_stats_collector::ProcessStatsCollectorBuilder;
 use crate::process_watcher::ProcessWatcher;
 use crate::process_watcher::ProcessWatcherStats;
 use crate::progress_trackers::progress_stepper::ChanneledStepUpdate;
-use crate::setup::setup_manager::SetupManager;
+use crate::setup::setup_manager::{SetupManager, SetupPhase};
 use crate::tasks_tracker::TasksTrackers;
 use crate::{BaseNodeStatus, LocalNodeAdapter, RemoteNodeAdapter};
 
@@ -63,6 +70,11 @@ pub enum NodeManagerError {
 }
 
 pub const STOP_ON_ERROR_CODES: [i32; 2] = [114, 102];
+const LOCAL_NODE_FALLBACK_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const LOCAL_NODE_FALLBACK_AFTER_CHECKS: u32 = 4;
+const LOCAL_NODE_MAX_BLOCKS_BEHIND: u64 = 10;
+// Per app session, so a node that keeps forking off doesn't resync in a loop
+const MAX_ORPHAN_CHAIN_RECOVERY_ATTEMPTS: u32 = 2;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub enum NodeType {
@@ -85,6 +97,67 @@ impl NodeType {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub enum NodeStorageMode {
+    Pruned {
+        horizon: u64,
+    },
+    // Local node has historically been started without a pruning horizon
+    #[default]
+    Archival,
+}
+
+impl NodeStorageMode {
+    /// A pruned database no longer has the full history, so it can't be turned into an archival one
+    pub fn is_compatible_with_database(&self, database_mode: &NodeStorageMode) -> bool {
+        !matches!(
+            (database_mode, self),
+            (NodeStorageMode::Pruned { .. }, NodeStorageMode::Archival)
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum OrphanChainRecovery {
+    /// Restart with fresh peers, the node reorgs back to the fork point by itself
+    Rewind,
+    Resync,
+}
+
+fn orphan_chain_recovery(
+    consensus: &ChainConsensus,
+    storage_mode: &NodeStorageMode,
+    attempt: u32,
+) -> OrphanChainRecovery {
+    let can_rewind = match (consensus.reorg_depth, storage_mode) {
+        (Some(depth), NodeStorageMode::Pruned { horizon }) => depth < *horizon,
+        (Some(_), NodeStorageMode::Archival) => true,
+        (None, _) => attempt == 0,
+    };
+    if can_rewind {
+        OrphanChainRecovery::Rewind
+    } else {
+        OrphanChainRecovery::Resync
+    }
+}
+
+/// Host part of a gRPC url or multiaddr, the unit peers are banned by
+pub(crate) fn peer_host(address: &str) -> Option<String> {
+    if address.starts_with('/') {
+        let mut parts = address.split('/').skip(1);
+        return match (parts.next(), parts.next()) {
+            (Some("ip4" | "ip6" | "dns" | "dns4" | "dns6"), Some(host)) => Some(host.to_string()),
+            _ => None,
+        };
+    }
+    let without_scheme = address.split_once("://").map_or(address, |(_, rest)| rest);
+    without_scheme
+        .split(['/', ':'])
+        .next()
+        .filter(|host| !host.is_empty())
+        .map(str::to_string)
+}
+
 #[derive(Clone)]
 pub struct NodeManager {
     node_type: Arc<RwLock<NodeType>>,
@@ -95,6 +168,9 @@ pub struct NodeManager {
     local_node_watch_rx: watch::Receiver<BaseNodeStatus>,
     remote_node_watch_rx: watch::Receiver<BaseNodeStatus>,
     local_node_db_cleared: Arc<AtomicBool>,
+    remote_endpoints: Arc<RwLock<Vec<RemoteNodeEndpointStatus>>>,
+    orphan_chain_recovery_attempts: Arc<AtomicU32>,
+    metered_sync_triggered: Arc<AtomicBool>,
 }
 
 impl NodeManager {
@@ -135,6 +211,9 @@ impl NodeManager {
             local_node_watch_rx,
             remote_node_watch_rx,
             local_node_db_cleared: Arc::new(AtomicBool::new(false)),
+            remote_endpoints: Arc::new(RwLock::new(Vec::new())),
+            orphan_chain_recovery_attempts: Arc::new(AtomicU32::new(0)),
+            metered_sync_triggered: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -153,6 +232,18 @@ impl NodeManager {
         let task_tracker = TasksTrackers::current().node_phase.get_task_tracker().await;
 
         if self.is_local().await {
+            let storage_mode = ConfigCore::content().await.node_storage_mode().clone();
+            let config_overrides = ConfigCore::content().await.node_config_overrides().clone();
+            let seed_peers = ConfigCore::content().await.node_seed_peers().clone();
+            let banned_peers = ConfigCore::content().await.node_banned_peers().clone();
+            let bandwidth_limits = *ConfigCore::content().await.bandwidth_limits();
+            if let Some(local_node_watcher) = self.local_node_watcher.write().await.as_mut() {
+                local_node_watcher.adapter.storage_mode = storage_mode;
+                local_node_watcher.adapter.config_overrides = config_overrides;
+                local_node_watcher.adapter.seed_peers = seed_peers;
+                local_node_watcher.adapter.banned_peers = banned_peers;
+                local_node_watcher.adapter.bandwidth_limits = bandwidth_limits;
+            }
             self.configure_adapter(
                 self.local_node_watcher.clone(),
                 self.is_local_current().await,
@@ -396,6 +487,59 @@ impl NodeManager {
         Ok(())
     }
 
+    /// On a metered connection the local node is held back until the user triggers a sync
+    pub fn is_local_sync_allowed(&self, metered_connection: bool) -> bool {
+        !metered_connection
+            || self
+                .metered_sync_triggered
+                .load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    pub fn set_metered_sync_triggered(&self, triggered: bool) {
+        self.metered_sync_triggered
+            .store(triggered, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    pub fn get_database_storage_mode(
+        &self,
+        base_path: &Path,
+    ) -> Result<Option<NodeStorageMode>, anyhow::Error> {
+        read_database_storage_mode(
+            &base_path
+                .join("node")
+                .join(Network::get_current().to_string().to_lowercase()),
+        )
+    }
+
+    /// Removes the local node database, the node must be stopped
+    pub async fn remove_database(&self, base_path: &Path) -> Result<(), anyhow::Error> {
+        let node_db_dir = base_path
+            .join("node")
+            .join(Network::get_current().to_string().to_lowercase())
+            .join("data");
+        if node_db_dir.exists() {
+            fs::remove_dir_all(node_db_dir).await?;
+        }
+        Ok(())
+    }
+
+    /// Config the local node runs with, or would run with on its next start
+    pub async fn get_local_node_effective_config(&self) -> Vec<(String, String)> {
+        let config = ConfigCore::content().await;
+        let local_node_watcher = self.local_node_watcher.read().await;
+        match local_node_watcher.as_ref() {
+            Some(local_node_watcher) => {
+                let mut adapter = local_node_watcher.adapter.clone();
+                adapter.storage_mode = config.node_storage_mode().clone();
+                adapter.config_overrides = config.node_config_overrides().clone();
+                adapter.seed_peers = config.node_seed_peers().clone();
+                adapter.banned_peers = config.node_banned_peers().clone();
+                adapter.effective_config()
+            }
+            None => Vec::new(),
+        }
+    }
+
     pub async fn get_node_type(&self) -> NodeType {
         let node_type = self.node_type.read().await;
         node_type.clone()
@@ -451,9 +595,124 @@ impl NodeManager {
         current_adapter.get_http_api_url()
     }
 
+    /// Probes the configured remote endpoints and picks the one the remote adapter should use.
+    /// Keeps the previously active endpoint while it is healthy.
+    pub async fn select_remote_grpc_address(&self, addresses: Vec<String>) -> Option<String> {
+        let mut endpoints = self.remote_endpoints.write().await;
+        let previous_active = endpoints
+            .iter()
+            .find(|endpoint| endpoint.is_active)
+            .map(|endpoint| endpoint.address.clone());
+        let mut updated_endpoints: Vec<RemoteNodeEndpointStatus> = addresses
+            .into_iter()
+            .map(|address| {
+                endpoints
+                    .iter()
+                    .find(|endpoint| endpoint.address == address)
+                    .cloned()
+                    .unwrap_or_else(|| RemoteNodeEndpointStatus::new(address))
+            })
+            .collect();
+        if updated_endpoints.len() > 1 {
+            probe_endpoints(&mut updated_endpoints).await;
+        }
+
+        let selected = previous_active
+            .and_then(|previous| {
+                updated_endpoints
+                    .iter()
+                    .find(|endpoint| endpoint.address == previous && endpoint.is_healthy())
+            })
+            .or_else(|| find_healthiest(&updated_endpoints))
+            .or_else(|| updated_endpoints.first())
+            .map(|endpoint| endpoint.address.clone());
+        for endpoint in updated_endpoints.iter_mut() {
+            endpoint.is_active = selected.as_ref() == Some(&endpoint.address);
+        }
+        *endpoints = updated_endpoints;
+
+        selected
+    }
+
+    /// Re-probes the remote endpoints, returns the address to switch to if the active one is
+    /// unhealthy or the primary one recovered. The target is marked active, so the restart picks it.
+    pub async fn check_remote_endpoints(&self) -> Option<String> {
+        let mut endpoints = self.remote_endpoints.read().await.clone();
+        if endpoints.len() < 2 {
+            return None;
+        }
+        probe_endpoints(&mut endpoints).await;
+        let failover_target = find_failover_target(&endpoints);
+        if let Some(target) = &failover_target {
+            for endpoint in endpoints.iter_mut() {
+                endpoint.is_active = endpoint.address == *target;
+            }
+        }
+        *self.remote_endpoints.write().await = endpoints;
+        failover_target
+    }
+
+    pub async fn get_remote_endpoints_statuses(&self) -> Vec<RemoteNodeEndpointStatus> {
+        self.remote_endpoints.read().await.clone()
+    }
+
     pub async fn check_if_is_orphan_chain(&self) -> Result<bool, anyhow::Error> {
+        Ok(self.check_chain_consensus().await?.is_orphan)
+    }
+
+    pub async fn check_chain_consensus(&self) -> Result<ChainConsensus, anyhow::Error> {
         let current_service = self.get_current_service().await?;
-        current_service.check_if_is_orphan_chain().await
+        let current_address = self.get_grpc_address().await.ok();
+        let reference_nodes = ConfigCore::content()
+            .await
+            .remote_base_node_addresses()
+            .iter()
+            .filter_map(|address| parse_grpc_address(address).ok())
+            .map(|grpc_address| grpc_connection_url(&grpc_address))
+            .filter(|address| current_address.as_ref() != Some(address))
+            .map(|address| NodeAdapterService::new(address, 1))
+            .collect();
+        current_service
+            .check_if_is_orphan_chain(reference_nodes)
+            .await
+    }
+
+    pub fn can_recover_from_orphan_chain(&self) -> bool {
+        self.orphan_chain_recovery_attempts
+            .load(std::sync::atomic::Ordering::SeqCst)
+            < MAX_ORPHAN_CHAIN_RECOVERY_ATTEMPTS
+    }
+
+    /// Restarts the node with fresh peers so it reorgs back to the fork point on its own. When
+    /// the fork is older than what the node can rewind, or a restart already failed to get it off
+    /// the fork, the database has to be resynced: that is left to the user, who is asked to
+    /// confirm through `OrphanChainResyncRequired`.
+    pub async fn recover_from_orphan_chain(
+        &self,
+        app_handle: AppHandle,
+        consensus: ChainConsensus,
+    ) -> Result<(), anyhow::Error> {
+        let attempt = self
+            .orphan_chain_recovery_attempts
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        let storage_mode = ConfigCore::content().await.node_storage_mode().clone();
+
+        match orphan_chain_recovery(&consensus, &storage_mode, attempt) {
+            OrphanChainRecovery::Rewind => {
+                info!(target: LOG_TARGET, "Orphan chain recovery: restarting node to rewind {:?} blocks", consensus.reorg_depth);
+                SetupManager::get_instance()
+                    .restart_phases(
+                        app_handle,
+                        vec![SetupPhase::Node, SetupPhase::Wallet, SetupPhase::Mining],
+                    )
+                    .await;
+            }
+            OrphanChainRecovery::Resync => {
+                info!(target: LOG_TARGET, "Orphan chain recovery: waiting for the user to confirm a resync");
+                EventsEmitter::emit_orphan_chain_resync_required(consensus.reorg_depth).await;
+            }
+        }
+        Ok(())
     }
 
     pub async fn list_connected_peers(&self) -> Result<Vec<String>, anyhow::Error> {
@@ -461,6 +720,43 @@ impl NodeManager {
         current_service.list_connected_peers().await
     }
 
+    pub async fn list_connected_peers_details(&self) -> Result<Vec<PeerDetails>, anyhow::Error> {
+        let current_service = self.get_current_service().await?;
+        current_service.list_connected_peers_details().await
+    }
+
+    pub async fn get_block_headers(
+        &self,
+        from_height: u64,
+        num_headers: u64,
+    ) -> Result<Vec<BlockHeaderInfo>, anyhow::Error> {
+        let current_service = self.get_current_service().await?;
+        current_service
+            .get_block_headers(from_height, num_headers)
+            .await
+    }
+
+    pub async fn get_block_header_by_hash(
+        &self,
+        hash: Vec<u8>,
+    ) -> Result<BlockHeaderInfo, anyhow::Error> {
+        let current_service = self.get_current_service().await?;
+        current_service.get_block_header_by_hash(hash).await
+    }
+
+    pub async fn get_difficulty_history(
+        &self,
+        num_blocks: u64,
+    ) -> Result<Vec<DifficultySample>, anyhow::Error> {
+        let current_service = self.get_current_service().await?;
+        current_service.get_difficulty_history(num_blocks).await
+    }
+
+    pub async fn get_mempool_stats(&self) -> Result<MempoolStats, anyhow::Error> {
+        let current_service = self.get_current_service().await?;
+        current_service.get_mempool_stats().await
+    }
+
     // Self Checks
     pub async fn is_local(&self) -> bool {
         let node_type = self.get_node_type().await;
@@ -755,6 +1051,9 @@ async fn monitor_local_node_sync_and_switch(
                             sleep(Duration::from_secs(30)).await;
                             info!(target: LOG_TARGET, "Local node synced, switching node type...");
                             switch_to_local(node_manager.clone(), node_type.clone()).await;
+                            if *ConfigCore::content().await.fallback_to_remote_node() {
+                                monitor_local_node_and_fallback(node_manager.clone()).await;
+                            }
                             break;
                         }
                         Err(NodeStatusMonitorError::NodeNotStarted) => {
@@ -772,6 +1071,72 @@ async fn monitor_local_node_sync_and_switch(
     }
 }
 
+/// Watches the local node after switching to it and goes back to the remote node
+/// when the local one stops responding or falls behind
+async fn monitor_local_node_and_fallback(node_manager: NodeManager) {
+    let mut unhealthy_checks = 0;
+    loop {
+        sleep(LOCAL_NODE_FALLBACK_CHECK_INTERVAL).await;
+
+        let local_service = {
+            let local_node_watcher = node_manager.local_node_watcher.read().await;
+            local_node_watcher
+                .as_ref()
+                .and_then(|watcher| watcher.adapter.get_service())
+        };
+        let remote_service = {
+            let remote_node_watcher = node_manager.remote_node_watcher.read().await;
+            remote_node_watcher
+                .as_ref()
+                .and_then(|watcher| watcher.adapter.get_service())
+        };
+        let (Some(local_service), Some(remote_service)) = (local_service, remote_service) else {
+            return;
+        };
+
+        let local_height =
+            tokio::time::timeout(Duration::from_secs(10), local_service.get_network_state())
+                .await
+                .ok()
+                .and_then(|status| status.ok())
+                .map(|status| status.block_height);
+        let remote_height =
+            tokio::time::timeout(Duration::from_secs(10), remote_service.get_network_state())
+                .await
+                .ok()
+                .and_then(|status| status.ok())
+                .map(|status| status.block_height);
+
+        let is_unhealthy = match (local_height, remote_height) {
+            (None, _) => {
+                warn!(target: LOG_TARGET, "Local node is not responding");
+                true
+            }
+            (Some(local_height), Some(remote_height))
+                if local_height + LOCAL_NODE_MAX_BLOCKS_BEHIND < remote_height =>
+            {
+                warn!(target: LOG_TARGET, "Local node is behind remote node: {local_height} < {remote_height}");
+                true
+            }
+            _ => false,
+        };
+        unhealthy_checks = if is_unhealthy {
+            unhealthy_checks + 1
+        } else {
+            0
+        };
+
+        if unhealthy_checks >= LOCAL_NODE_FALLBACK_AFTER_CHECKS {
+            info!(target: LOG_TARGET, "Falling back to remote node until local node is synced again");
+            node_manager.set_node_type(NodeType::RemoteUntilLocal).await;
+            SetupManager::get_instance()
+                .handle_fallback_to_remote_node()
+                .await;
+            return;
+        }
+    }
+}
+
 async fn switch_to_local(node_manager: NodeManager, node_type: Arc<RwLock<NodeType>>) {
     {
         let mut node_type = node_type.write().await;
@@ -798,3 +1163,55 @@ async fn switch_to_local(node_manager: NodeManager, node_type: Arc<RwLock<NodeTy
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_peer_host() {
+        assert_eq!(
+            peer_host("https://grpc.tari.com:443"),
+            Some("grpc.tari.com".to_string())
+        );
+        assert_eq!(
+            peer_host("/ip4/10.0.0.5/tcp/18189"),
+            Some("10.0.0.5".to_string())
+        );
+        assert_eq!(peer_host("/onion3/abcdef:18141"), None);
+    }
+
+    #[test]
+    fn rewinds_within_pruning_horizon_and_resyncs_beyond_it() {
+        let consensus = ChainConsensus {
+            is_orphan: true,
+            reorg_depth: Some(500),
+            stale_reference_nodes: Vec::new(),
+        };
+        assert_eq!(
+            orphan_chain_recovery(&consensus, &NodeStorageMode::Pruned { horizon: 1000 }, 1),
+            OrphanChainRecovery::Rewind
+        );
+        assert_eq!(
+            orphan_chain_recovery(&consensus, &NodeStorageMode::Pruned { horizon: 100 }, 0),
+            OrphanChainRecovery::Resync
+        );
+        assert_eq!(
+            orphan_chain_recovery(&consensus, &NodeStorageMode::Archival, 1),
+            OrphanChainRecovery::Rewind
+        );
+
+        let unknown_fork = ChainConsensus {
+            reorg_depth: None,
+            ..consensus
+        };
+        assert_eq!(
+            orphan_chain_recovery(&unknown_fork, &NodeStorageMode::Archival, 0),
+            OrphanChainRecovery::Rewind
+        );
+        assert_eq!(
+            orphan_chain_recovery(&unknown_fork, &NodeStorageMode::Archival, 1),
+            OrphanChainRecovery::Resync
+        );
+    }
+}
@@ -0,0 +1,233 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::Network;
+use tokio::fs;
+use tokio::sync::watch;
+
+use crate::download_utils::{extract, validate_checksum};
+use crate::node::local_node_adapter::mark_database_migrated;
+use crate::progress_trackers::progress_stepper::ChanneledStepUpdate;
+use crate::requests::clients::http_file_client::HttpFileClient;
+use crate::tasks_tracker::TasksTrackers;
+
+const LOG_TARGET: &str = "tari::universe::node_snapshot";
+
+/// Archive of a synced node's `<network>` folder (at least its `data` directory)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NodeSnapshotSource {
+    /// Local file path, `file://` url or http(s) url, usually on the LAN
+    pub url: String,
+    /// Hex encoded SHA-256 of the archive
+    pub sha256: String,
+}
+
+impl NodeSnapshotSource {
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        if self.url.trim().is_empty() {
+            return Err(anyhow!("Snapshot url is empty"));
+        }
+        if self.sha256.len() != 64 || !self.sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(anyhow!("Snapshot checksum must be a hex encoded SHA-256"));
+        }
+        Ok(())
+    }
+
+    fn local_path(&self) -> Option<PathBuf> {
+        if self.url.starts_with("http://") || self.url.starts_with("https://") {
+            return None;
+        }
+        Some(PathBuf::from(
+            self.url.strip_prefix("file://").unwrap_or(&self.url),
+        ))
+    }
+}
+
+pub struct NodeSnapshot;
+
+impl NodeSnapshot {
+    /// Seeds the local node database from the snapshot. Only runs for a fresh node,
+    /// returns `false` when a database already exists.
+    pub async fn bootstrap(
+        source: &NodeSnapshotSource,
+        base_path: &Path,
+        progress_tracker: Option<ChanneledStepUpdate>,
+    ) -> Result<bool, anyhow::Error> {
+        let network_dir = Self::network_dir(base_path);
+        if network_dir.join("data").exists() {
+            info!(target: LOG_TARGET, "Node database already exists, skipping snapshot bootstrap");
+            return Ok(false);
+        }
+
+        let result = match Self::stage(source, base_path, progress_tracker).await {
+            Ok(snapshot_root) => Self::install(&snapshot_root, &network_dir).await,
+            Err(e) => Err(e),
+        };
+        Self::remove_staging(base_path).await;
+        result?;
+
+        info!(target: LOG_TARGET, "Node database bootstrapped from snapshot {}", source.url);
+        Ok(true)
+    }
+
+    /// Downloads, verifies and extracts the snapshot into `snapshot_staging`, the node can keep
+    /// running meanwhile. Returns the staged directory that contains the `data` folder.
+    pub async fn stage(
+        source: &NodeSnapshotSource,
+        base_path: &Path,
+        progress_tracker: Option<ChanneledStepUpdate>,
+    ) -> Result<PathBuf, anyhow::Error> {
+        source.validate()?;
+        let staging_dir = Self::staging_dir(base_path);
+        if staging_dir.exists() {
+            fs::remove_dir_all(&staging_dir).await?;
+        }
+        fs::create_dir_all(&staging_dir).await?;
+        Self::extract_snapshot(source, &staging_dir, progress_tracker).await
+    }
+
+    /// Swaps the staged database into place, the node must be stopped. An existing database is
+    /// moved aside first and only removed once the snapshot is in place.
+    pub async fn install(snapshot_root: &Path, network_dir: &Path) -> Result<(), anyhow::Error> {
+        fs::create_dir_all(network_dir).await?;
+        let data_dir = network_dir.join("data");
+        let previous_data_dir = network_dir.join("data_previous");
+        if previous_data_dir.exists() {
+            fs::remove_dir_all(&previous_data_dir).await?;
+        }
+        let has_previous = data_dir.exists();
+        if has_previous {
+            fs::rename(&data_dir, &previous_data_dir).await?;
+        }
+        if let Err(e) = fs::rename(snapshot_root.join("data"), &data_dir).await {
+            if has_previous {
+                fs::rename(&previous_data_dir, &data_dir).await?;
+            }
+            return Err(e.into());
+        }
+
+        // Carries the migration version and storage mode the database was created with,
+        // the previous database's record doesn't apply to the snapshot
+        let migration_file = snapshot_root.join("migrations.json");
+        if migration_file.exists() {
+            fs::rename(migration_file, network_dir.join("migrations.json")).await?;
+        } else if network_dir.join("migrations.json").exists() {
+            fs::remove_file(network_dir.join("migrations.json")).await?;
+        }
+        mark_database_migrated(network_dir)?;
+
+        if has_previous {
+            info!(target: LOG_TARGET, "Removing the database replaced by the snapshot");
+            if let Err(e) = fs::remove_dir_all(&previous_data_dir).await {
+                warn!(target: LOG_TARGET, "Failed to remove the replaced node database: {e}");
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn remove_staging(base_path: &Path) {
+        let staging_dir = Self::staging_dir(base_path);
+        if staging_dir.exists() {
+            if let Err(e) = fs::remove_dir_all(&staging_dir).await {
+                warn!(target: LOG_TARGET, "Failed to remove snapshot staging directory: {e}");
+            }
+        }
+    }
+
+    pub fn network_dir(base_path: &Path) -> PathBuf {
+        base_path
+            .join("node")
+            .join(Network::get_current().to_string().to_lowercase())
+    }
+
+    fn staging_dir(base_path: &Path) -> PathBuf {
+        base_path.join("node").join("snapshot_staging")
+    }
+
+    /// Returns the extracted directory that contains the `data` folder
+    async fn extract_snapshot(
+        source: &NodeSnapshotSource,
+        staging_dir: &Path,
+        progress_tracker: Option<ChanneledStepUpdate>,
+    ) -> Result<PathBuf, anyhow::Error> {
+        let archive_path = match source.local_path() {
+            Some(archive_path) => {
+                info!(target: LOG_TARGET, "Using local node snapshot at {}", archive_path.display());
+                archive_path
+            }
+            None => {
+                info!(target: LOG_TARGET, "Downloading node snapshot from {}", source.url);
+                let progress_sender = Self::forward_download_progress(progress_tracker).await;
+                HttpFileClient::builder()
+                    .with_download_resume()
+                    .with_progress_status_sender(progress_sender)
+                    .build(source.url.clone(), staging_dir.to_path_buf())?
+                    .execute()
+                    .await?
+            }
+        };
+
+        if !validate_checksum(archive_path.clone(), source.sha256.to_lowercase()).await? {
+            return Err(anyhow!("Node snapshot checksum mismatch"));
+        }
+        info!(target: LOG_TARGET, "Node snapshot checksum verified, extracting");
+
+        let extract_dir = staging_dir.join("extracted");
+        fs::create_dir_all(&extract_dir).await?;
+        extract(&archive_path, &extract_dir).await?;
+
+        // Archives may either contain the network folder itself or its contents
+        let network = Network::get_current().to_string().to_lowercase();
+        [extract_dir.clone(), extract_dir.join(network)]
+            .into_iter()
+            .find(|dir| dir.join("data").is_dir())
+            .ok_or_else(|| anyhow!("Node snapshot does not contain a data directory"))
+    }
+
+    async fn forward_download_progress(
+        progress_tracker: Option<ChanneledStepUpdate>,
+    ) -> Option<watch::Sender<f64>> {
+        let progress_tracker = progress_tracker?;
+        let (sender, mut receiver) = watch::channel::<f64>(0.0);
+        TasksTrackers::current()
+            .node_phase
+            .get_task_tracker()
+            .await
+            .spawn(async move {
+                while receiver.changed().await.is_ok() {
+                    let percentage = *receiver.borrow();
+                    let mut params = HashMap::new();
+                    params.insert("progress".to_string(), percentage.to_string());
+                    progress_tracker
+                        .send_update(params, percentage / 100.0)
+                        .await;
+                }
+            });
+        Some(sender)
+    }
+}
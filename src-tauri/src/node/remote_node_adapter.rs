@@ -67,39 +67,47 @@ impl RemoteNodeAdapter {
     }
 
     pub fn get_service(&self) -> Option<NodeAdapterService> {
-        if let Some(grpc_address) = self.get_grpc_address() {
-            let address = if grpc_address.0.starts_with("http") {
-                format!("{}:{}", grpc_address.0, grpc_address.1)
-            } else {
-                format!("http://{}:{}", grpc_address.0, grpc_address.1)
-            };
-            Some(NodeAdapterService::new(address, 1))
-        } else {
-            None
-        }
+        self.get_grpc_address()
+            .map(|grpc_address| NodeAdapterService::new(grpc_connection_url(&grpc_address), 1))
     }
 
     // Expected format currently: https://grpc.<network>.tari.com:443
     pub fn set_grpc_address(&mut self, grpc_address: String) -> Result<(), anyhow::Error> {
-        let has_scheme = grpc_address.starts_with("http");
-        let is_https = grpc_address.starts_with("https");
-        if !has_scheme {
-            let parts = grpc_address.split(':').collect::<Vec<&str>>();
-            let port = parts[1].parse::<u16>()?;
-            let scheme = if port == 443 { "https://" } else { "http://" };
-            self.grpc_address = Some((
-                format!("{}{}", scheme, parts[0]),
-                if is_https { 443 } else { 80 },
-            ));
-            return Ok(());
-        }
-
-        let parts = grpc_address.split(':').collect::<Vec<&str>>();
-        self.grpc_address = Some((format!("{}:{}", parts[0], parts[1]), parts[2].parse()?));
+        self.grpc_address = Some(parse_grpc_address(&grpc_address)?);
         Ok(())
     }
 }
 
+pub(crate) fn parse_grpc_address(grpc_address: &str) -> Result<(String, u16), anyhow::Error> {
+    let has_scheme = grpc_address.starts_with("http");
+    let is_https = grpc_address.starts_with("https");
+    let parts = grpc_address.split(':').collect::<Vec<&str>>();
+    if !has_scheme {
+        let port = parts
+            .get(1)
+            .ok_or_else(|| anyhow::anyhow!("Missing port in gRPC address: {grpc_address}"))?
+            .parse::<u16>()?;
+        let scheme = if port == 443 { "https://" } else { "http://" };
+        return Ok((
+            format!("{}{}", scheme, parts[0]),
+            if is_https { 443 } else { 80 },
+        ));
+    }
+
+    let port = parts
+        .get(2)
+        .ok_or_else(|| anyhow::anyhow!("Missing port in gRPC address: {grpc_address}"))?;
+    Ok((format!("{}:{}", parts[0], parts[1]), port.parse()?))
+}
+
+pub(crate) fn grpc_connection_url(grpc_address: &(String, u16)) -> String {
+    if grpc_address.0.starts_with("http") {
+        format!("{}:{}", grpc_address.0, grpc_address.1)
+    } else {
+        format!("http://{}:{}", grpc_address.0, grpc_address.1)
+    }
+}
+
 #[async_trait]
 impl NodeAdapter for RemoteNodeAdapter {
     fn get_grpc_address(&self) -> Option<(String, u16)> {
@@ -187,11 +195,7 @@ impl ProcessAdapter for RemoteNodeAdapter {
         let grpc_address = self
             .get_grpc_address()
             .ok_or_else(|| anyhow::anyhow!("GRPC address not set"))?;
-        let address = if grpc_address.0.starts_with("http") {
-            format!("{}:{}", grpc_address.0, grpc_address.1)
-        } else {
-            format!("http://{}:{}", grpc_address.0, grpc_address.1)
-        };
+        let address = grpc_connection_url(&grpc_address);
         Ok((
             NullProcessInstance {
                 shutdown: inner_shutdown,
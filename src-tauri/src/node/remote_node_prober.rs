@@ -0,0 +1,239 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::time::{Duration, Instant, SystemTime};
+
+use futures::future::join_all;
+use log::{info, warn};
+use serde::Serialize;
+use tokio::time::timeout;
+
+use crate::node::{
+    node_adapter::NodeAdapterService,
+    remote_node_adapter::{grpc_connection_url, parse_grpc_address},
+};
+
+const LOG_TARGET: &str = "tari::universe::remote_node_prober";
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+// Failed probes in a row before we move away from the active endpoint
+const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+// Healthy probes in a row before we move back to the primary endpoint
+const MIN_CONSECUTIVE_SUCCESSES_FOR_FAILBACK: u32 = 3;
+const MAX_BLOCKS_BEHIND: u64 = 5;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RemoteNodeEndpointStatus {
+    pub address: String,
+    pub is_active: bool,
+    pub is_reachable: bool,
+    pub is_synced: bool,
+    pub block_height: u64,
+    pub latency_ms: Option<u64>,
+    pub consecutive_failures: u32,
+    pub consecutive_successes: u32,
+    pub last_error: Option<String>,
+    pub last_checked_at: Option<SystemTime>,
+}
+
+impl RemoteNodeEndpointStatus {
+    pub fn new(address: String) -> Self {
+        Self {
+            address,
+            is_active: false,
+            is_reachable: false,
+            is_synced: false,
+            block_height: 0,
+            latency_ms: None,
+            consecutive_failures: 0,
+            consecutive_successes: 0,
+            last_error: None,
+            last_checked_at: None,
+        }
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.is_reachable && self.consecutive_failures == 0
+    }
+
+    async fn probe(&mut self) {
+        let started_at = Instant::now();
+        let result = match parse_grpc_address(&self.address) {
+            Ok(grpc_address) => {
+                let service = NodeAdapterService::new(grpc_connection_url(&grpc_address), 1);
+                match timeout(PROBE_TIMEOUT, service.get_network_state()).await {
+                    Ok(Ok(status)) => Ok(status),
+                    Ok(Err(e)) => Err(e.to_string()),
+                    Err(_) => Err("Timed out".to_string()),
+                }
+            }
+            Err(e) => Err(e.to_string()),
+        };
+
+        self.last_checked_at = Some(SystemTime::now());
+        match result {
+            Ok(status) => {
+                self.is_reachable = true;
+                self.is_synced = status.is_synced;
+                self.block_height = status.block_height;
+                self.latency_ms = u64::try_from(started_at.elapsed().as_millis()).ok();
+                self.last_error = None;
+            }
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Remote node {} probe failed: {e}", self.address);
+                self.is_reachable = false;
+                self.is_synced = false;
+                self.latency_ms = None;
+                self.last_error = Some(e);
+            }
+        }
+    }
+}
+
+/// Probes all endpoints concurrently. Endpoints that are unreachable, not synced or lagging
+/// behind the best known height count as a failed probe.
+pub async fn probe_endpoints(endpoints: &mut [RemoteNodeEndpointStatus]) {
+    join_all(endpoints.iter_mut().map(|endpoint| endpoint.probe())).await;
+
+    let best_height = endpoints
+        .iter()
+        .filter(|endpoint| endpoint.is_reachable)
+        .map(|endpoint| endpoint.block_height)
+        .max()
+        .unwrap_or(0);
+
+    for endpoint in endpoints.iter_mut() {
+        if endpoint.is_reachable && !endpoint.is_synced {
+            endpoint.last_error = Some("Node is not synced".to_string());
+        }
+        if endpoint.is_reachable && endpoint.block_height + MAX_BLOCKS_BEHIND < best_height {
+            endpoint.last_error = Some(format!(
+                "Behind best remote node by {} blocks",
+                best_height - endpoint.block_height
+            ));
+        }
+
+        if endpoint.is_reachable && endpoint.last_error.is_none() {
+            endpoint.consecutive_failures = 0;
+            endpoint.consecutive_successes += 1;
+        } else {
+            endpoint.consecutive_failures += 1;
+            endpoint.consecutive_successes = 0;
+        }
+    }
+}
+
+/// Healthy endpoint with the lowest latency
+pub fn find_healthiest(
+    endpoints: &[RemoteNodeEndpointStatus],
+) -> Option<&RemoteNodeEndpointStatus> {
+    endpoints
+        .iter()
+        .filter(|endpoint| endpoint.is_healthy())
+        .min_by_key(|endpoint| endpoint.latency_ms.unwrap_or(u64::MAX))
+}
+
+/// Returns the endpoint to switch to: the primary (first) endpoint once it has been healthy for
+/// a few probes in a row, or the healthiest one when the active endpoint keeps failing its probes
+pub fn find_failover_target(endpoints: &[RemoteNodeEndpointStatus]) -> Option<String> {
+    let active = endpoints.iter().find(|endpoint| endpoint.is_active)?;
+    let primary = endpoints.first()?;
+    if !primary.is_active
+        && primary.is_healthy()
+        && primary.consecutive_successes >= MIN_CONSECUTIVE_SUCCESSES_FOR_FAILBACK
+    {
+        info!(
+            target: LOG_TARGET,
+            "Primary remote node {} recovered, failing back from {}",
+            primary.address, active.address
+        );
+        return Some(primary.address.clone());
+    }
+    if active.consecutive_failures < MAX_CONSECUTIVE_FAILURES {
+        return None;
+    }
+
+    let target = find_healthiest(endpoints)?;
+    info!(
+        target: LOG_TARGET,
+        "Remote node {} failed {} probes in a row, failing over to {}",
+        active.address, active.consecutive_failures, target.address
+    );
+    Some(target.address.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn endpoint(
+        address: &str,
+        latency_ms: u64,
+        consecutive_failures: u32,
+    ) -> RemoteNodeEndpointStatus {
+        RemoteNodeEndpointStatus {
+            is_reachable: consecutive_failures == 0,
+            latency_ms: Some(latency_ms),
+            consecutive_failures,
+            consecutive_successes: u32::from(consecutive_failures == 0),
+            ..RemoteNodeEndpointStatus::new(address.to_string())
+        }
+    }
+
+    #[test]
+    fn test_failover_only_after_repeated_failures() {
+        let mut endpoints = vec![
+            endpoint("https://a:443", 10, 1),
+            endpoint("https://b:443", 80, 0),
+            endpoint("https://c:443", 40, 0),
+        ];
+        endpoints[0].is_active = true;
+        assert!(find_failover_target(&endpoints).is_none());
+
+        endpoints[0].consecutive_failures = MAX_CONSECUTIVE_FAILURES;
+        assert_eq!(
+            find_failover_target(&endpoints).unwrap(),
+            "https://c:443".to_string()
+        );
+    }
+
+    #[test]
+    fn test_failback_to_primary_once_it_is_healthy_again() {
+        let mut endpoints = vec![
+            endpoint("https://primary:443", 50, 0),
+            endpoint("https://fallback:443", 10, 0),
+        ];
+        endpoints[1].is_active = true;
+        assert!(find_failover_target(&endpoints).is_none());
+
+        endpoints[0].consecutive_successes = MIN_CONSECUTIVE_SUCCESSES_FOR_FAILBACK;
+        assert_eq!(
+            find_failover_target(&endpoints).unwrap(),
+            "https://primary:443".to_string()
+        );
+
+        endpoints[0].is_active = true;
+        endpoints[1].is_active = false;
+        assert!(find_failover_target(&endpoints).is_none());
+    }
+}
@@ -0,0 +1,277 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use tari_utilities::epoch_time::EpochTime;
+
+use crate::p2pool::models::{Connections, P2poolStats};
+
+// Share chain blocks we can be behind the median peer before calling it stalled
+const MAX_SHARE_CHAIN_LAG: u64 = 10;
+const MAX_SECONDS_SINCE_GOSSIP: u64 = 60 * 10;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PeerDiagnostics {
+    pub peer_id: String,
+    pub version: Option<u64>,
+    pub user_agent: Option<String>,
+    pub user_agent_version: Option<String>,
+    pub squad: Option<String>,
+    /// As reported by p2pool
+    pub last_ping: Option<u64>,
+    /// Positive when the peer is ahead of us
+    pub randomx_height_lag: Option<i64>,
+    pub sha3x_height_lag: Option<i64>,
+    pub last_grey_list_reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct GreyListReasonStats {
+    pub reason: String,
+    pub count: u64,
+    pub last_seen: EpochTime,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct P2poolHealthVerdict {
+    pub is_healthy: bool,
+    pub issues: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct P2poolDiagnostics {
+    pub peers: Vec<PeerDiagnostics>,
+    pub grey_list_reasons: Vec<GreyListReasonStats>,
+    pub verdict: P2poolHealthVerdict,
+}
+
+/// Counts each time a peer gets greylisted for a new reason, across connection polls
+#[derive(Default)]
+pub struct GreyListTracker {
+    /// Only peers that are still connected, a peer coming back counts as a new greylisting
+    last_reason_by_peer: HashMap<String, String>,
+    reasons: HashMap<String, GreyListReasonStats>,
+}
+
+impl GreyListTracker {
+    pub fn record(&mut self, connections: &Connections) {
+        self.last_reason_by_peer.retain(|peer_id, _| {
+            connections
+                .peers
+                .iter()
+                .any(|peer| &peer.peer_id == peer_id)
+        });
+        for peer in &connections.peers {
+            let Some(reason) = &peer.last_grey_list_reason else {
+                continue;
+            };
+            if self.last_reason_by_peer.get(&peer.peer_id) == Some(reason) {
+                continue;
+            }
+            self.last_reason_by_peer
+                .insert(peer.peer_id.clone(), reason.clone());
+            let stats = self
+                .reasons
+                .entry(reason.clone())
+                .or_insert_with(|| GreyListReasonStats {
+                    reason: reason.clone(),
+                    count: 0,
+                    last_seen: EpochTime::now(),
+                });
+            stats.count += 1;
+            stats.last_seen = EpochTime::now();
+        }
+    }
+
+    pub fn reasons(&self) -> Vec<GreyListReasonStats> {
+        let mut reasons: Vec<GreyListReasonStats> = self.reasons.values().cloned().collect();
+        reasons.sort_by(|a, b| b.count.cmp(&a.count));
+        reasons
+    }
+}
+
+pub fn diagnose(
+    stats: Option<&P2poolStats>,
+    connections: &Connections,
+    grey_list_reasons: Vec<GreyListReasonStats>,
+) -> P2poolDiagnostics {
+    let height_lag = |peer_height: u64, our_height: Option<u64>| {
+        our_height.map(|our_height| i128::from(peer_height) - i128::from(our_height))
+    };
+    let our_randomx_height = stats.map(|s| s.randomx_stats.height);
+    let our_sha3x_height = stats.map(|s| s.sha3x_stats.height);
+
+    let peers: Vec<PeerDiagnostics> = connections
+        .peers
+        .iter()
+        .map(|peer| {
+            let peer_info = peer.peer_info.as_ref();
+            PeerDiagnostics {
+                peer_id: peer.peer_id.clone(),
+                version: peer_info.map(|info| info.version),
+                user_agent: peer_info.and_then(|info| info.user_agent.clone()),
+                user_agent_version: peer_info.and_then(|info| info.user_agent_version.clone()),
+                squad: peer_info.map(|info| info.squad.clone()),
+                last_ping: peer.last_ping,
+                randomx_height_lag: peer_info
+                    .and_then(|info| height_lag(info.current_random_x_height, our_randomx_height))
+                    .and_then(|lag| i64::try_from(lag).ok()),
+                sha3x_height_lag: peer_info
+                    .and_then(|info| height_lag(info.current_sha3x_height, our_sha3x_height))
+                    .and_then(|lag| i64::try_from(lag).ok()),
+                last_grey_list_reason: peer.last_grey_list_reason.clone(),
+            }
+        })
+        .collect();
+
+    let verdict = health_verdict(stats, &peers);
+    P2poolDiagnostics {
+        peers,
+        grey_list_reasons,
+        verdict,
+    }
+}
+
+fn health_verdict(stats: Option<&P2poolStats>, peers: &[PeerDiagnostics]) -> P2poolHealthVerdict {
+    let mut issues = Vec::new();
+    let Some(stats) = stats else {
+        return P2poolHealthVerdict {
+            is_healthy: false,
+            issues: vec!["P2pool is not reporting stats, it may not be running".to_string()],
+        };
+    };
+
+    if peers.is_empty() {
+        issues.push("No connected peers, the share chain can't sync".to_string());
+    }
+
+    let seconds_since_gossip = EpochTime::now()
+        .as_u64()
+        .saturating_sub(stats.last_gossip_message.as_u64());
+    if seconds_since_gossip > MAX_SECONDS_SINCE_GOSSIP {
+        issues.push(format!(
+            "No gossip received for {} minutes",
+            seconds_since_gossip / 60
+        ));
+    }
+
+    let grey_listed = peers
+        .iter()
+        .filter(|peer| peer.last_grey_list_reason.is_some())
+        .count();
+    if !peers.is_empty() && grey_listed * 2 > peers.len() {
+        issues.push(format!(
+            "{grey_listed} of {} peers are greylisted",
+            peers.len()
+        ));
+    }
+
+    for (algorithm, lags) in [
+        (
+            "RandomX",
+            peers
+                .iter()
+                .filter_map(|p| p.randomx_height_lag)
+                .collect::<Vec<_>>(),
+        ),
+        (
+            "SHA3x",
+            peers
+                .iter()
+                .filter_map(|p| p.sha3x_height_lag)
+                .collect::<Vec<_>>(),
+        ),
+    ] {
+        if let Some(median_lag) = median(lags) {
+            if u64::try_from(median_lag).is_ok_and(|lag| lag > MAX_SHARE_CHAIN_LAG) {
+                issues.push(format!(
+                    "{algorithm} share chain is {median_lag} blocks behind the median peer"
+                ));
+            }
+        }
+    }
+
+    P2poolHealthVerdict {
+        is_healthy: issues.is_empty(),
+        issues,
+    }
+}
+
+fn median(mut values: Vec<i64>) -> Option<i64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    values.get(values.len() / 2).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::p2pool::models::ConnectedPeerInfo;
+
+    fn grey_listed_peer(peer_id: &str, reason: &str) -> ConnectedPeerInfo {
+        ConnectedPeerInfo {
+            peer_id: peer_id.to_string(),
+            last_grey_list_reason: Some(reason.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn grey_list_reasons_are_counted_once_per_change() {
+        let mut tracker = GreyListTracker::default();
+        let connections = Connections {
+            peers: vec![
+                grey_listed_peer("a", "timeout"),
+                grey_listed_peer("b", "timeout"),
+            ],
+        };
+        tracker.record(&connections);
+        tracker.record(&connections);
+        tracker.record(&Connections {
+            peers: vec![grey_listed_peer("a", "invalid block")],
+        });
+
+        let reasons = tracker.reasons();
+        assert_eq!(reasons.len(), 2);
+        assert_eq!(reasons[0].reason, "timeout");
+        assert_eq!(reasons[0].count, 2);
+        assert_eq!(reasons[1].count, 1);
+    }
+
+    #[test]
+    fn disconnected_peers_are_forgotten() {
+        let mut tracker = GreyListTracker::default();
+        let connections = Connections {
+            peers: vec![grey_listed_peer("a", "timeout")],
+        };
+        tracker.record(&connections);
+        tracker.record(&Connections { peers: Vec::new() });
+        assert!(tracker.last_reason_by_peer.is_empty());
+
+        tracker.record(&connections);
+        assert_eq!(tracker.reasons()[0].count, 2);
+    }
+}
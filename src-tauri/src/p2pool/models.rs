@@ -20,6 +20,8 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 use tari_core::transactions::tari_amount::MicroMinotari;
 use tari_utilities::epoch_time::EpochTime;
@@ -76,14 +78,62 @@ pub struct P2poolStats {
     pub squad: String,
 }
 
+// Everything but the height is only reported by p2pool builds with per-miner stats
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ChainStats {
-    // pub squad: SquadDetails,
-    // pub num_of_miners: usize,
+    #[serde(default)]
+    pub squad: Option<SquadDetails>,
+    #[serde(default)]
+    pub num_of_miners: Option<usize>,
     pub height: u64,
-    // pub share_chain_length: u64,
-    // pub miner_block_stats: BlockStats,
-    // pub p2pool_block_stats: BlockStats,
+    #[serde(default)]
+    pub share_chain_length: Option<u64>,
+    #[serde(default)]
+    pub miner_block_stats: Option<BlockStats>,
+    #[serde(default)]
+    pub p2pool_block_stats: Option<BlockStats>,
+    /// Keyed by the base58 wallet address the miner submits shares with
+    #[serde(default)]
+    pub estimated_earnings: HashMap<String, EstimatedEarnings>,
+}
+
+impl ChainStats {
+    pub fn miner_stats(&self, tari_address: &str) -> MinerChainStats {
+        MinerChainStats {
+            height: self.height,
+            squad_size: self.num_of_miners,
+            share_chain_length: self.share_chain_length,
+            shares: self.miner_block_stats.clone(),
+            estimated_earnings: self.estimated_earnings.get(tari_address).cloned(),
+        }
+    }
+}
+
+/// Our miner's view of a single share chain
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct MinerChainStats {
+    pub height: u64,
+    pub squad_size: Option<usize>,
+    pub share_chain_length: Option<u64>,
+    pub shares: Option<BlockStats>,
+    pub estimated_earnings: Option<EstimatedEarnings>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct P2poolStatsSnapshot {
+    pub timestamp: EpochTime,
+    pub randomx: MinerChainStats,
+    pub sha3x: MinerChainStats,
+}
+
+impl P2poolStatsSnapshot {
+    pub fn from_stats(stats: &P2poolStats, tari_address: &str) -> Self {
+        Self {
+            timestamp: EpochTime::now(),
+            randomx: stats.randomx_stats.miner_stats(tari_address),
+            sha3x: stats.sha3x_stats.miner_stats(tari_address),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
@@ -94,6 +144,7 @@ pub struct BlockStats {
 }
 
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(default)]
 pub struct EstimatedEarnings {
     #[serde(rename = "1min")]
     pub one_minute: MicroMinotari,
@@ -132,5 +183,54 @@ pub(crate) struct ConnectedPeerInfo {
 
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub(crate) struct Connections {
-    peers: Vec<ConnectedPeerInfo>,
+    pub peers: Vec<ConnectedPeerInfo>,
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn chain_stats_from_height_only_payload() {
+        let stats: ChainStats = serde_json::from_str(r#"{"height": 1234}"#).unwrap();
+        assert_eq!(stats.height, 1234);
+        assert!(stats.squad.is_none());
+        assert!(stats.num_of_miners.is_none());
+        assert!(stats.estimated_earnings.is_empty());
+
+        let miner_stats = stats.miner_stats("address");
+        assert_eq!(miner_stats.height, 1234);
+        assert!(miner_stats.shares.is_none());
+        assert!(miner_stats.estimated_earnings.is_none());
+    }
+
+    #[test]
+    fn chain_stats_from_full_payload() {
+        let stats: ChainStats = serde_json::from_str(
+            r#"{
+                "squad": {"id": "squad_3", "name": "Squad 3"},
+                "num_of_miners": 12,
+                "height": 1234,
+                "share_chain_length": 400,
+                "miner_block_stats": {"accepted": 5, "rejected": 1, "submitted": 6},
+                "p2pool_block_stats": {"accepted": 50, "rejected": 2, "submitted": 52},
+                "estimated_earnings": {
+                    "address": {"1min": 10, "1h": 600, "1d": 14400, "1w": 100800, "30d": 432000}
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(stats.squad.as_ref().unwrap().id, "squad_3");
+
+        let miner_stats = stats.miner_stats("address");
+        assert_eq!(miner_stats.squad_size, Some(12));
+        assert_eq!(miner_stats.share_chain_length, Some(400));
+        assert_eq!(miner_stats.shares.unwrap().accepted, 5);
+        assert_eq!(
+            miner_stats.estimated_earnings.unwrap().one_day,
+            MicroMinotari(14400)
+        );
+        assert!(stats.miner_stats("other").estimated_earnings.is_none());
+    }
 }
@@ -24,16 +24,19 @@ use anyhow::anyhow;
 use anyhow::Error;
 use async_trait::async_trait;
 use log::{info, warn};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 use tari_common::configuration::Network;
 use tari_shutdown::Shutdown;
 use tari_utilities::epoch_time::EpochTime;
-use tokio::sync::watch;
+use tokio::sync::{watch, RwLock};
 
+use crate::internal_wallet::InternalWallet;
 use crate::p2pool;
-use crate::p2pool::models::{Connections, P2poolStats};
+use crate::p2pool::diagnostics::GreyListTracker;
+use crate::p2pool::models::{Connections, P2poolStats, P2poolStatsSnapshot};
 use crate::p2pool_manager::P2poolConfig;
 use crate::process_adapter::HealthStatus;
 use crate::process_adapter::ProcessStartupSpec;
@@ -45,11 +48,16 @@ use crate::utils::file_utils::convert_to_string;
 use crate::utils::windows_setup_utils::add_firewall_rule;
 
 const LOG_TARGET: &str = "tari::universe::p2pool_adapter";
+// One hour at the p2pool health check interval
+const STATS_HISTORY_LENGTH: usize = 120;
 
 #[allow(dead_code)]
 pub struct P2poolAdapter {
     pub(crate) config: Option<P2poolConfig>,
     stats_broadcast: watch::Sender<Option<P2poolStats>>,
+    // Kept here so the trend survives p2pool restarts
+    pub(crate) stats_history: Arc<RwLock<VecDeque<P2poolStatsSnapshot>>>,
+    pub(crate) grey_list_tracker: Arc<RwLock<GreyListTracker>>,
 }
 #[allow(dead_code)]
 impl P2poolAdapter {
@@ -57,6 +65,8 @@ impl P2poolAdapter {
         Self {
             config: None,
             stats_broadcast,
+            stats_history: Arc::new(RwLock::new(VecDeque::with_capacity(STATS_HISTORY_LENGTH))),
+            grey_list_tracker: Arc::new(RwLock::new(GreyListTracker::default())),
         }
     }
     pub fn config(&self) -> Option<&P2poolConfig> {
@@ -170,6 +180,8 @@ impl ProcessAdapter for P2poolAdapter {
             P2poolStatusMonitor::new(
                 format!("http://127.0.0.1:{}", config.stats_server_port),
                 self.stats_broadcast.clone(),
+                self.stats_history.clone(),
+                self.grey_list_tracker.clone(),
             ),
         ))
     }
@@ -188,18 +200,45 @@ impl ProcessAdapter for P2poolAdapter {
 pub struct P2poolStatusMonitor {
     stats_client: p2pool::stats_client::Client,
     latest_status_broadcast: watch::Sender<Option<P2poolStats>>,
+    stats_history: Arc<RwLock<VecDeque<P2poolStatsSnapshot>>>,
+    grey_list_tracker: Arc<RwLock<GreyListTracker>>,
 }
 #[allow(dead_code)]
 impl P2poolStatusMonitor {
     pub fn new(
         stats_server_addr: String,
         stats_broadcast: watch::Sender<Option<P2poolStats>>,
+        stats_history: Arc<RwLock<VecDeque<P2poolStatsSnapshot>>>,
+        grey_list_tracker: Arc<RwLock<GreyListTracker>>,
     ) -> Self {
         Self {
             stats_client: p2pool::stats_client::Client::new(stats_server_addr),
             latest_status_broadcast: stats_broadcast,
+            stats_history,
+            grey_list_tracker,
         }
     }
+
+    /// Greylistings are only reported while they last, so they are sampled on every poll
+    async fn record_grey_list_reasons(&self) {
+        match self.stats_client.connections().await {
+            Ok(connections) => self.grey_list_tracker.write().await.record(&connections),
+            Err(e) => warn!(target: LOG_TARGET, "Failed to get p2pool connections: {e}"),
+        }
+    }
+
+    async fn record_stats_history(&self, stats: &P2poolStats) {
+        let mining_address = if InternalWallet::is_initialized() {
+            InternalWallet::mining_rewards_address().await.to_base58()
+        } else {
+            String::new()
+        };
+        let mut stats_history = self.stats_history.write().await;
+        if stats_history.len() >= STATS_HISTORY_LENGTH {
+            stats_history.pop_front();
+        }
+        stats_history.push_back(P2poolStatsSnapshot::from_stats(stats, &mining_address));
+    }
 }
 #[allow(dead_code)]
 #[async_trait]
@@ -218,6 +257,8 @@ impl StatusMonitor for P2poolStatusMonitor {
                         return HealthStatus::Warning;
                     }
 
+                    self.record_stats_history(&stats).await;
+                    self.record_grey_list_reasons().await;
                     let _unused = self.latest_status_broadcast.send(Some(stats));
                     HealthStatus::Healthy
                 }
@@ -24,12 +24,14 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
+use anyhow::anyhow;
 use futures_util::future::FusedFuture;
 use log::{info, warn};
 use tokio::sync::{watch, RwLock};
 use tokio::time::sleep;
 
-use crate::p2pool::models::{Connections, P2poolStats};
+use crate::p2pool::diagnostics::{diagnose, P2poolDiagnostics};
+use crate::p2pool::models::{Connections, P2poolStats, P2poolStatsSnapshot};
 use crate::p2pool_adapter::P2poolAdapter;
 use crate::port_allocator::PortAllocator;
 use crate::process_stats_collector::ProcessStatsCollectorBuilder;
@@ -147,7 +149,29 @@ impl P2poolManager {
             Ok(None)
         }
     }
-    #[allow(dead_code)]
+
+    /// Fails when p2pool is not running, an empty peer list would read as a p2pool without peers
+    pub async fn get_diagnostics(
+        &self,
+        stats: Option<P2poolStats>,
+    ) -> Result<P2poolDiagnostics, anyhow::Error> {
+        if !self.is_running().await {
+            return Err(anyhow!("p2pool is not running"));
+        }
+        let connections = self
+            .get_connections()
+            .await?
+            .ok_or_else(|| anyhow!("p2pool is not running"))?;
+        let grey_list_tracker = self.watcher.read().await.adapter.grey_list_tracker.clone();
+        let grey_list_reasons = grey_list_tracker.read().await.reasons();
+        Ok(diagnose(stats.as_ref(), &connections, grey_list_reasons))
+    }
+    pub async fn get_stats_history(&self) -> Vec<P2poolStatsSnapshot> {
+        let stats_history = self.watcher.read().await.adapter.stats_history.clone();
+        let stats_history = stats_history.read().await;
+        stats_history.iter().cloned().collect()
+    }
+
     pub async fn is_running(&self) -> bool {
         let process_watcher = self.watcher.read().await;
         process_watcher.is_running()
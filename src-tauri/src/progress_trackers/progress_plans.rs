@@ -100,6 +100,7 @@ pub enum ProgressSetupNodePlan {
     BinariesWallet,
     BinariesMergeMiningProxy,
     StartTor,
+    BootstrapSnapshot,
     MigratingDatabase,
     StartingNode,
     WaitingForInitialSync,
@@ -122,6 +123,7 @@ impl ProgressStep for ProgressSetupNodePlan {
             ProgressSetupNodePlan::BinariesWallet => 1,
             ProgressSetupNodePlan::BinariesMergeMiningProxy => 1,
             ProgressSetupNodePlan::StartTor => 1,
+            ProgressSetupNodePlan::BootstrapSnapshot => 1,
             ProgressSetupNodePlan::MigratingDatabase => 1,
             ProgressSetupNodePlan::StartingNode => 1,
             ProgressSetupNodePlan::WaitingForInitialSync => 2,
@@ -140,6 +142,7 @@ impl ProgressStep for ProgressSetupNodePlan {
                 "binaries-merge-mining-proxy".to_string()
             }
             ProgressSetupNodePlan::StartTor => "start-tor".to_string(),
+            ProgressSetupNodePlan::BootstrapSnapshot => "bootstrap-snapshot".to_string(),
             ProgressSetupNodePlan::MigratingDatabase => "migrating-database".to_string(),
             ProgressSetupNodePlan::StartingNode => "starting-node".to_string(),
             ProgressSetupNodePlan::WaitingForInitialSync => "waiting-for-initial-sync".to_string(),
@@ -272,6 +275,50 @@ impl ProgressStep for ProgressSetupMiningPlan {
     }
 }
 
+#[derive(Clone, PartialEq, Debug)]
+pub enum ProgressNodeMaintenancePlan {
+    Verify,
+    Backup,
+    Trim,
+    Repair,
+    Done,
+}
+
+impl ProgressStep for ProgressNodeMaintenancePlan {
+    type ChannelEvent = ProgressPlanEventPayload;
+
+    fn get_event_type(&self) -> ProgressEvents {
+        ProgressEvents::NodeMaintenance
+    }
+
+    fn get_progress_weight(&self) -> u8 {
+        match self {
+            ProgressNodeMaintenancePlan::Verify => 1,
+            ProgressNodeMaintenancePlan::Backup => 4,
+            ProgressNodeMaintenancePlan::Trim => 1,
+            ProgressNodeMaintenancePlan::Repair => 4,
+            ProgressNodeMaintenancePlan::Done => 1,
+        }
+    }
+
+    fn get_title(&self) -> String {
+        match self {
+            ProgressNodeMaintenancePlan::Verify => "verify".to_string(),
+            ProgressNodeMaintenancePlan::Backup => "backup".to_string(),
+            ProgressNodeMaintenancePlan::Trim => "trim".to_string(),
+            ProgressNodeMaintenancePlan::Repair => "repair".to_string(),
+            ProgressNodeMaintenancePlan::Done => "done".to_string(),
+        }
+    }
+
+    fn resolve_to_event(&self) -> Self::ChannelEvent {
+        ProgressPlanEventPayload {
+            event_type: self.get_event_type(),
+            title: self.get_title(),
+        }
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Clone, PartialEq, Debug)]
 pub enum ProgressPlans {
@@ -280,6 +327,7 @@ pub enum ProgressPlans {
     Hardware(ProgressSetupHardwarePlan),
     Wallet(ProgressSetupWalletPlan),
     Mining(ProgressSetupMiningPlan),
+    NodeMaintenance(ProgressNodeMaintenancePlan),
 }
 #[allow(dead_code)]
 impl ProgressPlans {
@@ -290,6 +338,7 @@ impl ProgressPlans {
             ProgressPlans::Hardware(plan) => plan.get_event_type(),
             ProgressPlans::Wallet(plan) => plan.get_event_type(),
             ProgressPlans::Mining(plan) => plan.get_event_type(),
+            ProgressPlans::NodeMaintenance(plan) => plan.get_event_type(),
         }
     }
 }
@@ -304,6 +353,7 @@ impl ProgressStep for ProgressPlans {
             ProgressPlans::Hardware(plan) => plan.get_event_type(),
             ProgressPlans::Wallet(plan) => plan.get_event_type(),
             ProgressPlans::Mining(plan) => plan.get_event_type(),
+            ProgressPlans::NodeMaintenance(plan) => plan.get_event_type(),
         }
     }
 
@@ -314,6 +364,7 @@ impl ProgressStep for ProgressPlans {
             ProgressPlans::Hardware(plan) => plan.get_title(),
             ProgressPlans::Wallet(plan) => plan.get_title(),
             ProgressPlans::Mining(plan) => plan.get_title(),
+            ProgressPlans::NodeMaintenance(plan) => plan.get_title(),
         }
     }
 
@@ -324,6 +375,7 @@ impl ProgressStep for ProgressPlans {
             ProgressPlans::Hardware(plan) => plan.resolve_to_event(),
             ProgressPlans::Wallet(plan) => plan.resolve_to_event(),
             ProgressPlans::Mining(plan) => plan.resolve_to_event(),
+            ProgressPlans::NodeMaintenance(plan) => plan.resolve_to_event(),
         }
     }
 
@@ -334,6 +386,7 @@ impl ProgressStep for ProgressPlans {
             ProgressPlans::Hardware(plan) => plan.get_progress_weight(),
             ProgressPlans::Wallet(plan) => plan.get_progress_weight(),
             ProgressPlans::Mining(plan) => plan.get_progress_weight(),
+            ProgressPlans::NodeMaintenance(plan) => plan.get_progress_weight(),
         }
     }
 }
@@ -346,6 +399,7 @@ impl ProgressPlans {
             ProgressPlans::Hardware(_) => "setup-hardware".to_string(),
             ProgressPlans::Wallet(_) => "setup-wallet".to_string(),
             ProgressPlans::Mining(_) => "setup-mining".to_string(),
+            ProgressPlans::NodeMaintenance(_) => "node-maintenance".to_string(),
         }
     }
 
@@ -356,6 +410,7 @@ impl ProgressPlans {
             ProgressPlans::Hardware(_) => 0.1,
             ProgressPlans::Wallet(_) => 0.1,
             ProgressPlans::Mining(_) => 0.1,
+            ProgressPlans::NodeMaintenance(_) => 1.0,
         }
     }
 
@@ -366,6 +421,7 @@ impl ProgressPlans {
             ProgressPlans::Hardware(_) => 60.0,
             ProgressPlans::Wallet(_) => 80.0,
             ProgressPlans::Mining(_) => 90.0,
+            ProgressPlans::NodeMaintenance(_) => 0.0,
         }
     }
 }
@@ -20,7 +20,7 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use log::error;
+use log::{error, info};
 use tari_shutdown::ShutdownSignal;
 use tauri::{AppHandle, Manager};
 use tokio::sync::{
@@ -192,7 +192,11 @@ impl SetupPhaseImpl for CoreSetupPhase {
             .resolve_step(ProgressPlans::Core(ProgressSetupCorePlan::NetworkSpeedTest))
             .await;
 
-        NetworkStatus::current().run_speed_test_with_timeout().await;
+        if *ConfigCore::content().await.metered_connection() {
+            info!(target: LOG_TARGET, "Skipping network speed test on a metered connection");
+        } else {
+            NetworkStatus::current().run_speed_test_with_timeout().await;
+        }
 
         Ok(())
     }
@@ -23,6 +23,7 @@
 use crate::{
     binaries::{Binaries, BinaryResolver},
     configs::{config_mining::ConfigMining, trait_config::ConfigImpl},
+    cpu_miner_backend::CpuMinerBackend,
     events_emitter::EventsEmitter,
     gpu_devices::GpuDevices,
     gpu_miner::EngineType,
@@ -67,6 +68,7 @@ pub struct HardwareSetupPhaseOutput {}
 #[derive(Clone, Default)]
 pub struct HardwareSetupPhaseAppConfiguration {
     gpu_engine: EngineType,
+    cpu_miner_backend: CpuMinerBackend,
 }
 
 pub struct HardwareSetupPhase {
@@ -147,9 +149,14 @@ impl SetupPhaseImpl for HardwareSetupPhase {
     }
 
     async fn load_app_configuration() -> Result<Self::AppConfiguration, Error> {
-        let gpu_engine = ConfigMining::content().await.gpu_engine().clone();
+        let mining_config = ConfigMining::content().await;
+        let gpu_engine = mining_config.gpu_engine().clone();
+        let cpu_miner_backend = *mining_config.cpu_miner_backend();
 
-        Ok(HardwareSetupPhaseAppConfiguration { gpu_engine })
+        Ok(HardwareSetupPhaseAppConfiguration {
+            gpu_engine,
+            cpu_miner_backend,
+        })
     }
 
     async fn setup(self) {
@@ -187,7 +194,10 @@ impl SetupPhaseImpl for HardwareSetupPhase {
         );
 
         binary_resolver
-            .initialize_binary(Binaries::Xmrig, cpu_miner_binary_progress_tracker)
+            .initialize_binary(
+                self.app_configuration.cpu_miner_backend.binary(),
+                cpu_miner_binary_progress_tracker,
+            )
             .await?;
 
         progress_stepper
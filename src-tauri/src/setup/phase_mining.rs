@@ -27,20 +27,25 @@ use super::{
     utils::{setup_default_adapter::SetupDefaultAdapter, timeout_watcher::TimeoutWatcher},
 };
 use crate::{
-    configs::{config_core::ConfigCore, trait_config::ConfigImpl},
+    configs::{
+        config_core::ConfigCore, config_mining::ConfigMining, config_wallet::ConfigWallet,
+        trait_config::ConfigImpl,
+    },
     events_emitter::EventsEmitter,
     internal_wallet::InternalWallet,
+    mining_mode_advisor::{auto_switch_if_needed, AUTO_SWITCH_CHECK_INTERVAL},
     progress_trackers::{
         progress_plans::{ProgressPlans, ProgressSetupMiningPlan},
         progress_stepper::ProgressStepperBuilder,
         ProgressStepper,
     },
     setup::{listeners::SetupFeature, setup_manager::SetupPhase},
+    stratum_hub::stratum_hub_manager::StratumHubStartConfig,
     tasks_tracker::TasksTrackers,
     StartConfig, UniverseAppState,
 };
 use anyhow::Error;
-use log::info;
+use log::{info, warn};
 use tari_shutdown::ShutdownSignal;
 use tauri::{AppHandle, Manager};
 use tokio::sync::{
@@ -60,6 +65,9 @@ pub struct MiningSetupPhaseSessionConfiguration {}
 pub struct MiningSetupPhaseAppConfiguration {
     mmproxy_monero_nodes: Vec<String>,
     mmproxy_use_monero_fail: bool,
+    stratum_hub_enabled: bool,
+    stratum_hub_port: u16,
+    stratum_hub_sha3x_port: u16,
 }
 
 pub struct MiningSetupPhase {
@@ -134,10 +142,14 @@ impl SetupPhaseImpl for MiningSetupPhase {
     async fn load_app_configuration() -> Result<Self::AppConfiguration, Error> {
         let mmproxy_monero_nodes = ConfigCore::content().await.mmproxy_monero_nodes().clone();
         let mmproxy_use_monero_fail = *ConfigCore::content().await.mmproxy_use_monero_failover();
+        let mining_config = ConfigMining::content().await;
 
         Ok(MiningSetupPhaseAppConfiguration {
             mmproxy_use_monero_fail,
             mmproxy_monero_nodes,
+            stratum_hub_enabled: *mining_config.stratum_hub_enabled(),
+            stratum_hub_port: *mining_config.stratum_hub_port(),
+            stratum_hub_sha3x_port: *mining_config.stratum_hub_sha3x_port(),
         })
     }
 
@@ -157,7 +169,7 @@ impl SetupPhaseImpl for MiningSetupPhase {
                 .resolve_step(ProgressPlans::Mining(ProgressSetupMiningPlan::MMProxy))
                 .await;
             let (data_dir, config_dir, log_dir) = self.get_app_dirs()?;
-            let tari_address = InternalWallet::tari_address().await;
+            let tari_address = InternalWallet::mining_rewards_address().await;
             let state = self.app_handle.state::<UniverseAppState>();
             let telemetry_id = state
                 .telemetry_manager
@@ -175,6 +187,7 @@ impl SetupPhaseImpl for MiningSetupPhase {
                     config_path: config_dir.clone(),
                     log_path: log_dir.clone(),
                     tari_address: tari_address.clone(),
+                    monero_address: ConfigWallet::content().await.monero_address().clone(),
                     coinbase_extra: telemetry_id,
                     monero_nodes: self.app_configuration.mmproxy_monero_nodes.clone(),
                     use_monero_fail: self.app_configuration.mmproxy_use_monero_fail,
@@ -182,6 +195,13 @@ impl SetupPhaseImpl for MiningSetupPhase {
                 .await?;
 
             state.mm_proxy_manager.wait_ready().await?;
+
+            if self.app_configuration.stratum_hub_enabled {
+                // The hub is optional, a busy port should not stop local mining.
+                if let Err(e) = self.start_stratum_hub().await {
+                    warn!(target: LOG_TARGET, "Failed to start stratum hub: {e}");
+                }
+            }
         } else {
             progress_stepper.skip_step(ProgressPlans::Mining(ProgressSetupMiningPlan::MMProxy));
         }
@@ -199,6 +219,56 @@ impl SetupPhaseImpl for MiningSetupPhase {
 
         EventsEmitter::emit_mining_phase_finished(true).await;
 
+        let app_handle = self.app_handle.clone();
+        let mut shutdown_signal = TasksTrackers::current().mining_phase.get_signal().await;
+        TasksTrackers::current()
+            .mining_phase
+            .get_task_tracker()
+            .await
+            .spawn(async move {
+                let mut interval = tokio::time::interval(AUTO_SWITCH_CHECK_INTERVAL);
+                interval.tick().await;
+                loop {
+                    tokio::select! {
+                        _ = interval.tick() => {
+                            if auto_switch_if_needed(&app_handle).await {
+                                break;
+                            }
+                        },
+                        _ = shutdown_signal.wait() => break,
+                    }
+                }
+            });
+
         Ok(())
     }
 }
+
+impl MiningSetupPhase {
+    async fn start_stratum_hub(&self) -> Result<(), Error> {
+        let state = self.app_handle.state::<UniverseAppState>();
+        // Remote nodes are not ours to share, SHA3x miners only get forwarded to a local node.
+        let node_grpc_port = if state.node_manager.is_local_current().await {
+            state
+                .node_manager
+                .get_grpc_address()
+                .await?
+                .rsplit(':')
+                .next()
+                .and_then(|port| port.parse::<u16>().ok())
+        } else {
+            None
+        };
+
+        state
+            .stratum_hub_manager
+            .start(StratumHubStartConfig {
+                stratum_port: self.app_configuration.stratum_hub_port,
+                mm_proxy_port: state.mm_proxy_manager.get_port().await,
+                monero_address: ConfigWallet::content().await.monero_address().clone(),
+                sha3x_port: self.app_configuration.stratum_hub_sha3x_port,
+                node_grpc_port,
+            })
+            .await
+    }
+}
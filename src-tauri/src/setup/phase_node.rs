@@ -27,13 +27,16 @@ use crate::{
     configs::{config_core::ConfigCore, trait_config::ConfigImpl},
     events_emitter::EventsEmitter,
     events_manager::EventsManager,
-    node::node_manager::{NodeManagerError, STOP_ON_ERROR_CODES},
+    node::{
+        node_manager::{NodeManagerError, NodeType, STOP_ON_ERROR_CODES},
+        node_snapshot::{NodeSnapshot, NodeSnapshotSource},
+    },
     progress_trackers::{
         progress_plans::{ProgressPlans, ProgressSetupNodePlan},
         progress_stepper::ProgressStepperBuilder,
         ProgressStepper,
     },
-    setup::setup_manager::SetupPhase,
+    setup::setup_manager::{SetupManager, SetupPhase},
     tasks_tracker::TasksTrackers,
     UniverseAppState,
 };
@@ -59,13 +62,17 @@ use super::{
 
 static LOG_TARGET: &str = "tari::universe::phase_hardware";
 
+const ORPHAN_CHAIN_DETECTIONS_BEFORE_RECOVERY: u32 = 3;
+
 #[derive(Clone, Default)]
 pub struct NodeSetupPhaseOutput {}
 
 #[derive(Clone, Default)]
 pub struct NodeSetupPhaseAppConfiguration {
     use_tor: bool,
-    base_node_grpc_address: String,
+    base_node_grpc_addresses: Vec<String>,
+    node_snapshot_source: Option<NodeSnapshotSource>,
+    metered_connection: bool,
 }
 
 pub struct NodeSetupPhase {
@@ -137,6 +144,9 @@ impl SetupPhaseImpl for NodeSetupPhase {
                 ProgressSetupNodePlan::BinariesMergeMiningProxy,
             ))
             .add_step(ProgressPlans::Node(ProgressSetupNodePlan::StartTor))
+            .add_step(ProgressPlans::Node(
+                ProgressSetupNodePlan::BootstrapSnapshot,
+            ))
             .add_step(ProgressPlans::Node(
                 ProgressSetupNodePlan::MigratingDatabase,
             ))
@@ -157,11 +167,15 @@ impl SetupPhaseImpl for NodeSetupPhase {
     async fn load_app_configuration() -> Result<Self::AppConfiguration, Error> {
         let config_core = ConfigCore::content().await;
         let use_tor = *config_core.use_tor();
-        let base_node_grpc_address = config_core.remote_base_node_address().clone();
+        let base_node_grpc_addresses = config_core.remote_base_node_addresses();
+        let node_snapshot_source = config_core.node_snapshot_source().clone();
+        let metered_connection = *config_core.metered_connection();
 
         Ok(NodeSetupPhaseAppConfiguration {
             use_tor,
-            base_node_grpc_address,
+            base_node_grpc_addresses,
+            node_snapshot_source,
+            metered_connection,
         })
     }
 
@@ -173,7 +187,16 @@ impl SetupPhaseImpl for NodeSetupPhase {
     async fn setup_inner(&self) -> Result<(), Error> {
         let (data_dir, config_dir, log_dir) = self.get_app_dirs()?;
         let state = self.app_handle.state::<UniverseAppState>();
-        let node_type = state.node_manager.get_node_type().await;
+        let mut node_type = state.node_manager.get_node_type().await;
+        if node_type.is_local()
+            && !state
+                .node_manager
+                .is_local_sync_allowed(self.app_configuration.metered_connection)
+        {
+            info!(target: LOG_TARGET, "Metered connection, using the remote node until a local sync is triggered");
+            node_type = NodeType::Remote;
+            state.node_manager.set_node_type(node_type.clone()).await;
+        }
         log::info!(target: LOG_TARGET, "Phase Node Setup for {node_type:?}");
 
         let binary_resolver = BinaryResolver::current();
@@ -234,6 +257,27 @@ impl SetupPhaseImpl for NodeSetupPhase {
                 .await?;
         }
 
+        match &self.app_configuration.node_snapshot_source {
+            Some(snapshot_source) if node_type.is_local() => {
+                let snapshot_tracker = progress_stepper.channel_step_range_updates(
+                    ProgressPlans::Node(ProgressSetupNodePlan::BootstrapSnapshot),
+                    Some(ProgressPlans::Node(
+                        ProgressSetupNodePlan::MigratingDatabase,
+                    )),
+                );
+                if let Err(e) =
+                    NodeSnapshot::bootstrap(snapshot_source, &data_dir, snapshot_tracker).await
+                {
+                    warn!(target: LOG_TARGET, "Failed to bootstrap node from snapshot, syncing from peers instead: {e}");
+                }
+            }
+            _ => {
+                progress_stepper.skip_step(ProgressPlans::Node(
+                    ProgressSetupNodePlan::BootstrapSnapshot,
+                ));
+            }
+        }
+
         // Set up migration progress tracking
         let migration_tracker = progress_stepper.channel_step_range_updates(
             ProgressPlans::Node(ProgressSetupNodePlan::MigratingDatabase),
@@ -246,9 +290,18 @@ impl SetupPhaseImpl for NodeSetupPhase {
             ))
             .await;
 
+        let remote_grpc_address = if node_type.is_remote() {
+            state
+                .node_manager
+                .select_remote_grpc_address(self.app_configuration.base_node_grpc_addresses.clone())
+                .await
+        } else {
+            None
+        };
+
         for _i in 0..2 {
             let tor_control_port = state.tor_manager.get_control_port().await?;
-            info!(target: LOG_TARGET, "Starting node manager, grpc address: {}", self.app_configuration.base_node_grpc_address);
+            info!(target: LOG_TARGET, "Starting node manager, remote grpc address: {remote_grpc_address:?}");
             match state
                 .node_manager
                 .ensure_started(
@@ -257,7 +310,7 @@ impl SetupPhaseImpl for NodeSetupPhase {
                     log_dir.clone(),
                     self.app_configuration.use_tor,
                     tor_control_port,
-                    Some(self.app_configuration.base_node_grpc_address.clone()),
+                    remote_grpc_address.clone(),
                     migration_tracker.clone(),
                 )
                 .await
@@ -364,18 +417,36 @@ impl SetupPhaseImpl for NodeSetupPhase {
             .await
             .spawn(async move {
                 let mut interval: Interval = interval(Duration::from_secs(30));
+                let mut orphan_chain_detections = 0;
 
                 loop {
                     tokio::select! {
                         _ = interval.tick() => {
                             let state = app_handle_clone.state::<UniverseAppState>().inner();
-                            let check_if_orphan = state
+                            let chain_consensus = state
                                 .node_manager
-                                .check_if_is_orphan_chain()
+                                .check_chain_consensus()
                                 .await;
-                            match check_if_orphan {
-                                Ok(is_stuck) => {
+                            match chain_consensus {
+                                Ok(consensus) => {
+                                    let is_stuck = consensus.is_orphan;
                                     EventsEmitter::emit_stuck_on_orphan_chain(is_stuck).await;
+                                    orphan_chain_detections = if is_stuck { orphan_chain_detections + 1 } else { 0 };
+                                    // Give short lived forks a chance to reorg before recovering
+                                    if orphan_chain_detections >= ORPHAN_CHAIN_DETECTIONS_BEFORE_RECOVERY
+                                        && state.node_manager.is_local_current().await
+                                        && state.node_manager.can_recover_from_orphan_chain()
+                                    {
+                                        warn!(target: LOG_TARGET, "Node is stuck on orphan chain, starting recovery");
+                                        let app_handle = app_handle_clone.clone();
+                                        TasksTrackers::current().common.get_task_tracker().await.spawn(async move {
+                                            let node_manager = app_handle.state::<UniverseAppState>().node_manager.clone();
+                                            if let Err(e) = node_manager.recover_from_orphan_chain(app_handle.clone(), consensus).await {
+                                                error!(target: LOG_TARGET, "Orphan chain recovery failed: {e}");
+                                            }
+                                        });
+                                        break;
+                                    }
                                 }
                                 Err(ref e) => {
                                     error!(target: LOG_TARGET, "{e}");
@@ -390,6 +461,48 @@ impl SetupPhaseImpl for NodeSetupPhase {
                 }
             });
 
+        if self.app_configuration.base_node_grpc_addresses.len() > 1 {
+            let app_handle_clone: tauri::AppHandle = self.app_handle.clone();
+            let mut shutdown_signal = TasksTrackers::current().node_phase.get_signal().await;
+            TasksTrackers::current()
+                .node_phase
+                .get_task_tracker()
+                .await
+                .spawn(async move {
+                    let state = app_handle_clone.state::<UniverseAppState>().inner();
+                    let mut interval: Interval = interval(Duration::from_secs(60));
+                    interval.tick().await;
+
+                    loop {
+                        tokio::select! {
+                            _ = interval.tick() => {
+                                if !state.node_manager.is_remote().await {
+                                    break;
+                                }
+                                if let Some(address) = state.node_manager.check_remote_endpoints().await {
+                                    warn!(target: LOG_TARGET, "Switching remote node, restarting node phase with {address}");
+                                    // Restart from the common tracker, this task is stopped by the node phase shutdown
+                                    let app_handle = app_handle_clone.clone();
+                                    TasksTrackers::current().common.get_task_tracker().await.spawn(async move {
+                                        SetupManager::get_instance()
+                                            .restart_phases(
+                                                app_handle,
+                                                vec![SetupPhase::Node, SetupPhase::Wallet, SetupPhase::Mining],
+                                            )
+                                            .await;
+                                    });
+                                    break;
+                                }
+                            },
+                            _ = shutdown_signal.wait() => {
+                                info!(target: LOG_TARGET, "Stopping remote node health probes");
+                                break;
+                            }
+                        }
+                    }
+                });
+        }
+
         let app_handle_clone: tauri::AppHandle = self.app_handle.clone();
         TasksTrackers::current().node_phase.get_task_tracker().await.spawn(async move {
         let app_state = app_handle_clone.state::<UniverseAppState>().clone();
@@ -36,7 +36,7 @@ use crate::{
     },
     setup::setup_manager::SetupPhase,
     tasks_tracker::TasksTrackers,
-    wallet::wallet_manager::WalletStartupConfig,
+    wallet::{payments_scheduler::PaymentsScheduler, wallet_manager::WalletStartupConfig},
     UniverseAppState,
 };
 use anyhow::Error;
@@ -209,7 +209,7 @@ impl SetupPhaseImpl for WalletSetupPhase {
     async fn finalize_setup(&self) -> Result<(), Error> {
         let app_state = self.get_app_handle().state::<UniverseAppState>().clone();
         let node_status_watch_rx = (*app_state.node_status_watch_rx).clone();
-        if InternalWallet::is_internal().await {
+        if InternalWallet::is_internal().await || InternalWallet::is_watch_only().await {
             app_state
                 .wallet_manager
                 .wait_for_initial_wallet_scan(node_status_watch_rx)
@@ -223,6 +223,10 @@ impl SetupPhaseImpl for WalletSetupPhase {
             .resolve_step(ProgressPlans::Wallet(ProgressSetupWalletPlan::Done))
             .await;
 
+        if InternalWallet::is_internal().await {
+            PaymentsScheduler::spawn(self.get_app_handle().clone()).await;
+        }
+
         if InternalWallet::is_internal().await {
             let app_handle = self.get_app_handle().clone();
             let pin_locked = PinManager::pin_locked().await;
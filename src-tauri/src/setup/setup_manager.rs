@@ -335,6 +335,22 @@ impl SetupManager {
                     })
                     .await;
                 }
+            } else if ConfigWallet::content()
+                .await
+                .watch_only_wallet_details()
+                .is_some()
+            {
+                let _unused = ConfigUI::set_wallet_ui_mode(WalletUIMode::WatchOnly).await;
+                if let Err(e) = InternalWallet::initialize_watch_only(&app_handle, None).await {
+                    EventsEmitter::emit_critical_problem(CriticalProblemPayload {
+                        title: Some("Wallet(Watch-only) not initialized!".to_string()),
+                        description: Some(
+                            "Encountered an error while initializing the wallet.".to_string(),
+                        ),
+                        error_message: Some(e.to_string()),
+                    })
+                    .await;
+                }
             } else {
                 let _unused = ConfigUI::set_wallet_ui_mode(WalletUIMode::Standard).await;
                 match InternalWallet::initialize_with_seed(&app_handle).await {
@@ -749,6 +765,29 @@ impl SetupManager {
         }
     }
 
+    pub async fn handle_fallback_to_remote_node(&self) {
+        if let Some(app_handle) = self.app_handle.lock().await.clone() {
+            info!(target: LOG_TARGET, "Handle Falling back to Remote Node in Setup Manager");
+            EventsManager::handle_node_type_update(&app_handle).await;
+
+            // Called from a node phase task, restart from the common tracker so we don't wait on ourselves
+            TasksTrackers::current()
+                .common
+                .get_task_tracker()
+                .await
+                .spawn(async move {
+                    SetupManager::get_instance()
+                        .restart_phases(
+                            app_handle,
+                            vec![SetupPhase::Node, SetupPhase::Wallet, SetupPhase::Mining],
+                        )
+                        .await;
+                });
+        } else {
+            error!(target: LOG_TARGET, "Failed to reset phases after falling back to Remote Node: app_handle not defined");
+        }
+    }
+
     pub async fn spawn_sleep_mode_handler(app_handle: AppHandle) {
         info!(target: LOG_TARGET, "Spawning Sleep Mode Handler");
         let mut shutdown_signal = TasksTrackers::current().common.get_signal().await;
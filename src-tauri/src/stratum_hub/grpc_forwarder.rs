@@ -0,0 +1,168 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+use log::{info, warn};
+use tari_shutdown::ShutdownSignal;
+use tari_utilities::epoch_time::EpochTime;
+use tokio::{
+    io::copy_bidirectional,
+    net::{TcpListener, TcpStream},
+    sync::RwLock,
+};
+use tokio_util::task::TaskTracker;
+
+use super::worker_stats::Sha3xClientStats;
+
+const LOG_TARGET: &str = "tari::universe::stratum_hub";
+
+/// Exposes the local node's gRPC to the LAN on a stable port, so GPU (SHA3x) miners on other
+/// rigs can fetch templates and submit blocks through this instance. The traffic is forwarded
+/// as-is, so only connection level accounting is available per client. Since every gRPC method
+/// the node allows is reachable this way, only clients on the local network are accepted.
+pub struct GrpcForwarder {
+    listener: TcpListener,
+    upstream_address: SocketAddr,
+    clients: Arc<RwLock<HashMap<String, Sha3xClientStats>>>,
+}
+
+impl GrpcForwarder {
+    pub fn new(
+        listener: TcpListener,
+        upstream_address: SocketAddr,
+        clients: Arc<RwLock<HashMap<String, Sha3xClientStats>>>,
+    ) -> Self {
+        Self {
+            listener,
+            upstream_address,
+            clients,
+        }
+    }
+
+    pub async fn run(self, task_tracker: TaskTracker, mut shutdown_signal: ShutdownSignal) {
+        loop {
+            tokio::select! {
+                accepted = self.listener.accept() => {
+                    let (stream, remote_address) = match accepted {
+                        Ok(accepted) => accepted,
+                        Err(e) => {
+                            warn!(target: LOG_TARGET, "Failed to accept SHA3x client: {e}");
+                            continue;
+                        }
+                    };
+                    if !is_lan_address(remote_address.ip()) {
+                        warn!(target: LOG_TARGET, "Refusing SHA3x client {remote_address}: not on the local network");
+                        continue;
+                    }
+                    let upstream_address = self.upstream_address;
+                    let clients = self.clients.clone();
+                    let shutdown_signal = shutdown_signal.clone();
+                    task_tracker.spawn(async move {
+                        Self::forward(stream, remote_address, upstream_address, clients, shutdown_signal).await;
+                    });
+                },
+                _ = shutdown_signal.wait() => break,
+            }
+        }
+    }
+
+    async fn forward(
+        mut stream: TcpStream,
+        remote_address: SocketAddr,
+        upstream_address: SocketAddr,
+        clients: Arc<RwLock<HashMap<String, Sha3xClientStats>>>,
+        mut shutdown_signal: ShutdownSignal,
+    ) {
+        let mut upstream = match TcpStream::connect(upstream_address).await {
+            Ok(upstream) => upstream,
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Failed to connect SHA3x client {remote_address} to node gRPC: {e}");
+                return;
+            }
+        };
+        let client_key = remote_address.ip().to_string();
+        {
+            let mut clients = clients.write().await;
+            let client = clients
+                .entry(client_key.clone())
+                .or_insert_with(|| Sha3xClientStats::new(client_key.clone()));
+            client.active_connections += 1;
+            client.total_connections += 1;
+            client.last_seen = EpochTime::now();
+        }
+        info!(target: LOG_TARGET, "SHA3x client {remote_address} connected");
+
+        let result = tokio::select! {
+            result = copy_bidirectional(&mut stream, &mut upstream) => result.ok(),
+            _ = shutdown_signal.wait() => None,
+        };
+
+        let mut clients = clients.write().await;
+        if let Some(client) = clients.get_mut(&client_key) {
+            client.active_connections = client.active_connections.saturating_sub(1);
+            client.last_seen = EpochTime::now();
+            if let Some((received, sent)) = result {
+                client.bytes_received += received;
+                client.bytes_sent += sent;
+            }
+        }
+    }
+}
+
+/// Loopback, private and link local addresses. IPv4 mapped IPv6 addresses are checked as IPv4.
+pub(super) fn is_lan_address(ip: IpAddr) -> bool {
+    match ip.to_canonical() {
+        IpAddr::V4(ip) => ip.is_loopback() || ip.is_private() || ip.is_link_local(),
+        IpAddr::V6(ip) => ip.is_loopback() || ip.is_unique_local() || ip.is_unicast_link_local(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn only_accepts_local_network_clients() {
+        for address in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.5",
+            "192.168.1.20",
+            "169.254.3.4",
+            "::1",
+            "fd12::1",
+            "fe80::1",
+            "::ffff:192.168.1.20",
+        ] {
+            assert!(is_lan_address(address.parse().unwrap()), "{address}");
+        }
+        for address in ["8.8.8.8", "172.32.0.1", "2001:db8::1", "::ffff:8.8.8.8"] {
+            assert!(!is_lan_address(address.parse().unwrap()), "{address}");
+        }
+    }
+}
@@ -0,0 +1,328 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{collections::HashSet, ops::RangeInclusive, time::Instant};
+
+use anyhow::{anyhow, Error};
+use reqwest::Client;
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+
+/// Size in bytes of the previous block id that precedes the nonce in a Monero block header.
+const PREV_ID_SIZE: usize = 32;
+const NONCE_SIZE: usize = 4;
+/// Workers are told to keep the top nonce byte fixed (the nicehash extension), so the hub can
+/// serve at most this many workers without two of them searching the same nonces.
+pub const MAX_NONCE_PREFIXES: usize = 256;
+
+/// A RandomX job handed out to LAN workers, built from a merge mining proxy block template.
+pub struct MoneroJob {
+    pub job_id: String,
+    pub height: u64,
+    pub seed_hash: String,
+    /// Difficulty a share has to meet to be submitted as a block.
+    pub difficulty: u64,
+    blockhashing_blob: Vec<u8>,
+    hashing_nonce_offset: usize,
+    blocktemplate_blob: Vec<u8>,
+    nonce_offset: usize,
+    submitted_nonces: Mutex<HashSet<[u8; NONCE_SIZE]>>,
+    pub created_at: Instant,
+}
+
+impl MoneroJob {
+    pub fn from_block_template(job_id: String, template: &Value) -> Result<Self, Error> {
+        let field = |name: &str| {
+            template
+                .get(name)
+                .ok_or_else(|| anyhow!("Block template is missing `{name}`"))
+        };
+        let blocktemplate_blob = hex::decode(
+            field("blocktemplate_blob")?
+                .as_str()
+                .ok_or_else(|| anyhow!("Invalid `blocktemplate_blob`"))?,
+        )?;
+        let nonce_offset = nonce_offset(&blocktemplate_blob)?;
+        let blockhashing_blob = hex::decode(
+            field("blockhashing_blob")?
+                .as_str()
+                .ok_or_else(|| anyhow!("Invalid `blockhashing_blob`"))?,
+        )?;
+        let hashing_nonce_offset = nonce_offset(&blockhashing_blob)?;
+
+        Ok(Self {
+            job_id,
+            height: field("height")?
+                .as_u64()
+                .ok_or_else(|| anyhow!("Invalid `height`"))?,
+            seed_hash: field("seed_hash")?
+                .as_str()
+                .ok_or_else(|| anyhow!("Invalid `seed_hash`"))?
+                .to_string(),
+            difficulty: field("difficulty")?
+                .as_u64()
+                .ok_or_else(|| anyhow!("Invalid `difficulty`"))?,
+            blockhashing_blob,
+            hashing_nonce_offset,
+            blocktemplate_blob,
+            nonce_offset,
+            submitted_nonces: Mutex::new(HashSet::new()),
+            created_at: Instant::now(),
+        })
+    }
+
+    pub fn prev_id(&self) -> &[u8] {
+        &self.blocktemplate_blob[self.nonce_offset - PREV_ID_SIZE..self.nonce_offset]
+    }
+
+    /// Hashing blob for a worker, with the worker's nonce prefix in the top byte of the nonce.
+    pub fn blob_for_nonce_prefix(&self, nonce_prefix: u8) -> String {
+        let mut blob = self.blockhashing_blob.clone();
+        blob[self.hashing_nonce_offset + NONCE_SIZE - 1] = nonce_prefix;
+        hex::encode(blob)
+    }
+
+    /// Returns false if the nonce was already submitted for this job.
+    pub async fn register_nonce(&self, nonce: [u8; NONCE_SIZE]) -> bool {
+        self.submitted_nonces.lock().await.insert(nonce)
+    }
+
+    pub fn block_with_nonce(&self, nonce: [u8; NONCE_SIZE]) -> Vec<u8> {
+        let mut block = self.blocktemplate_blob.clone();
+        block[self.nonce_offset..self.nonce_offset + NONCE_SIZE].copy_from_slice(&nonce);
+        block
+    }
+}
+
+/// Hands out the top nonce byte to connected workers so each one searches its own nonce range.
+pub struct NoncePrefixes {
+    in_use: [bool; MAX_NONCE_PREFIXES],
+}
+
+impl Default for NoncePrefixes {
+    fn default() -> Self {
+        Self {
+            in_use: [false; MAX_NONCE_PREFIXES],
+        }
+    }
+}
+
+impl NoncePrefixes {
+    pub fn allocate(&mut self) -> Option<u8> {
+        let index = self.in_use.iter().position(|in_use| !in_use)?;
+        self.in_use[index] = true;
+        u8::try_from(index).ok()
+    }
+
+    pub fn release(&mut self, nonce_prefix: u8) {
+        self.in_use[usize::from(nonce_prefix)] = false;
+    }
+}
+
+/// Nonces a worker with the given prefix may submit, as the little endian value in the blob.
+pub fn nonce_range(nonce_prefix: u8) -> RangeInclusive<u32> {
+    let start = u32::from(nonce_prefix) << 24;
+    start..=(start | 0x00ff_ffff)
+}
+
+pub fn is_in_nonce_range(nonce_prefix: u8, nonce: [u8; NONCE_SIZE]) -> bool {
+    nonce_range(nonce_prefix).contains(&u32::from_le_bytes(nonce))
+}
+
+/// Talks to the merge mining proxy's monerod compatible json rpc.
+#[derive(Clone)]
+pub struct MoneroJobSource {
+    rpc_url: String,
+    wallet_address: String,
+    client: Client,
+}
+
+impl MoneroJobSource {
+    pub fn new(mm_proxy_port: u16, wallet_address: String) -> Self {
+        Self {
+            rpc_url: format!("http://127.0.0.1:{mm_proxy_port}/json_rpc"),
+            wallet_address,
+            client: Client::new(),
+        }
+    }
+
+    pub async fn get_block_template(&self) -> Result<Value, Error> {
+        self.call(
+            "get_block_template",
+            json!({
+                "wallet_address": self.wallet_address,
+                "reserve_size": 0
+            }),
+        )
+        .await
+    }
+
+    pub async fn submit_block(&self, block: &[u8]) -> Result<(), Error> {
+        self.call("submit_block", json!([hex::encode(block)]))
+            .await
+            .map(|_| ())
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value, Error> {
+        let request_body = json!({
+            "jsonrpc": "2.0",
+            "id": "0",
+            "method": method,
+            "params": params
+        });
+        let response: Value = self
+            .client
+            .post(&self.rpc_url)
+            .json(&request_body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        if let Some(error) = response.get("error").filter(|e| !e.is_null()) {
+            return Err(anyhow!("{method} failed: {error}"));
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| anyhow!("{method} returned no result"))
+    }
+}
+
+/// Offset of the nonce in a serialized block: after the major/minor version and timestamp
+/// varints and the previous block id.
+fn nonce_offset(blob: &[u8]) -> Result<usize, Error> {
+    let mut offset = 0;
+    for _ in 0..3 {
+        offset = skip_varint(blob, offset)?;
+    }
+    let nonce_offset = offset + PREV_ID_SIZE;
+    if blob.len() < nonce_offset + NONCE_SIZE {
+        return Err(anyhow!("Block template blob is too short"));
+    }
+    Ok(nonce_offset)
+}
+
+fn skip_varint(blob: &[u8], mut offset: usize) -> Result<usize, Error> {
+    loop {
+        let byte = blob
+            .get(offset)
+            .ok_or_else(|| anyhow!("Unexpected end of block template blob"))?;
+        offset += 1;
+        if byte & 0x80 == 0 {
+            return Ok(offset);
+        }
+    }
+}
+
+/// Compact 4 byte target understood by xmrig for the given share difficulty.
+pub fn difficulty_to_target(difficulty: u64) -> String {
+    let target = u64::from(u32::MAX) / difficulty.max(1);
+    hex::encode(u32::try_from(target).unwrap_or(u32::MAX).to_le_bytes())
+}
+
+/// Same check monerod does: the 256 bit little endian hash multiplied by the difficulty must
+/// not overflow.
+pub fn meets_difficulty(hash: &[u8; 32], difficulty: u64) -> bool {
+    let mut carry: u128 = 0;
+    for limb in hash.chunks_exact(8) {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(limb);
+        let product = u128::from(u64::from_le_bytes(bytes)) * u128::from(difficulty) + carry;
+        carry = product >> 64;
+    }
+    carry == 0
+}
+
+pub fn parse_nonce(nonce: &str) -> Result<[u8; NONCE_SIZE], Error> {
+    let bytes = hex::decode(nonce)?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow!("Nonce must be {NONCE_SIZE} bytes"))
+}
+
+pub fn parse_hash(hash: &str) -> Result<[u8; 32], Error> {
+    let bytes = hex::decode(hash)?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow!("Result hash must be 32 bytes"))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn finds_nonce_after_header_varints() {
+        // major 16, minor 16, two byte timestamp varint, 32 byte prev id, 4 byte nonce
+        let mut blob = vec![0x10, 0x10, 0x80, 0x01];
+        blob.extend_from_slice(&[0xaa; PREV_ID_SIZE]);
+        blob.extend_from_slice(&[0; NONCE_SIZE]);
+        assert_eq!(nonce_offset(&blob).unwrap(), 36);
+        assert!(nonce_offset(&blob[..30]).is_err());
+    }
+
+    #[test]
+    fn workers_get_disjoint_nonce_ranges() {
+        let mut header = vec![0x10, 0x10, 0x01];
+        header.extend_from_slice(&[0xaa; PREV_ID_SIZE]);
+        header.extend_from_slice(&[0; NONCE_SIZE]);
+        let template = json!({
+            "blocktemplate_blob": hex::encode([header.as_slice(), &[0x01, 0x02]].concat()),
+            "blockhashing_blob": hex::encode([header.as_slice(), &[0xbb; 33]].concat()),
+            "height": 1,
+            "seed_hash": "00",
+            "difficulty": 100,
+        });
+        let job = MoneroJob::from_block_template("1".to_string(), &template).unwrap();
+
+        let mut prefixes = NoncePrefixes::default();
+        let first = prefixes.allocate().unwrap();
+        let second = prefixes.allocate().unwrap();
+        assert_ne!(first, second);
+        let (first_range, second_range) = (nonce_range(first), nonce_range(second));
+        assert!(
+            first_range.end() < second_range.start() || second_range.end() < first_range.start()
+        );
+
+        for prefix in [first, second] {
+            let blob = hex::decode(job.blob_for_nonce_prefix(prefix)).unwrap();
+            let nonce: [u8; NONCE_SIZE] = blob[35..39].try_into().unwrap();
+            assert!(is_in_nonce_range(prefix, nonce));
+            assert!(is_in_nonce_range(prefix, [0xff, 0xff, 0xff, prefix]));
+        }
+        assert!(!is_in_nonce_range(first, [0, 0, 0, second]));
+
+        prefixes.release(first);
+        assert_eq!(prefixes.allocate(), Some(first));
+    }
+
+    #[test]
+    fn checks_hash_against_difficulty() {
+        let mut hash = [0u8; 32];
+        hash[31] = 0x01;
+        assert!(meets_difficulty(&hash, 1 << 7));
+        assert!(!meets_difficulty(&hash, 1 << 8));
+        assert_eq!(difficulty_to_target(1), "ffffffff");
+    }
+}
@@ -0,0 +1,356 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{net::SocketAddr, sync::Arc};
+
+use anyhow::{anyhow, Error};
+use log::{info, warn};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tari_shutdown::ShutdownSignal;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{tcp::OwnedWriteHalf, TcpStream},
+    sync::broadcast::error::RecvError,
+};
+
+use crate::utils::rand_utils::get_rand_string;
+
+use super::{
+    monero_job::{
+        difficulty_to_target, is_in_nonce_range, meets_difficulty, parse_hash, parse_nonce,
+        MoneroJob,
+    },
+    stratum_hub_manager::StratumHubState,
+    worker_stats::{WorkerStats, INITIAL_SHARE_DIFFICULTY},
+};
+
+const LOG_TARGET: &str = "tari::universe::stratum_hub";
+const MAX_LINE_LENGTH: usize = 16 * 1024;
+const MAX_WORKER_NAME_LENGTH: usize = 64;
+
+#[derive(Deserialize)]
+struct StratumRequest {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Deserialize)]
+struct LoginParams {
+    #[serde(default)]
+    login: String,
+    #[serde(default)]
+    rigid: String,
+    agent: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SubmitParams {
+    job_id: String,
+    nonce: String,
+    result: String,
+}
+
+/// A single xmrig (or compatible) worker connected to the hub over the stratum protocol.
+pub struct StratumConnection {
+    state: Arc<StratumHubState>,
+    remote_address: SocketAddr,
+    session_id: String,
+    worker_key: Option<String>,
+    /// Top nonce byte reserved for this connection, see `NoncePrefixes`.
+    nonce_prefix: Option<u8>,
+    share_difficulty: u64,
+    /// Difficulty of the previous job, so shares still in flight after a retarget are not lost.
+    previous_share_difficulty: u64,
+}
+
+impl StratumConnection {
+    pub fn new(state: Arc<StratumHubState>, remote_address: SocketAddr) -> Self {
+        Self {
+            state,
+            remote_address,
+            session_id: get_rand_string(16),
+            worker_key: None,
+            nonce_prefix: None,
+            share_difficulty: INITIAL_SHARE_DIFFICULTY,
+            previous_share_difficulty: INITIAL_SHARE_DIFFICULTY,
+        }
+    }
+
+    pub async fn run(mut self, stream: TcpStream, mut shutdown_signal: ShutdownSignal) {
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut job_receiver = self.state.job_broadcast.subscribe();
+
+        loop {
+            tokio::select! {
+                line = lines.next_line() => {
+                    let line = match line {
+                        Ok(Some(line)) if line.len() <= MAX_LINE_LENGTH => line,
+                        Ok(Some(_)) => {
+                            warn!(target: LOG_TARGET, "Dropping {}: request too long", self.remote_address);
+                            break;
+                        }
+                        Ok(None) => break,
+                        Err(e) => {
+                            warn!(target: LOG_TARGET, "Read from {} failed: {e}", self.remote_address);
+                            break;
+                        }
+                    };
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let response = self.handle_line(&line).await;
+                    if let Err(e) = Self::send(&mut writer, &response).await {
+                        warn!(target: LOG_TARGET, "Write to {} failed: {e}", self.remote_address);
+                        break;
+                    }
+                },
+                job = job_receiver.recv() => {
+                    let job = match job {
+                        Ok(job) => job,
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    };
+                    let Some(nonce_prefix) = self.nonce_prefix else {
+                        continue;
+                    };
+                    self.retarget().await;
+                    let notification = json!({
+                        "jsonrpc": "2.0",
+                        "method": "job",
+                        "params": self.job_params(&job, nonce_prefix),
+                    });
+                    if let Err(e) = Self::send(&mut writer, &notification).await {
+                        warn!(target: LOG_TARGET, "Write to {} failed: {e}", self.remote_address);
+                        break;
+                    }
+                },
+                _ = shutdown_signal.wait() => break,
+            }
+        }
+
+        if let Some(nonce_prefix) = self.nonce_prefix {
+            self.state.nonce_prefixes.lock().await.release(nonce_prefix);
+        }
+        if let Some(worker_key) = &self.worker_key {
+            if let Some(worker) = self.state.workers.write().await.get_mut(worker_key) {
+                worker.is_connected = false;
+            }
+            info!(target: LOG_TARGET, "Worker {worker_key} disconnected");
+        }
+    }
+
+    async fn send(writer: &mut OwnedWriteHalf, message: &Value) -> Result<(), Error> {
+        let mut payload = serde_json::to_vec(message)?;
+        payload.push(b'\n');
+        writer.write_all(&payload).await?;
+        Ok(())
+    }
+
+    async fn handle_line(&mut self, line: &str) -> Value {
+        let request: StratumRequest = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(e) => return Self::error_response(&Value::Null, &format!("Invalid request: {e}")),
+        };
+        let result = match request.method.as_str() {
+            "login" => self.login(request.params).await,
+            "getjob" => self.current_job().await,
+            "submit" => self.submit(request.params).await,
+            "keepalived" => Ok(json!({ "status": "KEEPALIVED" })),
+            method => Err(anyhow!("Unsupported method: {method}")),
+        };
+        match result {
+            Ok(result) => json!({
+                "id": request.id,
+                "jsonrpc": "2.0",
+                "error": null,
+                "result": result,
+            }),
+            Err(e) => Self::error_response(&request.id, &e.to_string()),
+        }
+    }
+
+    fn error_response(id: &Value, message: &str) -> Value {
+        json!({
+            "id": id,
+            "jsonrpc": "2.0",
+            "error": { "code": -1, "message": message },
+            "result": null,
+        })
+    }
+
+    async fn login(&mut self, params: Value) -> Result<Value, Error> {
+        let params: LoginParams = serde_json::from_value(params)?;
+        let name = if params.rigid.trim().is_empty() {
+            params.login.trim()
+        } else {
+            params.rigid.trim()
+        };
+        let name: String = if name.is_empty() { "worker" } else { name }
+            .chars()
+            .take(MAX_WORKER_NAME_LENGTH)
+            .collect();
+        let worker_key = format!("{name}@{}", self.remote_address.ip());
+        let nonce_prefix = match self.nonce_prefix {
+            Some(nonce_prefix) => nonce_prefix,
+            None => self
+                .state
+                .nonce_prefixes
+                .lock()
+                .await
+                .allocate()
+                .ok_or_else(|| anyhow!("Stratum hub has no free nonce space for more workers"))?,
+        };
+        self.nonce_prefix = Some(nonce_prefix);
+
+        {
+            let mut workers = self.state.workers.write().await;
+            workers
+                .entry(worker_key.clone())
+                .and_modify(|worker| worker.reconnect(self.remote_address, params.agent.clone()))
+                .or_insert_with(|| WorkerStats::new(name, self.remote_address, params.agent));
+        }
+        info!(target: LOG_TARGET, "Worker {worker_key} logged in");
+        self.worker_key = Some(worker_key);
+        self.share_difficulty = INITIAL_SHARE_DIFFICULTY;
+        self.previous_share_difficulty = INITIAL_SHARE_DIFFICULTY;
+
+        let job = self
+            .state
+            .current_job()
+            .await
+            .ok_or_else(|| anyhow!("No job available yet"))?;
+        Ok(json!({
+            "id": self.session_id,
+            "job": self.job_params(&job, nonce_prefix),
+            "extensions": ["keepalive", "nicehash"],
+            "status": "OK",
+        }))
+    }
+
+    async fn current_job(&self) -> Result<Value, Error> {
+        let nonce_prefix = self
+            .nonce_prefix
+            .ok_or_else(|| anyhow!("Unauthenticated"))?;
+        let job = self
+            .state
+            .current_job()
+            .await
+            .ok_or_else(|| anyhow!("No job available yet"))?;
+        Ok(self.job_params(&job, nonce_prefix))
+    }
+
+    async fn submit(&mut self, params: Value) -> Result<Value, Error> {
+        let (Some(worker_key), Some(nonce_prefix)) = (self.worker_key.clone(), self.nonce_prefix)
+        else {
+            return Err(anyhow!("Unauthenticated"));
+        };
+        let params: SubmitParams = serde_json::from_value(params)?;
+
+        let Some(job) = self.state.find_job(&params.job_id).await else {
+            self.update_worker(&worker_key, |worker| worker.stale_shares += 1)
+                .await;
+            return Err(anyhow!("Block expired"));
+        };
+        let (nonce, hash) = match (parse_nonce(&params.nonce), parse_hash(&params.result)) {
+            (Ok(nonce), Ok(hash)) => (nonce, hash),
+            (Err(e), _) | (_, Err(e)) => {
+                self.update_worker(&worker_key, |worker| worker.rejected_shares += 1)
+                    .await;
+                return Err(e);
+            }
+        };
+        if !is_in_nonce_range(nonce_prefix, nonce) {
+            self.update_worker(&worker_key, |worker| worker.rejected_shares += 1)
+                .await;
+            return Err(anyhow!("Nonce outside of the worker's nonce range"));
+        }
+        if !job.register_nonce(nonce).await {
+            self.update_worker(&worker_key, |worker| worker.rejected_shares += 1)
+                .await;
+            return Err(anyhow!("Duplicate share"));
+        }
+        let share_difficulty = self.share_difficulty.min(self.previous_share_difficulty);
+        if !meets_difficulty(&hash, share_difficulty) {
+            self.update_worker(&worker_key, |worker| worker.rejected_shares += 1)
+                .await;
+            return Err(anyhow!("Low difficulty share"));
+        }
+
+        self.update_worker(&worker_key, |worker| {
+            worker.record_accepted(share_difficulty)
+        })
+        .await;
+
+        if meets_difficulty(&hash, job.difficulty) {
+            self.submit_block(&worker_key, &job, nonce).await;
+        }
+
+        Ok(json!({ "status": "OK" }))
+    }
+
+    async fn submit_block(&self, worker_key: &str, job: &MoneroJob, nonce: [u8; 4]) {
+        let Some(job_source) = self.state.job_source.read().await.clone() else {
+            return;
+        };
+        match job_source.submit_block(&job.block_with_nonce(nonce)).await {
+            Ok(()) => {
+                info!(target: LOG_TARGET, "Worker {worker_key} found a block at height {}", job.height);
+                self.update_worker(worker_key, |worker| worker.blocks_found += 1)
+                    .await;
+            }
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Block from worker {worker_key} was rejected: {e}");
+            }
+        }
+    }
+
+    async fn update_worker(&self, worker_key: &str, update: impl FnOnce(&mut WorkerStats)) {
+        if let Some(worker) = self.state.workers.write().await.get_mut(worker_key) {
+            update(worker);
+        }
+    }
+
+    async fn retarget(&mut self) {
+        if let Some(worker_key) = &self.worker_key {
+            if let Some(worker) = self.state.workers.write().await.get_mut(worker_key) {
+                self.previous_share_difficulty = self.share_difficulty;
+                self.share_difficulty = worker.retarget();
+            }
+        }
+    }
+
+    fn job_params(&self, job: &MoneroJob, nonce_prefix: u8) -> Value {
+        json!({
+            "blob": job.blob_for_nonce_prefix(nonce_prefix),
+            "job_id": job.job_id,
+            "target": difficulty_to_target(self.share_difficulty),
+            "algo": "rx/0",
+            "height": job.height,
+            "seed_hash": job.seed_hash,
+        })
+    }
+}
@@ -0,0 +1,326 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    net::{Ipv4Addr, SocketAddr},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use anyhow::{anyhow, Error};
+use log::{info, warn};
+use serde::Serialize;
+use tari_shutdown::{Shutdown, ShutdownSignal};
+use tokio::{
+    net::TcpListener,
+    sync::{broadcast, Mutex, RwLock},
+};
+use tokio_util::task::TaskTracker;
+
+use crate::tasks_tracker::TasksTrackers;
+
+use super::{
+    grpc_forwarder::{is_lan_address, GrpcForwarder},
+    monero_job::{MoneroJob, MoneroJobSource, NoncePrefixes},
+    stratum_connection::StratumConnection,
+    worker_stats::{Sha3xClientStats, WorkerStats},
+};
+
+const LOG_TARGET: &str = "tari::universe::stratum_hub";
+const TEMPLATE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Hand out a fresh template at least this often so the Tari side of merge mining stays current.
+const JOB_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+/// Number of recent jobs that still accept shares.
+const MAX_ACTIVE_JOBS: usize = 4;
+
+pub struct StratumHubStartConfig {
+    pub stratum_port: u16,
+    pub mm_proxy_port: u16,
+    pub monero_address: String,
+    pub sha3x_port: u16,
+    /// gRPC port of the local node forwarded to SHA3x miners. `None` if the node is remote.
+    pub node_grpc_port: Option<u16>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StratumHubStatus {
+    pub is_running: bool,
+    pub stratum_port: Option<u16>,
+    pub sha3x_port: Option<u16>,
+    pub job_height: Option<u64>,
+    pub workers: Vec<WorkerStats>,
+    pub sha3x_clients: Vec<Sha3xClientStats>,
+}
+
+pub struct StratumHubState {
+    pub job_source: RwLock<Option<MoneroJobSource>>,
+    pub job_broadcast: broadcast::Sender<Arc<MoneroJob>>,
+    pub workers: RwLock<HashMap<String, WorkerStats>>,
+    pub nonce_prefixes: Mutex<NoncePrefixes>,
+    jobs: RwLock<VecDeque<Arc<MoneroJob>>>,
+    job_counter: AtomicU64,
+    sha3x_clients: Arc<RwLock<HashMap<String, Sha3xClientStats>>>,
+}
+
+impl StratumHubState {
+    pub async fn current_job(&self) -> Option<Arc<MoneroJob>> {
+        self.jobs.read().await.front().cloned()
+    }
+
+    pub async fn find_job(&self, job_id: &str) -> Option<Arc<MoneroJob>> {
+        self.jobs
+            .read()
+            .await
+            .iter()
+            .find(|job| job.job_id == job_id)
+            .cloned()
+    }
+}
+
+/// LAN mining hub: other rigs point xmrig at `<this machine>:<stratum port>` and mine RandomX
+/// through the local merge mining proxy, while GPU miners can use the forwarded node gRPC.
+/// Share and hashrate accounting is only available for the stratum workers, SHA3x clients only
+/// get connection accounting. Both ports only accept clients on the local network.
+#[derive(Clone)]
+pub struct StratumHubManager {
+    state: Arc<StratumHubState>,
+    shutdown: Arc<RwLock<Option<Shutdown>>>,
+    ports: Arc<RwLock<(Option<u16>, Option<u16>)>>,
+}
+
+impl StratumHubManager {
+    pub fn new() -> Self {
+        let (job_broadcast, _) = broadcast::channel(8);
+        Self {
+            state: Arc::new(StratumHubState {
+                job_source: RwLock::new(None),
+                job_broadcast,
+                workers: RwLock::new(HashMap::new()),
+                nonce_prefixes: Mutex::new(NoncePrefixes::default()),
+                jobs: RwLock::new(VecDeque::new()),
+                job_counter: AtomicU64::new(0),
+                sha3x_clients: Arc::new(RwLock::new(HashMap::new())),
+            }),
+            shutdown: Arc::new(RwLock::new(None)),
+            ports: Arc::new(RwLock::new((None, None))),
+        }
+    }
+
+    pub async fn start(&self, config: StratumHubStartConfig) -> Result<(), Error> {
+        self.stop().await;
+        if config.monero_address.is_empty() {
+            return Err(anyhow!("Stratum hub needs a Monero address to mine to"));
+        }
+
+        let stratum_listener = Self::bind(config.stratum_port).await?;
+        let forwarder = match config.node_grpc_port {
+            Some(node_grpc_port) => Some(GrpcForwarder::new(
+                Self::bind(config.sha3x_port).await?,
+                SocketAddr::from((Ipv4Addr::LOCALHOST, node_grpc_port)),
+                self.state.sha3x_clients.clone(),
+            )),
+            None => None,
+        };
+
+        *self.state.job_source.write().await = Some(MoneroJobSource::new(
+            config.mm_proxy_port,
+            config.monero_address,
+        ));
+        self.state.jobs.write().await.clear();
+        *self.ports.write().await = (
+            Some(config.stratum_port),
+            forwarder.as_ref().map(|_| config.sha3x_port),
+        );
+
+        let shutdown = Shutdown::new();
+        let shutdown_signal = shutdown.to_signal();
+        *self.shutdown.write().await = Some(shutdown);
+
+        let task_tracker = TasksTrackers::current()
+            .mining_phase
+            .get_task_tracker()
+            .await;
+        let mut phase_shutdown_signal = TasksTrackers::current().mining_phase.get_signal().await;
+
+        let manager = self.clone();
+        let mut hub_shutdown_signal = shutdown_signal.clone();
+        task_tracker.spawn(async move {
+            tokio::select! {
+                _ = phase_shutdown_signal.wait() => manager.stop().await,
+                _ = hub_shutdown_signal.wait() => {},
+            }
+        });
+
+        let state = self.state.clone();
+        let signal = shutdown_signal.clone();
+        task_tracker.spawn(async move {
+            Self::poll_jobs(state, signal).await;
+        });
+
+        let state = self.state.clone();
+        let signal = shutdown_signal.clone();
+        let tracker = task_tracker.clone();
+        task_tracker.spawn(async move {
+            Self::accept_workers(stratum_listener, state, tracker, signal).await;
+        });
+
+        if let Some(forwarder) = forwarder {
+            let tracker = task_tracker.clone();
+            task_tracker.spawn(async move {
+                forwarder.run(tracker, shutdown_signal).await;
+            });
+        }
+
+        info!(target: LOG_TARGET, "Stratum hub listening on port {}", config.stratum_port);
+        Ok(())
+    }
+
+    pub async fn stop(&self) {
+        if let Some(mut shutdown) = self.shutdown.write().await.take() {
+            shutdown.trigger();
+            info!(target: LOG_TARGET, "Stratum hub stopped");
+        }
+        *self.ports.write().await = (None, None);
+        for worker in self.state.workers.write().await.values_mut() {
+            worker.is_connected = false;
+        }
+    }
+
+    pub async fn is_running(&self) -> bool {
+        self.shutdown
+            .read()
+            .await
+            .as_ref()
+            .is_some_and(|shutdown| !shutdown.is_triggered())
+    }
+
+    pub async fn get_status(&self) -> StratumHubStatus {
+        let (stratum_port, sha3x_port) = *self.ports.read().await;
+        let mut workers: Vec<WorkerStats> = self
+            .state
+            .workers
+            .write()
+            .await
+            .values_mut()
+            .map(|worker| {
+                worker.update_hashrate();
+                worker.clone()
+            })
+            .collect();
+        workers.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut sha3x_clients: Vec<Sha3xClientStats> = self
+            .state
+            .sha3x_clients
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        sha3x_clients.sort_by(|a, b| a.remote_address.cmp(&b.remote_address));
+
+        StratumHubStatus {
+            is_running: self.is_running().await,
+            stratum_port,
+            sha3x_port,
+            job_height: self.state.current_job().await.map(|job| job.height),
+            workers,
+            sha3x_clients,
+        }
+    }
+
+    async fn bind(port: u16) -> Result<TcpListener, Error> {
+        TcpListener::bind(SocketAddr::from((Ipv4Addr::UNSPECIFIED, port)))
+            .await
+            .map_err(|e| anyhow!("Failed to listen on port {port}: {e}"))
+    }
+
+    async fn accept_workers(
+        listener: TcpListener,
+        state: Arc<StratumHubState>,
+        task_tracker: TaskTracker,
+        mut shutdown_signal: ShutdownSignal,
+    ) {
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((_, remote_address)) if !is_lan_address(remote_address.ip()) => {
+                        warn!(target: LOG_TARGET, "Refusing worker {remote_address}: not on the local network");
+                    }
+                    Ok((stream, remote_address)) => {
+                        let connection = StratumConnection::new(state.clone(), remote_address);
+                        let signal = shutdown_signal.clone();
+                        task_tracker.spawn(async move {
+                            connection.run(stream, signal).await;
+                        });
+                    }
+                    Err(e) => warn!(target: LOG_TARGET, "Failed to accept worker: {e}"),
+                },
+                _ = shutdown_signal.wait() => break,
+            }
+        }
+    }
+
+    async fn poll_jobs(state: Arc<StratumHubState>, mut shutdown_signal: ShutdownSignal) {
+        let mut interval = tokio::time::interval(TEMPLATE_POLL_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    if let Err(e) = Self::refresh_job(&state).await {
+                        warn!(target: LOG_TARGET, "Failed to refresh stratum job: {e}");
+                    }
+                },
+                _ = shutdown_signal.wait() => break,
+            }
+        }
+    }
+
+    async fn refresh_job(state: &StratumHubState) -> Result<(), Error> {
+        let Some(job_source) = state.job_source.read().await.clone() else {
+            return Ok(());
+        };
+        let template = job_source.get_block_template().await?;
+        let job_id = format!("{:x}", state.job_counter.fetch_add(1, Ordering::Relaxed));
+        let job = Arc::new(MoneroJob::from_block_template(job_id, &template)?);
+
+        if let Some(current) = state.current_job().await {
+            if current.height == job.height
+                && current.prev_id() == job.prev_id()
+                && current.created_at.elapsed() < JOB_REFRESH_INTERVAL
+            {
+                return Ok(());
+            }
+        }
+
+        {
+            let mut jobs = state.jobs.write().await;
+            jobs.push_front(job.clone());
+            jobs.truncate(MAX_ACTIVE_JOBS);
+        }
+        // No receivers just means no workers are connected.
+        let _unused = state.job_broadcast.send(job);
+        Ok(())
+    }
+}
@@ -0,0 +1,177 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::VecDeque,
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use tari_utilities::epoch_time::EpochTime;
+
+const HASHRATE_WINDOW: Duration = Duration::from_secs(10 * 60);
+const TARGET_SHARE_INTERVAL_SECS: f64 = 15.0;
+pub const INITIAL_SHARE_DIFFICULTY: u64 = 10_000;
+const MIN_SHARE_DIFFICULTY: u64 = 1_000;
+/// H/s, well above what a single rig does on RandomX. The hub has no RandomX hasher, so a share's
+/// result hash is taken from the worker unverified and a worker could claim any amount of work.
+const MAX_WORKER_HASHRATE: f64 = 200_000.0;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkerStats {
+    pub name: String,
+    pub remote_address: SocketAddr,
+    pub agent: Option<String>,
+    pub is_connected: bool,
+    pub connected_since: EpochTime,
+    pub last_share_at: Option<EpochTime>,
+    pub accepted_shares: u64,
+    pub rejected_shares: u64,
+    pub stale_shares: u64,
+    pub blocks_found: u64,
+    pub share_difficulty: u64,
+    pub hashrate: f64,
+    #[serde(skip)]
+    connected_at: Instant,
+    #[serde(skip)]
+    recent_shares: VecDeque<(Instant, u64)>,
+}
+
+impl WorkerStats {
+    pub fn new(name: String, remote_address: SocketAddr, agent: Option<String>) -> Self {
+        Self {
+            name,
+            remote_address,
+            agent,
+            is_connected: true,
+            connected_since: EpochTime::now(),
+            last_share_at: None,
+            accepted_shares: 0,
+            rejected_shares: 0,
+            stale_shares: 0,
+            blocks_found: 0,
+            share_difficulty: INITIAL_SHARE_DIFFICULTY,
+            hashrate: 0.0,
+            connected_at: Instant::now(),
+            recent_shares: VecDeque::new(),
+        }
+    }
+
+    /// Marks a returning worker as connected again while keeping its share counters.
+    pub fn reconnect(&mut self, remote_address: SocketAddr, agent: Option<String>) {
+        self.remote_address = remote_address;
+        self.agent = agent;
+        self.is_connected = true;
+        self.connected_since = EpochTime::now();
+        self.connected_at = Instant::now();
+        self.recent_shares.clear();
+        self.share_difficulty = INITIAL_SHARE_DIFFICULTY;
+        self.hashrate = 0.0;
+    }
+
+    pub fn record_accepted(&mut self, difficulty: u64) {
+        self.accepted_shares += 1;
+        self.last_share_at = Some(EpochTime::now());
+        self.recent_shares.push_back((Instant::now(), difficulty));
+        self.update_hashrate();
+    }
+
+    pub fn update_hashrate(&mut self) {
+        while self
+            .recent_shares
+            .front()
+            .is_some_and(|(at, _)| at.elapsed() > HASHRATE_WINDOW)
+        {
+            self.recent_shares.pop_front();
+        }
+        let window = self
+            .connected_at
+            .elapsed()
+            .min(HASHRATE_WINDOW)
+            .as_secs_f64();
+        let total_difficulty: u64 = self.recent_shares.iter().map(|(_, d)| d).sum();
+        self.hashrate = if window > 0.0 {
+            (total_difficulty as f64 / window).min(MAX_WORKER_HASHRATE)
+        } else {
+            0.0
+        };
+    }
+
+    /// Retargets the share difficulty so the worker submits roughly one share every
+    /// `TARGET_SHARE_INTERVAL_SECS`. Keeps the current difficulty until enough shares are in.
+    /// Bounded by `MAX_WORKER_HASHRATE`, which caps the difficulty a share is credited with.
+    pub fn retarget(&mut self) -> u64 {
+        self.update_hashrate();
+        if self.recent_shares.len() >= 4 {
+            let wanted = (self.hashrate * TARGET_SHARE_INTERVAL_SECS).round();
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let wanted = wanted.max(0.0) as u64;
+            self.share_difficulty = wanted.max(MIN_SHARE_DIFFICULTY);
+        }
+        self.share_difficulty
+    }
+}
+
+/// Connection accounting only: the SHA3x gRPC traffic is forwarded opaquely, so there is no
+/// per-client hashrate or share count.
+#[derive(Debug, Clone, Serialize)]
+pub struct Sha3xClientStats {
+    pub remote_address: String,
+    pub active_connections: u32,
+    pub total_connections: u64,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub last_seen: EpochTime,
+}
+
+impl Sha3xClientStats {
+    pub fn new(remote_address: String) -> Self {
+        Self {
+            remote_address,
+            active_connections: 0,
+            total_connections: 0,
+            bytes_sent: 0,
+            bytes_received: 0,
+            last_seen: EpochTime::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn claimed_work_is_capped() {
+        let mut worker =
+            WorkerStats::new("rig".to_string(), ([192, 168, 1, 20], 3333).into(), None);
+        worker.connected_at = Instant::now() - Duration::from_secs(60);
+        for _ in 0..10 {
+            worker.record_accepted(u64::MAX / 100);
+        }
+        assert!(worker.hashrate <= MAX_WORKER_HASHRATE);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let max_difficulty = (MAX_WORKER_HASHRATE * TARGET_SHARE_INTERVAL_SECS) as u64;
+        assert!(worker.retarget() <= max_difficulty);
+    }
+}
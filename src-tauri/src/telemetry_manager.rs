@@ -513,6 +513,10 @@ async fn get_telemetry_data_inner(
         "config_tor_enabled".to_string(),
         config.use_tor().to_string(),
     );
+    extra_data.insert(
+        "config_metered_connection".to_string(),
+        config.metered_connection().to_string(),
+    );
 
     // Add payment ID from current tari address
     if InternalWallet::is_initialized() {
@@ -558,6 +562,14 @@ async fn get_telemetry_data_inner(
         "tor_circuit_ok".to_string(),
         tor_status.circuit_ok.to_string(),
     );
+    extra_data.insert(
+        "tor_bytes_read".to_string(),
+        tor_status.bytes_read.to_string(),
+    );
+    extra_data.insert(
+        "tor_bytes_written".to_string(),
+        tor_status.bytes_written.to_string(),
+    );
 
     if !all_cpus.is_empty() {
         extra_data.insert("all_cpus".to_string(), all_cpus.join(","));
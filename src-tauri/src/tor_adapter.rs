@@ -50,6 +50,7 @@ pub(crate) struct TorAdapter {
     socks_port: u16,
     config_file: Option<PathBuf>,
     config: TorConfig,
+    pub(crate) bandwidth_limits: BandwidthLimits,
     status_broadcast: watch::Sender<TorStatus>,
 }
 
@@ -61,6 +62,7 @@ impl TorAdapter {
             socks_port: port,
             config_file: None,
             config: TorConfig::default(),
+            bandwidth_limits: BandwidthLimits::default(),
             status_broadcast,
         }
     }
@@ -284,6 +286,14 @@ impl ProcessAdapter for TorAdapter {
             args.push("1".to_string());
         }
 
+        if let Some(rate) = self.bandwidth_limits.kib_per_sec {
+            info!(target: LOG_TARGET, "Limiting tor bandwidth to {rate} KiB/s");
+            args.push("--BandwidthRate".to_string());
+            args.push(format!("{rate} KBytes"));
+            args.push("--BandwidthBurst".to_string());
+            args.push(format!("{rate} KBytes"));
+        }
+
         Ok((
             ProcessInstance {
                 shutdown: inner_shutdown,
@@ -374,6 +384,30 @@ impl Default for TorConfig {
     }
 }
 
+/// Cap in KiB/s. Tor enforces it as a byte rate for everything routed through it, applied to
+/// upload and download separately. The node can't cap bytes itself, it runs with fewer peers and
+/// sync sessions instead, with or without Tor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BandwidthLimits {
+    pub kib_per_sec: Option<u64>,
+}
+
+impl BandwidthLimits {
+    pub fn is_limited(&self) -> bool {
+        self.kib_per_sec.is_some()
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.kib_per_sec == Some(0) {
+            return Err(anyhow!(
+                "Bandwidth limit must be greater than 0 KiB/s, leave empty for no limit"
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[cfg(target_os = "linux")]
 fn get_libevent_envs(_binary_version_path: &std::path::Path) -> Option<HashMap<String, String>> {
     let mut tor_bundle_path = _binary_version_path.to_path_buf();
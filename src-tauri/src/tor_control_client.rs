@@ -34,6 +34,8 @@ const BOOTSTRAP_QUERY: &str = "GETINFO status/bootstrap-phase\r\n";
 const AUTH_COMMAND: &str = "AUTHENTICATE\r\n";
 const CIRCUIT_QUERY: &str = "GETINFO status/circuit-established\r\n";
 const NETWORK_QUERY: &str = "GETINFO network-liveness\r\n";
+const TRAFFIC_READ_QUERY: &str = "GETINFO traffic/read\r\n";
+const TRAFFIC_WRITTEN_QUERY: &str = "GETINFO traffic/written\r\n";
 
 #[derive(Default, Clone, Copy, Debug, Serialize)]
 pub(crate) struct TorStatus {
@@ -41,6 +43,9 @@ pub(crate) struct TorStatus {
     pub is_bootstrapped: bool,
     pub network_liveness: bool,
     pub circuit_ok: bool,
+    // Totals since tor started, includes node traffic when it is routed through tor
+    pub bytes_read: u64,
+    pub bytes_written: u64,
 }
 
 pub(crate) struct TorControlClient {
@@ -116,12 +121,47 @@ impl TorControlClient {
             if response.contains("network-liveness=up") {
                 network_liveness = true;
             }
+            let _s250_ok = reader.next_line().await?;
+        }
+
+        let mut bytes_read = 0;
+        writer.write_all(TRAFFIC_READ_QUERY.as_bytes()).await?;
+        writer.flush().await?;
+        if let Some(response) = reader.next_line().await? {
+            // Expected output 250-traffic/read=12345
+            bytes_read = parse_traffic_response(&response, "traffic/read");
+            let _s250_ok = reader.next_line().await?;
         }
+
+        let mut bytes_written = 0;
+        writer.write_all(TRAFFIC_WRITTEN_QUERY.as_bytes()).await?;
+        writer.flush().await?;
+        if let Some(response) = reader.next_line().await? {
+            bytes_written = parse_traffic_response(&response, "traffic/written");
+        }
+
         Ok(TorStatus {
             bootstrap_phase,
             is_bootstrapped: bootstrapped,
             network_liveness,
             circuit_ok,
+            bytes_read,
+            bytes_written,
         })
     }
 }
+
+fn parse_traffic_response(response: &str, key: &str) -> u64 {
+    match response
+        .strip_prefix("250-")
+        .and_then(|r| r.strip_prefix(key))
+        .and_then(|r| r.strip_prefix('='))
+        .and_then(|r| r.trim().parse::<u64>().ok())
+    {
+        Some(bytes) => bytes,
+        None => {
+            warn!(target: LOG_TARGET, "Failed to parse {key} response: {response}");
+            0
+        }
+    }
+}
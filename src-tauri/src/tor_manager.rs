@@ -20,6 +20,8 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use crate::configs::config_core::ConfigCore;
+use crate::configs::trait_config::ConfigImpl;
 use crate::process_stats_collector::ProcessStatsCollectorBuilder;
 use crate::process_watcher::ProcessWatcher;
 use crate::tasks_tracker::TasksTrackers;
@@ -82,6 +84,8 @@ impl TorManager {
                 .adapter
                 .load_or_create_config(config_path.clone())
                 .await?;
+            process_watcher.adapter.bandwidth_limits =
+                *ConfigCore::content().await.bandwidth_limits();
             process_watcher
                 .start(
                     base_path,
@@ -154,6 +158,10 @@ impl TorManager {
             .await
     }
 
+    pub fn get_status(&self) -> TorStatus {
+        *self.status_watch_rx.borrow()
+    }
+
     pub async fn get_control_port(&self) -> Result<Option<u16>, anyhow::Error> {
         let process_watcher = self.watcher.read().await;
         Ok(process_watcher
@@ -20,14 +20,19 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::num::NonZeroU32;
+
 use anyhow::anyhow;
 use ring::{
-    aead,
+    aead, pbkdf2,
     rand::{SecureRandom, SystemRandom},
 };
 use sha2::{Digest, Sha256};
 use tari_utilities::SafePassword;
 
+const PBKDF2_ITERATIONS: u32 = 600_000;
+pub const SALT_LENGTH: usize = 16;
+
 fn derive_key(passphrase: &SafePassword) -> [u8; 32] {
     let mut hasher = Sha256::new();
     hasher.update(passphrase.reveal());
@@ -37,8 +42,54 @@ fn derive_key(passphrase: &SafePassword) -> [u8; 32] {
     key
 }
 
+// Slow key derivation for user chosen passphrases, which are far weaker than a PIN protected seed
+fn derive_stretched_key(passphrase: &SafePassword, salt: &[u8]) -> Result<[u8; 32], anyhow::Error> {
+    let iterations = NonZeroU32::new(PBKDF2_ITERATIONS)
+        .ok_or_else(|| anyhow!("Invalid key derivation iterations"))?;
+    let mut key = [0u8; 32];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.reveal(),
+        &mut key,
+    );
+    Ok(key)
+}
+
+pub fn generate_salt() -> Result<[u8; SALT_LENGTH], anyhow::Error> {
+    let mut salt = [0u8; SALT_LENGTH];
+    SystemRandom::new()
+        .fill(&mut salt)
+        .map_err(|_| anyhow!("Failed to generate salt"))?;
+    Ok(salt)
+}
+
 pub fn encrypt(data: &[u8], passphrase: &SafePassword) -> Result<Vec<u8>, anyhow::Error> {
-    let key_bytes = derive_key(passphrase);
+    seal(data, derive_key(passphrase))
+}
+
+pub fn decrypt(encrypted_data: &[u8], passphrase: &SafePassword) -> Result<Vec<u8>, anyhow::Error> {
+    open(encrypted_data, derive_key(passphrase))
+}
+
+pub fn encrypt_with_salt(
+    data: &[u8],
+    passphrase: &SafePassword,
+    salt: &[u8],
+) -> Result<Vec<u8>, anyhow::Error> {
+    seal(data, derive_stretched_key(passphrase, salt)?)
+}
+
+pub fn decrypt_with_salt(
+    encrypted_data: &[u8],
+    passphrase: &SafePassword,
+    salt: &[u8],
+) -> Result<Vec<u8>, anyhow::Error> {
+    open(encrypted_data, derive_stretched_key(passphrase, salt)?)
+}
+
+fn seal(data: &[u8], key_bytes: [u8; 32]) -> Result<Vec<u8>, anyhow::Error> {
     let unbound_key = aead::UnboundKey::new(&aead::AES_256_GCM, &key_bytes)
         .map_err(|_| anyhow!("Failed to create unbound key"))?;
     let key = aead::LessSafeKey::new(unbound_key);
@@ -60,11 +111,10 @@ pub fn encrypt(data: &[u8], passphrase: &SafePassword) -> Result<Vec<u8>, anyhow
     Ok(result)
 }
 
-pub fn decrypt(encrypted_data: &[u8], passphrase: &SafePassword) -> Result<Vec<u8>, anyhow::Error> {
+fn open(encrypted_data: &[u8], key_bytes: [u8; 32]) -> Result<Vec<u8>, anyhow::Error> {
     if encrypted_data.len() < 12 + 16 {
         return Err(anyhow!("Invalid encrypted data length"));
     }
-    let key_bytes = derive_key(passphrase);
     let unbound_key = aead::UnboundKey::new(&aead::AES_256_GCM, &key_bytes)
         .map_err(|_| anyhow!("Failed to create unbound key"))?;
     let key = aead::LessSafeKey::new(unbound_key);
@@ -115,3 +165,20 @@ fn test_decrypt_with_tampered_ciphertext_fails() {
         "Decryption should fail if ciphertext is tampered"
     );
 }
+
+#[test]
+fn test_encrypt_decrypt_with_salt_roundtrip() {
+    let data = b"Secret message";
+    let passphrase = SafePassword::from("correct horse battery staple");
+    let salt = generate_salt().expect("Salt generation failed");
+    let encrypted = encrypt_with_salt(data, &passphrase, &salt).expect("Encryption failed");
+    let decrypted = decrypt_with_salt(&encrypted, &passphrase, &salt).expect("Decryption failed");
+    assert_eq!(decrypted, data);
+
+    let other_salt = generate_salt().expect("Salt generation failed");
+    let result = decrypt_with_salt(&encrypted, &passphrase, &other_salt);
+    assert!(
+        result.is_err(),
+        "Decryption should fail with a different salt"
+    );
+}
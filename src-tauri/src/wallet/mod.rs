@@ -20,9 +20,11 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+pub mod payments_scheduler;
 pub mod spend_wallet;
 pub mod transaction_service;
 pub mod wallet_adapter;
+pub mod wallet_backup;
 pub mod wallet_manager;
 pub mod wallet_status_monitor;
 pub mod wallet_types;
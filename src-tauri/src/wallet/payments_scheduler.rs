@@ -0,0 +1,415 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Datelike, Days, Local, NaiveTime, TimeZone};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use tari_core::transactions::tari_amount::MicroMinotari;
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    configs::{
+        config_wallet::{ConfigWallet, ConfigWalletContent},
+        trait_config::ConfigImpl,
+    },
+    events_emitter::EventsEmitter,
+    pin::PinManager,
+    tasks_tracker::TasksTrackers,
+    UniverseAppState,
+};
+
+static LOG_TARGET: &str = "tari::universe::payments_scheduler";
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+// Left on top of the reserve so a sweep can cover the transaction fee
+const SWEEP_FEE_MARGIN: MicroMinotari = MicroMinotari(10_000);
+// Minimum time between two sweeps, so a failing sweep is not retried on every check
+const SWEEP_COOLDOWN: Duration = Duration::from_secs(30 * 60);
+pub const MAX_PAYMENTS_HISTORY_LENGTH: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PaymentSchedule {
+    /// Every day at the given local hour
+    Daily { hour: u32 },
+    /// Every week on the given day (0 = Monday .. 6 = Sunday) at the given local hour
+    Weekly { weekday: u32, hour: u32 },
+}
+
+impl PaymentSchedule {
+    pub fn validate(&self) -> Result<(), String> {
+        let (weekday, hour) = match self {
+            PaymentSchedule::Daily { hour } => (0, *hour),
+            PaymentSchedule::Weekly { weekday, hour } => (*weekday, *hour),
+        };
+        if weekday > 6 {
+            return Err("Weekday must be between 0 (Monday) and 6 (Sunday)".to_string());
+        }
+        if hour > 23 {
+            return Err("Hour must be between 0 and 23".to_string());
+        }
+        Ok(())
+    }
+
+    /// Returns the most recent scheduled occurrence at or before `now`
+    pub fn last_occurrence<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let (days_back, hour) = match self {
+            PaymentSchedule::Daily { hour } => (0, *hour),
+            PaymentSchedule::Weekly { weekday, hour } => {
+                let today = now.weekday().num_days_from_monday();
+                ((today + 7 - weekday % 7) % 7, *hour)
+            }
+        };
+        let period = match self {
+            PaymentSchedule::Daily { .. } => Days::new(1),
+            PaymentSchedule::Weekly { .. } => Days::new(7),
+        };
+
+        let date = now
+            .date_naive()
+            .checked_sub_days(Days::new(u64::from(days_back)))?;
+        let candidate = date
+            .and_time(NaiveTime::from_hms_opt(hour, 0, 0)?)
+            .and_local_timezone(now.timezone())
+            .earliest()?;
+        if candidate > *now {
+            let previous_date = date.checked_sub_days(period)?;
+            previous_date
+                .and_time(NaiveTime::from_hms_opt(hour, 0, 0)?)
+                .and_local_timezone(now.timezone())
+                .earliest()
+        } else {
+            Some(candidate)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScheduledPaymentKind {
+    /// Sends everything above the reserve once the available balance reaches `threshold`
+    Sweep { threshold: MicroMinotari },
+    /// Sends a fixed `amount` on every occurrence of `schedule`
+    Recurring {
+        amount: MicroMinotari,
+        schedule: PaymentSchedule,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledPayment {
+    pub id: String,
+    pub name: String,
+    pub destination: String,
+    pub payment_id: Option<String>,
+    pub kind: ScheduledPaymentKind,
+    pub enabled: bool,
+    pub created_at: SystemTime,
+    pub last_run_at: Option<SystemTime>,
+}
+
+impl ScheduledPayment {
+    fn is_schedule_due(&self, now: SystemTime) -> bool {
+        match &self.kind {
+            ScheduledPaymentKind::Sweep { .. } => match self.last_run_at {
+                Some(last_run_at) => now
+                    .duration_since(last_run_at)
+                    .map(|elapsed| elapsed >= SWEEP_COOLDOWN)
+                    .unwrap_or(false),
+                None => true,
+            },
+            ScheduledPaymentKind::Recurring { schedule, .. } => {
+                let now_local: DateTime<Local> = now.into();
+                let Some(occurrence) = schedule.last_occurrence(&now_local) else {
+                    return false;
+                };
+                let since: DateTime<Local> = self.last_run_at.unwrap_or(self.created_at).into();
+                occurrence > since
+            }
+        }
+    }
+
+    /// Amount which should be sent right now, `None` when nothing is due
+    pub fn due_amount(
+        &self,
+        now: SystemTime,
+        available_balance: MicroMinotari,
+        min_reserve: MicroMinotari,
+    ) -> Option<MicroMinotari> {
+        if !self.enabled || !self.is_schedule_due(now) {
+            return None;
+        }
+        match &self.kind {
+            ScheduledPaymentKind::Sweep { threshold } => {
+                if available_balance < *threshold {
+                    return None;
+                }
+                let kept = min_reserve + SWEEP_FEE_MARGIN;
+                if available_balance <= kept {
+                    return None;
+                }
+                Some(available_balance - kept)
+            }
+            ScheduledPaymentKind::Recurring { amount, .. } => Some(*amount),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScheduledPaymentStatus {
+    Sent,
+    Skipped,
+    Failed,
+    /// Not sent because the wallet is PIN locked, the user has to send it themselves
+    PendingPin,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledPaymentRecord {
+    pub scheduled_payment_id: String,
+    pub executed_at: SystemTime,
+    pub destination: String,
+    pub amount: MicroMinotari,
+    pub status: ScheduledPaymentStatus,
+    pub message: Option<String>,
+}
+
+pub struct PaymentsScheduler {}
+
+impl PaymentsScheduler {
+    /// Spawns the scheduler loop on the wallet phase task tracker, so it follows the wallet lifecycle
+    pub async fn spawn(app_handle: AppHandle) {
+        let mut shutdown_signal = TasksTrackers::current().wallet_phase.get_signal().await;
+        TasksTrackers::current()
+            .wallet_phase
+            .get_task_tracker()
+            .await
+            .spawn(async move {
+                let mut interval = tokio::time::interval(CHECK_INTERVAL);
+                loop {
+                    tokio::select! {
+                        _ = interval.tick() => {
+                            PaymentsScheduler::process_due_payments(&app_handle).await;
+                        }
+                        _ = shutdown_signal.wait() => {
+                            info!(target: LOG_TARGET, "Shutdown signal received, stopping payments scheduler");
+                            break;
+                        }
+                    }
+                }
+            });
+    }
+
+    async fn process_due_payments(app_handle: &AppHandle) {
+        let wallet_config = ConfigWallet::content().await;
+        if !wallet_config
+            .scheduled_payments()
+            .iter()
+            .any(|payment| payment.enabled)
+        {
+            return;
+        }
+
+        let state = app_handle.state::<UniverseAppState>();
+        if !state.wallet_manager.is_initial_scan_completed() {
+            return;
+        }
+
+        for payment in wallet_config.scheduled_payments() {
+            // Balance is refreshed for every payment since a previous one could have spent from it
+            let available_balance = match state.wallet_manager.get_balance().await {
+                Ok(balance) => balance.available_balance,
+                Err(e) => {
+                    warn!(target: LOG_TARGET, "Could not get wallet balance for scheduled payments: {e}");
+                    return;
+                }
+            };
+            let min_reserve = *wallet_config.payments_min_reserve();
+            let now = SystemTime::now();
+            let Some(amount) = payment.due_amount(now, available_balance, min_reserve) else {
+                continue;
+            };
+
+            let record = if PinManager::pin_locked().await {
+                // Never prompt for the PIN from the background, the user didn't start this payment
+                ScheduledPaymentRecord {
+                    scheduled_payment_id: payment.id.clone(),
+                    executed_at: now,
+                    destination: payment.destination.clone(),
+                    amount,
+                    status: ScheduledPaymentStatus::PendingPin,
+                    message: Some(
+                        "Wallet is locked with a PIN, send this payment manually".to_string(),
+                    ),
+                }
+            } else if available_balance < amount + min_reserve {
+                ScheduledPaymentRecord {
+                    scheduled_payment_id: payment.id.clone(),
+                    executed_at: now,
+                    destination: payment.destination.clone(),
+                    amount,
+                    status: ScheduledPaymentStatus::Skipped,
+                    message: Some(format!(
+                        "Available balance {available_balance} would drop below the reserve of {min_reserve}"
+                    )),
+                }
+            } else {
+                PaymentsScheduler::send(app_handle, payment, amount, now).await
+            };
+
+            info!(target: LOG_TARGET, "Scheduled payment {} finished with {:?}", payment.id, record.status);
+            if let Err(e) = ConfigWallet::update_field(
+                ConfigWalletContent::record_scheduled_payment,
+                record.clone(),
+            )
+            .await
+            {
+                warn!(target: LOG_TARGET, "Could not store scheduled payment result: {e}");
+            }
+            if record.status == ScheduledPaymentStatus::PendingPin {
+                EventsEmitter::emit_scheduled_payment_pending_pin(record).await;
+            } else {
+                EventsEmitter::emit_scheduled_payment_executed(record).await;
+            }
+        }
+    }
+
+    async fn send(
+        app_handle: &AppHandle,
+        payment: &ScheduledPayment,
+        amount: MicroMinotari,
+        now: SystemTime,
+    ) -> ScheduledPaymentRecord {
+        let state = app_handle.state::<UniverseAppState>();
+        let amount_str = format!(
+            "{}.{:06}",
+            amount.as_u64() / 1_000_000,
+            amount.as_u64() % 1_000_000
+        );
+        let result = state
+            .wallet_manager
+            .send_one_sided_to_stealth_address(
+                amount_str,
+                payment.destination.clone(),
+                payment.payment_id.clone(),
+                app_handle,
+            )
+            .await
+            .map_err(|e| e.to_string());
+
+        let (status, message) = match result {
+            Ok(()) => (ScheduledPaymentStatus::Sent, None),
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Scheduled payment {} failed: {e}", payment.id);
+                (ScheduledPaymentStatus::Failed, Some(e))
+            }
+        };
+        ScheduledPaymentRecord {
+            scheduled_payment_id: payment.id.clone(),
+            executed_at: now,
+            destination: payment.destination.clone(),
+            amount,
+            status,
+            message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use chrono::Utc;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn daily_occurrence_is_today_after_the_hour() {
+        let schedule = PaymentSchedule::Daily { hour: 8 };
+        // 2025-01-10 is a Friday
+        let occurrence = schedule.last_occurrence(&utc(2025, 1, 10, 9)).unwrap();
+        assert_eq!(
+            occurrence,
+            Utc.with_ymd_and_hms(2025, 1, 10, 8, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn daily_occurrence_is_yesterday_before_the_hour() {
+        let schedule = PaymentSchedule::Daily { hour: 8 };
+        let occurrence = schedule.last_occurrence(&utc(2025, 1, 10, 7)).unwrap();
+        assert_eq!(
+            occurrence,
+            Utc.with_ymd_and_hms(2025, 1, 9, 8, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn weekly_occurrence_points_to_last_friday() {
+        let schedule = PaymentSchedule::Weekly {
+            weekday: 4,
+            hour: 12,
+        };
+        // Monday after the Friday
+        let occurrence = schedule.last_occurrence(&utc(2025, 1, 13, 10)).unwrap();
+        assert_eq!(
+            occurrence,
+            Utc.with_ymd_and_hms(2025, 1, 10, 12, 0, 0).unwrap()
+        );
+        // Friday morning, before the hour, goes back a full week
+        let occurrence = schedule.last_occurrence(&utc(2025, 1, 10, 10)).unwrap();
+        assert_eq!(
+            occurrence,
+            Utc.with_ymd_and_hms(2025, 1, 3, 12, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn sweep_keeps_reserve_and_fee_margin() {
+        let payment = ScheduledPayment {
+            id: "sweep".to_string(),
+            name: "Cold storage".to_string(),
+            destination: "".to_string(),
+            payment_id: None,
+            kind: ScheduledPaymentKind::Sweep {
+                threshold: MicroMinotari(5_000_000),
+            },
+            enabled: true,
+            created_at: SystemTime::now(),
+            last_run_at: None,
+        };
+        let reserve = MicroMinotari(1_000_000);
+
+        assert_eq!(
+            payment.due_amount(SystemTime::now(), MicroMinotari(4_000_000), reserve),
+            None
+        );
+        assert_eq!(
+            payment.due_amount(SystemTime::now(), MicroMinotari(6_000_000), reserve),
+            Some(MicroMinotari(6_000_000) - reserve - SWEEP_FEE_MARGIN)
+        );
+    }
+}
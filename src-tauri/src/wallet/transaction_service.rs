@@ -35,6 +35,7 @@ use tari_common::configuration::Network;
 use tauri::Manager;
 
 const LOG_TARGET: &str = "tari::universe::transaction_service";
+pub const FEE_PER_GRAM: u64 = 1; // TODO: Implement fee calculation logic
 
 /// This struct encapsulates all functionality related to transactions
 pub struct TransactionService<'a> {
@@ -74,7 +75,7 @@ impl<'a> TransactionService<'a> {
                 u256: vec![],
                 user_bytes: vec![],
             }),
-            fee_per_gram: 1, // TODO: Implement fee calculation logic
+            fee_per_gram: FEE_PER_GRAM,
             payment_type: PaymentType::OneSidedToStealthAddress.into(),
         };
 
@@ -34,7 +34,9 @@ use crate::wallet::wallet_types::{
 use anyhow::Error;
 use log::{info, warn};
 use minotari_node_grpc_client::grpc::wallet_client::WalletClient;
-use minotari_node_grpc_client::grpc::{GetAllCompletedTransactionsRequest, GetBalanceRequest};
+use minotari_node_grpc_client::grpc::{
+    Empty, GetAllCompletedTransactionsRequest, GetBalanceRequest,
+};
 use std::path::PathBuf;
 use std::time::Duration;
 use tari_common::configuration::Network;
@@ -98,6 +100,25 @@ impl WalletAdapter {
         Ok(WalletBalance::from_response(balance))
     }
 
+    pub async fn get_unspent_amounts(
+        &self,
+    ) -> Result<Vec<MicroMinotari>, WalletStatusMonitorError> {
+        let mut client = WalletClient::connect(self.wallet_grpc_address())
+            .await
+            .map_err(|_e| WalletStatusMonitorError::WalletNotStarted)?;
+        let res = client
+            .get_unspent_amounts(Empty {})
+            .await
+            .map_err(|e| WalletStatusMonitorError::UnknownError(e.into()))?;
+
+        Ok(res
+            .into_inner()
+            .amount
+            .into_iter()
+            .map(MicroMinotari)
+            .collect())
+    }
+
     pub async fn get_transactions(
         &self,
         offset: Option<u32>,
@@ -0,0 +1,351 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::anyhow;
+use base64::prelude::*;
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::Network;
+use tari_common_types::tari_address::TariAddress;
+use tari_key_manager::cipher_seed::CipherSeed;
+use tari_utilities::SafePassword;
+use tauri::AppHandle;
+use tokio::fs;
+
+use crate::configs::config_wallet::{ConfigWallet, ConfigWalletContent, WalletId};
+use crate::configs::trait_config::ConfigImpl;
+use crate::events_emitter::EventsEmitter;
+use crate::internal_wallet::{InternalWallet, TariWalletDetails};
+use crate::pin::PinManager;
+use crate::utils::cryptography;
+
+static LOG_TARGET: &str = "tari::universe::wallet_backup";
+
+const BACKUP_FORMAT: &str = "tari-universe-wallet-backup";
+pub const WALLET_BACKUP_VERSION: u32 = 2;
+const MIN_PASSPHRASE_LENGTH: usize = 8;
+
+/// Plain part of the backup file, the payload is encrypted with the user passphrase
+#[derive(Debug, Serialize, Deserialize)]
+struct WalletBackupFile {
+    format: String,
+    version: u32,
+    network: String,
+    created_at: u64,
+    salt: String,
+    payload: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BackupTariWallet {
+    id: WalletId,
+    name: String,
+    seed: Vec<u8>,
+}
+
+/// Only the wallet keys are backed up, the rest of the wallet config stays local to a machine
+#[derive(Serialize, Deserialize)]
+pub struct WalletBackupPayload {
+    /// Selected wallet first
+    tari_wallets: Vec<BackupTariWallet>,
+    monero_seed: Option<Vec<u8>>,
+    /// External Monero address, used when no Monero seed is owned
+    monero_address: String,
+}
+
+/// Wallet selection before a restore, used to undo a restore that failed halfway
+struct PreviousWalletState {
+    selected_wallet_id: Option<WalletId>,
+    selected_external_tari_address: Option<TariAddress>,
+    was_watch_only: bool,
+    monero_seed: Option<[u8; 32]>,
+    monero_address: String,
+}
+
+impl PreviousWalletState {
+    async fn capture(pin_password: Option<SafePassword>) -> Result<Self, anyhow::Error> {
+        let wallet_config = ConfigWallet::content().await;
+        let monero_seed = if *wallet_config.monero_address_is_generated() {
+            let monero_seed_bytes = InternalWallet::get_monero_seed(pin_password)
+                .await?
+                .inner()
+                .to_vec();
+            Some(parse_monero_seed(&monero_seed_bytes)?)
+        } else {
+            None
+        };
+        Ok(Self {
+            selected_wallet_id: wallet_config.tari_wallets().first().cloned(),
+            selected_external_tari_address: wallet_config.selected_external_tari_address().clone(),
+            was_watch_only: InternalWallet::is_watch_only().await,
+            monero_seed,
+            monero_address: wallet_config.monero_address().clone(),
+        })
+    }
+
+    async fn rollback(
+        self,
+        app_handle: &AppHandle,
+        added_wallet_ids: Vec<WalletId>,
+        pin_password: Option<SafePassword>,
+    ) -> Result<(), anyhow::Error> {
+        for wallet_id in added_wallet_ids {
+            InternalWallet::discard_tari_wallet(wallet_id).await?;
+        }
+        match self.monero_seed {
+            Some(monero_seed) => {
+                InternalWallet::restore_monero_wallet(app_handle, monero_seed, pin_password)
+                    .await?;
+            }
+            None => InternalWallet::set_external_monero_address(self.monero_address).await?,
+        }
+
+        if self.was_watch_only {
+            InternalWallet::initialize_watch_only(app_handle, None).await
+        } else if let Some(address) = self.selected_external_tari_address {
+            InternalWallet::initialize_seedless(app_handle, Some(address)).await
+        } else if let Some(wallet_id) = self.selected_wallet_id {
+            InternalWallet::switch_tari_wallet(app_handle, wallet_id).await
+        } else {
+            Ok(())
+        }
+    }
+}
+
+pub struct WalletBackup;
+
+impl WalletBackup {
+    pub async fn export(
+        app_handle: &AppHandle,
+        path: &Path,
+        passphrase: SafePassword,
+    ) -> Result<(), anyhow::Error> {
+        if !InternalWallet::is_internal().await {
+            return Err(anyhow!("Only internal wallets can be backed up"));
+        }
+        validate_passphrase(&passphrase)?;
+        let pin_password = PinManager::get_validated_pin_if_defined(app_handle).await?;
+        let wallet_config = ConfigWallet::content().await;
+
+        let mut tari_wallets = Vec::new();
+        for wallet_id in wallet_config.tari_wallets() {
+            let record = wallet_config
+                .tari_wallet_records()
+                .iter()
+                .find(|r| r.details.id == *wallet_id);
+            if record.is_some_and(|r| r.is_locked) {
+                // The seed is enciphered with a forgotten PIN and can't be read
+                warn!(target: LOG_TARGET, "Skipping locked Tari wallet {wallet_id:?} in backup");
+                continue;
+            }
+            let seed =
+                InternalWallet::get_tari_seed_for_wallet(wallet_id.clone(), pin_password.clone())
+                    .await?;
+            tari_wallets.push(BackupTariWallet {
+                id: wallet_id.clone(),
+                name: record.map(|r| r.name.clone()).unwrap_or_default(),
+                seed: seed.to_binary().map_err(|e| anyhow!(e.to_string()))?,
+            });
+        }
+
+        let monero_seed = if *wallet_config.monero_address_is_generated() {
+            let monero_seed = InternalWallet::get_monero_seed(pin_password).await?;
+            Some(monero_seed.inner().to_vec())
+        } else {
+            None
+        };
+
+        let payload = WalletBackupPayload {
+            tari_wallets,
+            monero_seed,
+            monero_address: wallet_config.monero_address().clone(),
+        };
+
+        let salt = cryptography::generate_salt()?;
+        let encrypted_payload =
+            cryptography::encrypt_with_salt(&serde_json::to_vec(&payload)?, &passphrase, &salt)?;
+        let backup_file = WalletBackupFile {
+            format: BACKUP_FORMAT.to_string(),
+            version: WALLET_BACKUP_VERSION,
+            network: Network::get_current_or_user_setting_or_default().to_string(),
+            created_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            salt: hex::encode(salt),
+            payload: BASE64_STANDARD.encode(encrypted_payload),
+        };
+
+        fs::write(path, serde_json::to_vec_pretty(&backup_file)?).await?;
+        info!(target: LOG_TARGET, "Wallet backup exported to {path:?}");
+        Ok(())
+    }
+
+    pub async fn read(
+        path: &Path,
+        passphrase: SafePassword,
+    ) -> Result<WalletBackupPayload, anyhow::Error> {
+        let backup_file: WalletBackupFile = serde_json::from_slice(&fs::read(path).await?)
+            .map_err(|_| anyhow!("Not a wallet backup file"))?;
+        if backup_file.format != BACKUP_FORMAT {
+            return Err(anyhow!("Not a wallet backup file"));
+        }
+        if backup_file.version > WALLET_BACKUP_VERSION {
+            return Err(anyhow!(
+                "Backup version {} is not supported, please update the app",
+                backup_file.version
+            ));
+        }
+        let network = Network::get_current_or_user_setting_or_default().to_string();
+        if backup_file.network != network {
+            return Err(anyhow!(
+                "Backup was created on {} but the app runs on {}",
+                backup_file.network,
+                network
+            ));
+        }
+
+        let salt = hex::decode(&backup_file.salt)?;
+        let encrypted_payload = BASE64_STANDARD.decode(&backup_file.payload)?;
+        let payload = cryptography::decrypt_with_salt(&encrypted_payload, &passphrase, &salt)
+            .map_err(|_| anyhow!("Wrong backup passphrase"))?;
+        Ok(serde_json::from_slice(&payload)?)
+    }
+
+    /// Adds the backed up wallets next to the existing ones and selects the wallet selected at export time.
+    /// The whole backup is validated before anything is written, and the changes made so far are undone
+    /// when a later step fails.
+    pub async fn restore(
+        app_handle: &AppHandle,
+        payload: WalletBackupPayload,
+    ) -> Result<(), anyhow::Error> {
+        let pin_password = PinManager::get_validated_pin_if_defined(app_handle).await?;
+
+        // Wallets are added in reverse, so the one selected at export time ends up selected
+        let mut staged_wallets = Vec::new();
+        for wallet in payload.tari_wallets.iter().rev() {
+            let seed = CipherSeed::from_binary(&wallet.seed)
+                .map_err(|_| anyhow!("Could not parse Tari Seed from backup"))?;
+            let details =
+                InternalWallet::get_tari_wallet_details(wallet.id.clone(), seed.clone()).await?;
+            staged_wallets.push((wallet.name.clone(), seed, details));
+        }
+        if staged_wallets.is_empty() {
+            return Err(anyhow!("Backup does not contain any Tari wallet"));
+        }
+        let monero_seed = payload
+            .monero_seed
+            .as_deref()
+            .map(parse_monero_seed)
+            .transpose()?;
+        let previous_state = PreviousWalletState::capture(pin_password.clone()).await?;
+
+        let mut added_wallet_ids = Vec::new();
+        let result = WalletBackup::commit_restore(
+            app_handle,
+            staged_wallets,
+            monero_seed,
+            payload.monero_address,
+            pin_password.clone(),
+            &mut added_wallet_ids,
+        )
+        .await;
+        if let Err(e) = result {
+            warn!(target: LOG_TARGET, "Wallet backup restore failed, rolling back: {e}");
+            if let Err(rollback_error) = previous_state
+                .rollback(app_handle, added_wallet_ids, pin_password)
+                .await
+            {
+                error!(target: LOG_TARGET, "Failed to roll back wallet backup restore: {rollback_error}");
+            }
+            return Err(e);
+        }
+
+        EventsEmitter::emit_wallet_config_loaded(&ConfigWallet::content().await).await;
+        Ok(())
+    }
+
+    async fn commit_restore(
+        app_handle: &AppHandle,
+        staged_wallets: Vec<(String, CipherSeed, TariWalletDetails)>,
+        monero_seed: Option<[u8; 32]>,
+        monero_address: String,
+        pin_password: Option<SafePassword>,
+        added_wallet_ids: &mut Vec<WalletId>,
+    ) -> Result<(), anyhow::Error> {
+        let mut selected_wallet_id = None;
+        for (name, seed, details) in staged_wallets {
+            let existing_record = ConfigWallet::content()
+                .await
+                .tari_wallet_records()
+                .iter()
+                .find(|r| r.details.spend_public_key_hex == details.spend_public_key_hex)
+                .cloned();
+            let wallet_id = match &existing_record {
+                Some(record) if !record.is_locked => record.details.id.clone(),
+                _ => {
+                    let (added_details, _seed_binary) =
+                        InternalWallet::add_tari_wallet(app_handle, seed, pin_password.clone())
+                            .await?;
+                    if existing_record.is_none() {
+                        added_wallet_ids.push(added_details.id.clone());
+                        if !name.is_empty() {
+                            ConfigWallet::update_field(
+                                ConfigWalletContent::set_tari_wallet_name,
+                                (added_details.id.clone(), name),
+                            )
+                            .await?;
+                        }
+                    }
+                    added_details.id
+                }
+            };
+            selected_wallet_id = Some(wallet_id);
+        }
+        info!(target: LOG_TARGET, "Restored {} Tari wallet(s) from backup", added_wallet_ids.len());
+
+        if let Some(monero_seed) = monero_seed {
+            InternalWallet::restore_monero_wallet(app_handle, monero_seed, pin_password).await?;
+        } else if !monero_address.is_empty() {
+            InternalWallet::set_external_monero_address(monero_address).await?;
+        }
+
+        let selected_wallet_id =
+            selected_wallet_id.ok_or_else(|| anyhow!("Backup does not contain any Tari wallet"))?;
+        InternalWallet::switch_tari_wallet(app_handle, selected_wallet_id).await
+    }
+}
+
+fn parse_monero_seed(monero_seed: &[u8]) -> Result<[u8; 32], anyhow::Error> {
+    monero_seed
+        .try_into()
+        .map_err(|_| anyhow!("Monero seed is not 32 bytes"))
+}
+
+fn validate_passphrase(passphrase: &SafePassword) -> Result<(), anyhow::Error> {
+    if passphrase.reveal().len() < MIN_PASSPHRASE_LENGTH {
+        return Err(anyhow!(
+            "Backup passphrase must be at least {MIN_PASSPHRASE_LENGTH} characters long"
+        ));
+    }
+    Ok(())
+}
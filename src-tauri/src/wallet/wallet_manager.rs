@@ -28,25 +28,34 @@ use crate::node::node_manager::{NodeManager, NodeManagerError};
 use crate::process_stats_collector::ProcessStatsCollectorBuilder;
 use crate::process_watcher::ProcessWatcher;
 use crate::tasks_tracker::TasksTrackers;
+use crate::wallet::transaction_service::FEE_PER_GRAM;
 use crate::wallet::wallet_adapter::WalletAdapter;
 use crate::wallet::wallet_status_monitor::WalletStatusMonitorError;
-use crate::wallet::wallet_types::{TransactionInfo, TransactionStatus, WalletBalance, WalletState};
+use crate::wallet::wallet_types::{
+    SelfSweepPreview, TransactionInfo, TransactionStatus, UnspentOutput, WalletBalance, WalletState,
+};
 use crate::BaseNodeStatus;
 use futures_util::future::FusedFuture;
 use log::info;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::Duration;
 use tari_common::configuration::Network;
+use tari_core::consensus::ConsensusConstants;
+use tari_core::transactions::fee::Fee;
 use tari_core::transactions::tari_amount::{MicroMinotari, Minotari};
+use tari_core::transactions::weight::TransactionWeight;
 use tari_shutdown::ShutdownSignal;
 use tokio::fs;
 use tokio::sync::watch;
 use tokio::sync::RwLock;
 
 static LOG_TARGET: &str = "tari::universe::wallet_manager";
+/// Rough size of the features and script of a one-sided stealth output, used for fee estimates
+const OUTPUT_FEATURES_AND_SCRIPT_SIZE: usize = 64;
 
 #[derive(Debug, Clone)]
 pub struct WalletStartupConfig {
@@ -61,6 +70,8 @@ pub struct WalletStartupConfig {
 pub enum WalletManagerError {
     #[error("Wallet not started")]
     WalletNotStarted,
+    #[error("Watch-only wallet can't send transactions")]
+    WatchOnlyWallet,
     #[error("Node manager error: {0}")]
     NodeManagerError(#[from] NodeManagerError),
     #[error("Unknown error: {0}")]
@@ -189,6 +200,66 @@ impl WalletManager {
         Ok(())
     }
 
+    /// Parks the database of the previous wallet under `wallet_data/<key>` and brings back the database of
+    /// the next wallet if it was used before, so switching between wallets doesn't force a full rescan.
+    /// A wallet without a key (seedless) has nothing worth keeping and its database is removed.
+    pub async fn swap_data_folder(
+        &self,
+        base_path: &Path,
+        previous_wallet_key: Option<&str>,
+        next_wallet_key: Option<&str>,
+    ) -> Result<(), anyhow::Error> {
+        if previous_wallet_key.is_some() && previous_wallet_key == next_wallet_key {
+            return Ok(());
+        }
+        self.initial_scan_completed
+            .store(false, std::sync::atomic::Ordering::Relaxed);
+
+        let network = Network::get_current().to_string().to_lowercase();
+        let path_to_network_wallet = base_path.join("wallet").join(&network);
+        let stashed_path = |key: &str| base_path.join("wallet_data").join(key).join(&network);
+
+        if path_to_network_wallet.try_exists()? && path_to_network_wallet.is_dir() {
+            match previous_wallet_key {
+                Some(key) => {
+                    let stash = stashed_path(key);
+                    if stash.try_exists()? {
+                        fs::remove_dir_all(&stash).await?;
+                    }
+                    if let Some(parent) = stash.parent() {
+                        fs::create_dir_all(parent).await?;
+                    }
+                    fs::rename(&path_to_network_wallet, &stash).await?;
+                    log::info!(target: LOG_TARGET, "Parked wallet data folder at {stash:?}");
+                }
+                None => fs::remove_dir_all(&path_to_network_wallet).await?,
+            }
+        }
+
+        if let Some(key) = next_wallet_key {
+            let stash = stashed_path(key);
+            if stash.try_exists()? {
+                fs::create_dir_all(base_path.join("wallet")).await?;
+                fs::rename(&stash, &path_to_network_wallet).await?;
+                log::info!(target: LOG_TARGET, "Restored wallet data folder from {stash:?}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the parked database of a wallet that is no longer owned
+    pub async fn remove_parked_data_folder(
+        &self,
+        base_path: &Path,
+        wallet_key: &str,
+    ) -> Result<(), anyhow::Error> {
+        let path = base_path.join("wallet_data").join(wallet_key);
+        if path.try_exists()? {
+            fs::remove_dir_all(path).await?;
+        }
+        Ok(())
+    }
+
     pub async fn get_balance(&self) -> Result<WalletBalance, anyhow::Error> {
         let process_watcher = self.watcher.read().await;
         process_watcher.adapter.get_balance().await
@@ -241,6 +312,9 @@ impl WalletManager {
         payment_id: Option<String>,
         app_handle: &tauri::AppHandle,
     ) -> Result<(), WalletManagerError> {
+        if InternalWallet::is_watch_only().await {
+            return Err(WalletManagerError::WatchOnlyWallet);
+        }
         let process_watcher = self.watcher.read().await;
         if !process_watcher.is_running() {
             return Err(WalletManagerError::WalletNotStarted);
@@ -288,6 +362,102 @@ impl WalletManager {
         Ok(matching_tx)
     }
 
+    /// Lists the wallet's unspent outputs. The wallet grpc only reports the output values (no features or
+    /// maturity), so mined height and maturity are resolved by matching each value against the received
+    /// transactions. Outputs that can't be matched are reported as not mature.
+    pub async fn get_unspent_outputs(&self) -> Result<Vec<UnspentOutput>, WalletManagerError> {
+        let current_block_height = self.base_node_watch_rx.borrow().block_height;
+        let amounts = {
+            let process_watcher = self.watcher.read().await;
+            if !process_watcher.is_running() {
+                return Err(WalletManagerError::WalletNotStarted);
+            }
+            process_watcher
+                .adapter
+                .get_unspent_amounts()
+                .await
+                .map_err(|e| match e {
+                    WalletStatusMonitorError::WalletNotStarted => {
+                        WalletManagerError::WalletNotStarted
+                    }
+                    _ => WalletManagerError::UnknownError(e.into()),
+                })?
+        };
+        let transactions = self.get_transactions(None, None, None).await?;
+
+        let network = Network::get_current();
+        Ok(match_unspent_outputs(
+            amounts,
+            &transactions,
+            current_block_height,
+            |mined_height| coinbase_maturity(network, mined_height),
+        ))
+    }
+
+    pub async fn preview_sweep_to_self(&self) -> Result<SelfSweepPreview, WalletManagerError> {
+        let outputs = self.get_unspent_outputs().await?;
+        let balance = self.get_balance().await?;
+
+        let mature_outputs: Vec<&UnspentOutput> = outputs.iter().filter(|o| o.is_mature).collect();
+        let mature_output_count = mature_outputs.len();
+        let mature_total: u64 = mature_outputs.iter().map(|o| o.value.as_u64()).sum();
+        // Outputs locked by pending transactions are not spendable, so never exceed the available balance
+        let total_amount = MicroMinotari(mature_total.min(balance.available_balance.as_u64()));
+        let estimated_fee = estimate_sweep_fee(mature_output_count);
+
+        Ok(SelfSweepPreview {
+            mature_output_count,
+            total_amount,
+            estimated_fee,
+            resulting_amount: MicroMinotari(
+                total_amount.as_u64().saturating_sub(estimated_fee.as_u64()),
+            ),
+        })
+    }
+
+    /// Sends the spendable balance, less the estimated fee, to our own address. The wallet gRPC
+    /// has no way to pick the inputs, so the wallet's own input selection decides what is spent.
+    pub async fn sweep_to_self(
+        &self,
+        app_handle: &tauri::AppHandle,
+    ) -> Result<SelfSweepPreview, WalletManagerError> {
+        if InternalWallet::is_watch_only().await {
+            return Err(WalletManagerError::WatchOnlyWallet);
+        }
+        let preview = self.preview_sweep_to_self().await?;
+        if preview.mature_output_count < 2 {
+            return Err(WalletManagerError::UnknownError(anyhow::anyhow!(
+                "At least two mature outputs are required to sweep"
+            )));
+        }
+        if preview.resulting_amount.as_u64() == 0 {
+            return Err(WalletManagerError::UnknownError(anyhow::anyhow!(
+                "Spendable balance does not cover the sweep fee"
+            )));
+        }
+
+        let process_watcher = self.watcher.read().await;
+        if !process_watcher.is_running() {
+            return Err(WalletManagerError::WalletNotStarted);
+        }
+        self.node_manager.wait_ready().await?;
+
+        let destination = InternalWallet::tari_address().await.to_base58();
+        info!(target: LOG_TARGET, "Sweeping {} to our own address", preview.resulting_amount);
+        process_watcher
+            .adapter
+            .send_one_sided_to_stealth_address(
+                preview.resulting_amount.as_u64(),
+                destination,
+                None,
+                app_handle,
+            )
+            .await
+            .map_err(WalletManagerError::UnknownError)?;
+
+        Ok(preview)
+    }
+
     #[allow(clippy::too_many_lines)]
     pub async fn wait_for_initial_wallet_scan(
         &self,
@@ -466,3 +636,149 @@ impl WalletManager {
         lock.is_pid_file_exists(base_path)
     }
 }
+
+/// Coinbase lock height in force at `mined_height` on the given network
+fn coinbase_maturity(network: Network, mined_height: u64) -> u64 {
+    ConsensusConstants::for_network(network)
+        .iter()
+        .rev()
+        .find(|constants| constants.effective_from_height() <= mined_height)
+        .map_or(0, ConsensusConstants::coinbase_min_maturity)
+}
+
+fn estimate_sweep_fee(input_count: usize) -> MicroMinotari {
+    let weight = TransactionWeight::latest();
+    // Account for a change output as well, the wallet includes one when estimating the fee
+    let num_outputs = 2;
+    let features_and_scripts_size =
+        weight.round_up_features_and_scripts_size(OUTPUT_FEATURES_AND_SCRIPT_SIZE * num_outputs);
+    Fee::new(weight).calculate(
+        MicroMinotari(FEE_PER_GRAM),
+        1,
+        input_count,
+        num_outputs,
+        features_and_scripts_size,
+    )
+}
+
+fn match_unspent_outputs(
+    amounts: Vec<MicroMinotari>,
+    transactions: &[TransactionInfo],
+    current_block_height: u64,
+    coinbase_maturity: impl Fn(u64) -> u64,
+) -> Vec<UnspentOutput> {
+    // Received and mined transactions keyed by value, oldest first
+    let mut received: HashMap<u64, Vec<(u64, bool)>> = HashMap::new();
+    for tx in transactions
+        .iter()
+        .filter(|tx| tx.direction == 1 && !tx.is_cancelled && tx.mined_in_block_height > 0)
+    {
+        let is_coinbase = matches!(
+            tx.status,
+            TransactionStatus::Coinbase
+                | TransactionStatus::CoinbaseConfirmed
+                | TransactionStatus::CoinbaseUnconfirmed
+        );
+        received
+            .entry(tx.amount.as_u64())
+            .or_default()
+            .push((tx.mined_in_block_height, is_coinbase));
+    }
+    for candidates in received.values_mut() {
+        candidates.sort_by(|a, b| b.0.cmp(&a.0));
+    }
+
+    let mut outputs: Vec<UnspentOutput> = amounts
+        .into_iter()
+        .map(|value| {
+            let matched = received.get_mut(&value.as_u64()).and_then(|c| c.pop());
+            let (mined_height, is_coinbase) = match matched {
+                Some((height, is_coinbase)) => (Some(height), is_coinbase),
+                None => (None, false),
+            };
+            let maturity = mined_height.map(|height| {
+                if is_coinbase {
+                    height + coinbase_maturity(height)
+                } else {
+                    height
+                }
+            });
+            UnspentOutput {
+                value,
+                mined_height,
+                maturity,
+                is_coinbase,
+                // We can't tell whether an unmatched output is an immature coinbase, so don't offer it
+                is_mature: maturity.is_some_and(|maturity| maturity <= current_block_height),
+            }
+        })
+        .collect();
+    outputs.sort_by(|a, b| b.value.as_u64().cmp(&a.value.as_u64()));
+    outputs
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    const COINBASE_MATURITY: u64 = 720;
+
+    fn received_tx(amount: u64, height: u64, status: TransactionStatus) -> TransactionInfo {
+        TransactionInfo {
+            tx_id: height.to_string(),
+            source_address: String::new(),
+            dest_address: String::new(),
+            status,
+            amount: MicroMinotari(amount),
+            is_cancelled: false,
+            direction: 1,
+            excess_sig: Vec::new(),
+            fee: 0,
+            timestamp: 0,
+            payment_id: String::new(),
+            mined_in_block_height: height,
+            payment_reference: None,
+        }
+    }
+
+    #[test]
+    fn coinbase_outputs_mature_after_coinbase_maturity() {
+        let transactions = vec![
+            received_tx(500, 100, TransactionStatus::CoinbaseConfirmed),
+            received_tx(700, 1000, TransactionStatus::CoinbaseConfirmed),
+        ];
+        let outputs = match_unspent_outputs(
+            vec![MicroMinotari(500), MicroMinotari(700)],
+            &transactions,
+            1000,
+            |_| COINBASE_MATURITY,
+        );
+
+        let immature = outputs.iter().find(|o| o.value.as_u64() == 700).unwrap();
+        assert!(immature.is_coinbase);
+        assert_eq!(immature.maturity, Some(1000 + COINBASE_MATURITY));
+        assert!(!immature.is_mature);
+
+        let mature = outputs.iter().find(|o| o.value.as_u64() == 500).unwrap();
+        assert_eq!(mature.maturity, Some(100 + COINBASE_MATURITY));
+        assert!(mature.is_mature);
+    }
+
+    #[test]
+    fn unmatched_outputs_are_not_mature() {
+        let transactions = vec![received_tx(500, 100, TransactionStatus::MinedConfirmed)];
+        let outputs = match_unspent_outputs(
+            vec![MicroMinotari(500), MicroMinotari(500), MicroMinotari(900)],
+            &transactions,
+            10_000,
+            |_| COINBASE_MATURITY,
+        );
+
+        assert_eq!(outputs.iter().filter(|o| o.is_mature).count(), 1);
+        assert!(outputs
+            .iter()
+            .filter(|o| o.mined_height.is_none())
+            .all(|o| o.maturity.is_none() && !o.is_mature));
+    }
+}
@@ -187,3 +187,25 @@ impl From<i32> for TransactionStatus {
         }
     }
 }
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UnspentOutput {
+    pub value: MicroMinotari,
+    /// Height of the block the output was mined in, if it could be matched to a received transaction
+    pub mined_height: Option<u64>,
+    /// Height from which the output can be spent, unknown when the output could not be matched
+    pub maturity: Option<u64>,
+    pub is_coinbase: bool,
+    pub is_mature: bool,
+}
+
+/// Estimate for sending the spendable balance back to our own address. The wallet chooses the
+/// inputs, so which outputs end up merged is not controlled from here.
+#[derive(Debug, Serialize, Clone)]
+pub struct SelfSweepPreview {
+    /// Mature outputs the spendable balance is made of
+    pub mature_output_count: usize,
+    pub total_amount: MicroMinotari,
+    pub estimated_fee: MicroMinotari,
+    pub resulting_amount: MicroMinotari,
+}
@@ -27,6 +27,8 @@ pub(crate) struct Summary {
     pub(crate) connection: Connection,
 
     pub(crate) hashrate: Hashrate,
+    #[serde(default)]
+    pub(crate) results: Option<Results>,
     // hugepages: bool,
 }
 
@@ -36,10 +38,15 @@ pub struct Resources {}
 #[derive(Deserialize, Debug)]
 pub struct Memory {}
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone, Default)]
 pub struct Results {
     // Sometimes this is not present in v6.21.0
     // error_log: Vec<String>,
+    // In daemon (merge mining) mode every share is a block submitted to the proxy
+    #[serde(default)]
+    pub(crate) shares_good: u64,
+    #[serde(default)]
+    pub(crate) shares_total: u64,
 }
 
 #[derive(Deserialize, Debug, Clone)]
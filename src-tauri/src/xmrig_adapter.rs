@@ -22,12 +22,16 @@
 
 use anyhow::Error;
 use async_trait::async_trait;
-use log::warn;
-use std::path::PathBuf;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tari_shutdown::Shutdown;
 use tokio::sync::watch;
 
+use crate::cpu_miner_backend::{CpuMinerNodeConnection, CpuMinerSummary};
 use crate::port_allocator::PortAllocator;
 use crate::process_adapter::{
     HealthStatus, ProcessAdapter, ProcessInstance, ProcessStartupSpec, StatusMonitor,
@@ -37,88 +41,304 @@ use crate::xmrig::http_api::models::Summary;
 use crate::xmrig::http_api::XmrigHttpApiClient;
 
 const LOG_TARGET: &str = "tari::universe::xmrig_adapter";
+const DEFAULT_DONATE_LEVEL: u8 = 1;
+const MAX_DONATE_LEVEL: u8 = 99;
 
-pub enum XmrigNodeConnection {
-    LocalMmproxy {
-        host_name: String,
-        port: u16,
-        monero_address: String,
-    },
-    Pool {
-        host_name: String,
-        port: u16,
-        tari_address: String,
-    },
-    MergeMinedPool {
-        host_name: String,
-        port: u16,
-        monero_address: String,
-        tari_address: String,
-    },
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum RandomXMode {
+    #[default]
+    Auto,
+    Fast,
+    Light,
 }
 
-impl XmrigNodeConnection {
-    pub fn generate_args(&self) -> Vec<String> {
+impl Display for RandomXMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            XmrigNodeConnection::LocalMmproxy {
-                host_name,
-                port,
-                monero_address,
-            } => {
-                vec![
-                    "--daemon".to_string(),
-                    format!("--url={}:{}", host_name, port),
-                    // "--daemon-poll-interval=10000".to_string(),
-                    "--coin=monero".to_string(),
-                    // We are using a local daemon, so retry as soon as possible
-                    "--retry-pause=1".to_string(),
-                    "--user".to_string(),
-                    format!("{}", monero_address),
-                ]
-            }
-            XmrigNodeConnection::Pool {
-                host_name,
-                port,
-                tari_address: monero_address,
-            } => {
-                vec![
-                    "--url".to_string(),
-                    format!("{}:{}", host_name, port),
-                    "--coin=monero".to_string(),
-                    "--user".to_string(),
-                    format!("{}", monero_address),
-                ]
-            }
-            XmrigNodeConnection::MergeMinedPool {
-                host_name,
-                port,
-                monero_address,
-                tari_address,
-            } => {
-                vec![
-                    "--url".to_string(),
-                    format!("{}:{}", host_name, port),
-                    "--coin=monero".to_string(),
-                    "--user".to_string(),
-                    format!("{}:{}", monero_address, tari_address),
-                ]
-            }
+            RandomXMode::Auto => write!(f, "auto"),
+            RandomXMode::Fast => write!(f, "fast"),
+            RandomXMode::Light => write!(f, "light"),
+        }
+    }
+}
+
+/// Manual xmrig settings. Anything left unset keeps xmrig's own behaviour.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct XmrigTuning {
+    /// Overrides the thread count derived from the mining mode cpu usage
+    pub threads: Option<u32>,
+    /// Hex mask of the cores threads are pinned to, e.g. "0xF"
+    pub cpu_affinity: Option<String>,
+    pub huge_pages_1gb: bool,
+    pub msr_enabled: bool,
+    pub randomx_mode: RandomXMode,
+    pub donate_level: u8,
+    /// Full xmrig JSON config. Its pools, http and log-file settings are replaced by ours. The
+    /// options above, and the mining mode thread count, only fill in the `cpu`, `randomx` and
+    /// `donate-level` settings the file leaves unset.
+    pub config_file: Option<PathBuf>,
+}
+
+impl Default for XmrigTuning {
+    fn default() -> Self {
+        Self {
+            threads: None,
+            cpu_affinity: None,
+            huge_pages_1gb: false,
+            msr_enabled: true,
+            randomx_mode: RandomXMode::Auto,
+            donate_level: DEFAULT_DONATE_LEVEL,
+            config_file: None,
+        }
+    }
+}
+
+impl XmrigTuning {
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.threads == Some(0) {
+            return Err(anyhow::anyhow!("Thread count must be greater than zero"));
+        }
+        if let Some(affinity) = &self.cpu_affinity {
+            parse_affinity_mask(affinity)?;
+        }
+        if self.donate_level > MAX_DONATE_LEVEL {
+            return Err(anyhow::anyhow!(
+                "Donate level must be between 0 and {MAX_DONATE_LEVEL}"
+            ));
+        }
+        if let Some(config_file) = &self.config_file {
+            read_config_file(config_file)?;
+        }
+        Ok(())
+    }
+
+    /// Config file counterpart of `args`, for the settings missing from a user supplied config
+    fn fill_config(&self, config: &mut Value, threads: Option<u32>) -> Result<(), Error> {
+        let object = config
+            .as_object_mut()
+            .ok_or_else(|| anyhow::anyhow!("xmrig config must be a JSON object"))?;
+        object
+            .entry("donate-level")
+            .or_insert_with(|| json!(self.donate_level));
+        if let Some(threads) = threads {
+            // Same as xmrig's own --threads/--cpu-affinity transform: a profile for every algorithm
+            let profile = match &self.cpu_affinity {
+                Some(affinity) => json!({
+                    "threads": threads,
+                    "affinity": parse_affinity_mask(affinity)?,
+                }),
+                None => json!(threads),
+            };
+            insert_if_absent(object, "cpu", "*", profile)?;
+        }
+        if self.huge_pages_1gb {
+            insert_if_absent(object, "randomx", "1gb-pages", json!(true))?;
+        }
+        if !self.msr_enabled {
+            insert_if_absent(object, "randomx", "wrmsr", json!(false))?;
+        }
+        if self.randomx_mode != RandomXMode::Auto {
+            insert_if_absent(
+                object,
+                "randomx",
+                "mode",
+                json!(self.randomx_mode.to_string()),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn args(&self) -> Vec<String> {
+        let mut args = vec![format!("--donate-level={}", self.donate_level)];
+        if let Some(affinity) = &self.cpu_affinity {
+            args.push(format!("--cpu-affinity={affinity}"));
+        }
+        if self.huge_pages_1gb {
+            args.push("--randomx-1gb-pages".to_string());
+        }
+        if !self.msr_enabled {
+            // -1 tells xmrig to skip the MSR mod entirely
+            args.push("--randomx-wrmsr=-1".to_string());
+        }
+        if self.randomx_mode != RandomXMode::Auto {
+            args.push(format!("--randomx-mode={}", self.randomx_mode));
+        }
+        args
+    }
+}
+
+fn parse_affinity_mask(affinity: &str) -> Result<u64, Error> {
+    let digits = affinity
+        .strip_prefix("0x")
+        .or_else(|| affinity.strip_prefix("0X"))
+        .unwrap_or(affinity);
+    match u64::from_str_radix(digits, 16) {
+        Ok(0) | Err(_) => Err(anyhow::anyhow!(
+            "Invalid CPU affinity mask {affinity}, expected a non zero hex mask like 0xF"
+        )),
+        Ok(mask) => Ok(mask),
+    }
+}
+
+fn insert_if_absent(
+    object: &mut Map<String, Value>,
+    section: &str,
+    key: &str,
+    value: Value,
+) -> Result<(), Error> {
+    object
+        .entry(section)
+        .or_insert_with(|| json!({}))
+        .as_object_mut()
+        .ok_or_else(|| anyhow::anyhow!("`{section}` in the xmrig config file must be an object"))?
+        .entry(key)
+        .or_insert(value);
+    Ok(())
+}
+
+fn read_config_file(path: &Path) -> Result<Value, Error> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Could not read xmrig config file {}: {e}", path.display()))?;
+    let config: Value = serde_json::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("xmrig config file is not valid JSON: {e}"))?;
+    if !config.is_object() {
+        return Err(anyhow::anyhow!(
+            "xmrig config file must contain a JSON object"
+        ));
+    }
+    Ok(config)
+}
+
+fn node_connection_pool(node_connection: &CpuMinerNodeConnection) -> Value {
+    match node_connection {
+        CpuMinerNodeConnection::LocalMmproxy {
+            host_name,
+            port,
+            monero_address,
+        } => json!({
+            "url": format!("{host_name}:{port}"),
+            "user": monero_address,
+            "coin": "monero",
+            "daemon": true,
+        }),
+        CpuMinerNodeConnection::Pool {
+            host_name,
+            port,
+            tari_address,
+        } => json!({
+            "url": format!("{host_name}:{port}"),
+            "user": tari_address,
+            "coin": "monero",
+        }),
+        CpuMinerNodeConnection::MergeMinedPool {
+            host_name,
+            port,
+            monero_address,
+            tari_address,
+        } => json!({
+            "url": format!("{host_name}:{port}"),
+            "user": format!("{monero_address}:{tari_address}"),
+            "coin": "monero",
+        }),
+    }
+}
+
+/// Replaces the settings Universe depends on in a user supplied xmrig config
+fn merge_config(
+    mut config: Value,
+    node_connection: &CpuMinerNodeConnection,
+    http_api_port: u16,
+    http_api_token: &str,
+    log_file: &Path,
+) -> Result<Value, Error> {
+    let object = config
+        .as_object_mut()
+        .ok_or_else(|| anyhow::anyhow!("xmrig config must be a JSON object"))?;
+    object.insert(
+        "pools".to_string(),
+        json!([node_connection_pool(node_connection)]),
+    );
+    object.insert(
+        "http".to_string(),
+        json!({
+            "enabled": true,
+            "host": "127.0.0.1",
+            "port": http_api_port,
+            "access-token": http_api_token,
+            "restricted": true,
+        }),
+    );
+    object.insert("log-file".to_string(), json!(log_file.to_string_lossy()));
+    if matches!(node_connection, CpuMinerNodeConnection::LocalMmproxy { .. }) {
+        // We are using a local daemon, so retry as soon as possible
+        object.insert("retry-pause".to_string(), json!(1));
+    }
+    Ok(config)
+}
+
+/// xmrig arguments for the given connection
+fn node_connection_args(node_connection: &CpuMinerNodeConnection) -> Vec<String> {
+    match node_connection {
+        CpuMinerNodeConnection::LocalMmproxy {
+            host_name,
+            port,
+            monero_address,
+        } => {
+            vec![
+                "--daemon".to_string(),
+                format!("--url={}:{}", host_name, port),
+                // "--daemon-poll-interval=10000".to_string(),
+                "--coin=monero".to_string(),
+                // We are using a local daemon, so retry as soon as possible
+                "--retry-pause=1".to_string(),
+                "--user".to_string(),
+                format!("{}", monero_address),
+            ]
+        }
+        CpuMinerNodeConnection::Pool {
+            host_name,
+            port,
+            tari_address: monero_address,
+        } => {
+            vec![
+                "--url".to_string(),
+                format!("{}:{}", host_name, port),
+                "--coin=monero".to_string(),
+                "--user".to_string(),
+                format!("{}", monero_address),
+            ]
+        }
+        CpuMinerNodeConnection::MergeMinedPool {
+            host_name,
+            port,
+            monero_address,
+            tari_address,
+        } => {
+            vec![
+                "--url".to_string(),
+                format!("{}:{}", host_name, port),
+                "--coin=monero".to_string(),
+                "--user".to_string(),
+                format!("{}:{}", monero_address, tari_address),
+            ]
         }
     }
 }
 
 pub struct XmrigAdapter {
-    pub node_connection: Option<XmrigNodeConnection>,
+    pub node_connection: Option<CpuMinerNodeConnection>,
     // pub monero_address: Option<String>,
     pub http_api_token: String,
     pub http_api_port: u16,
     pub cpu_threads: Option<u32>,
+    pub tuning: XmrigTuning,
     pub extra_options: Vec<String>,
-    pub summary_broadcast: watch::Sender<Option<Summary>>,
+    pub summary_broadcast: watch::Sender<Option<CpuMinerSummary>>,
 }
 
 impl XmrigAdapter {
-    pub fn new(summary_broadcast: watch::Sender<Option<Summary>>) -> Self {
+    pub fn new(summary_broadcast: watch::Sender<Option<CpuMinerSummary>>) -> Self {
         let http_api_port = PortAllocator::new().assign_port_with_fallback();
         let http_api_token = "pass".to_string();
         Self {
@@ -127,10 +347,64 @@ impl XmrigAdapter {
             http_api_token: http_api_token.clone(),
             http_api_port,
             cpu_threads: None,
+            tuning: XmrigTuning::default(),
             extra_options: Vec::new(),
             summary_broadcast,
         }
     }
+
+    fn command_line_args(
+        &self,
+        node_connection: &CpuMinerNodeConnection,
+        xmrig_log_file: &Path,
+    ) -> Vec<String> {
+        let mut args = node_connection_args(node_connection);
+
+        match xmrig_log_file.to_str() {
+            Some(log_file) => {
+                args.push(format!("--log-file={}", &log_file));
+            }
+            None => {
+                warn!(target: LOG_TARGET, "Could not convert xmrig log file path to string");
+                warn!(target: LOG_TARGET, "Logs argument will not be added to xmrig");
+            }
+        };
+
+        args.push(format!("--http-port={}", self.http_api_port));
+        args.push(format!("--http-access-token={}", self.http_api_token));
+        args.extend(self.tuning.args());
+
+        // don't specify threads for ludicrous mode
+        if let Some(cpu_threads) = self.tuning.threads.or(self.cpu_threads) {
+            args.push(format!("--threads={cpu_threads}"));
+        }
+        args.push("--verbose".to_string());
+        args
+    }
+
+    fn write_merged_config(
+        &self,
+        config_file: &Path,
+        node_connection: &CpuMinerNodeConnection,
+        xmrig_log_file: &Path,
+        config_dir: &Path,
+    ) -> Result<PathBuf, Error> {
+        let mut config = merge_config(
+            read_config_file(config_file)?,
+            node_connection,
+            self.http_api_port,
+            &self.http_api_token,
+            xmrig_log_file,
+        )?;
+        self.tuning
+            .fill_config(&mut config, self.tuning.threads.or(self.cpu_threads))?;
+        let merged_config_file = config_dir.join("xmrig").join("config.json");
+        if let Some(parent) = merged_config_file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&merged_config_file, serde_json::to_string_pretty(&config)?)?;
+        Ok(merged_config_file)
+    }
 }
 
 impl ProcessAdapter for XmrigAdapter {
@@ -140,17 +414,16 @@ impl ProcessAdapter for XmrigAdapter {
     fn spawn_inner(
         &self,
         data_dir: PathBuf,
-        _config_dir: PathBuf,
+        config_dir: PathBuf,
         log_dir: PathBuf,
         binary_version_path: PathBuf,
         _is_first_start: bool,
     ) -> Result<(ProcessInstance, Self::StatusMonitor), anyhow::Error> {
         let xmrig_shutdown = Shutdown::new();
-        let mut args = self
+        let node_connection = self
             .node_connection
             .as_ref()
-            .ok_or(anyhow::anyhow!("Node connection not set"))?
-            .generate_args();
+            .ok_or(anyhow::anyhow!("Node connection not set"))?;
         let xmrig_log_file = log_dir.join("xmrig").join("xmrig.log");
         std::fs::create_dir_all(
             xmrig_log_file
@@ -158,33 +431,19 @@ impl ProcessAdapter for XmrigAdapter {
                 .expect("Could not get xmrig root log dir"),
         )?;
 
-        let xmrig_log_file_parent = xmrig_log_file
-            .parent()
-            .ok_or_else(|| anyhow::anyhow!("Could not get parent directory of xmrig log file"))?;
-
-        match xmrig_log_file.to_str() {
-            Some(log_file) => {
-                args.push(format!("--log-file={}", &log_file));
-            }
-            None => {
-                warn!(target: LOG_TARGET, "Could not convert xmrig log file path to string");
-                warn!(target: LOG_TARGET, "Logs argument will not be added to xmrig");
+        let mut args = match &self.tuning.config_file {
+            Some(config_file) => {
+                let merged_config_file = self.write_merged_config(
+                    config_file,
+                    node_connection,
+                    &xmrig_log_file,
+                    &config_dir,
+                )?;
+                info!(target: LOG_TARGET, "Starting xmrig with config file {}", merged_config_file.display());
+                vec![format!("--config={}", merged_config_file.display())]
             }
+            None => self.command_line_args(node_connection, &xmrig_log_file),
         };
-
-        std::fs::create_dir_all(xmrig_log_file_parent).unwrap_or_else(|error| {
-            warn!(target: LOG_TARGET, "Could not create xmrig log file parent directory - {error}");
-        });
-
-        args.push(format!("--http-port={}", self.http_api_port));
-        args.push(format!("--http-access-token={}", self.http_api_token));
-        args.push("--donate-level=1".to_string());
-
-        // don't specify threads for ludicrous mode
-        if let Some(cpu_threads) = self.cpu_threads {
-            args.push(format!("--threads={cpu_threads}"));
-        }
-        args.push("--verbose".to_string());
         for extra_option in &self.extra_options {
             args.push(extra_option.clone());
         }
@@ -224,7 +483,7 @@ impl ProcessAdapter for XmrigAdapter {
 #[derive(Clone)]
 pub struct XmrigStatusMonitor {
     client: XmrigHttpApiClient,
-    summary_broadcast: watch::Sender<Option<Summary>>,
+    summary_broadcast: watch::Sender<Option<CpuMinerSummary>>,
 }
 
 #[async_trait]
@@ -233,7 +492,7 @@ impl StatusMonitor for XmrigStatusMonitor {
         match tokio::time::timeout(timeout_duration, self.summary()).await {
             Ok(summary_result) => match summary_result {
                 Ok(s) => {
-                    let _result = self.summary_broadcast.send(Some(s));
+                    let _result = self.summary_broadcast.send(Some(CpuMinerSummary::from(s)));
                     HealthStatus::Healthy
                 }
                 Err(e) => {
@@ -256,3 +515,90 @@ impl XmrigStatusMonitor {
         self.client.summary().await
     }
 }
+
+impl From<Summary> for CpuMinerSummary {
+    fn from(summary: Summary) -> Self {
+        let results = summary.results.unwrap_or_default();
+        Self {
+            hash_rate: summary
+                .hashrate
+                .total
+                .first()
+                .copied()
+                .flatten()
+                .unwrap_or_default(),
+            is_connected: summary.connection.uptime > 0,
+            shares_good: results.shares_good,
+            shares_total: results.shares_total,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn merge_config_keeps_user_settings_and_replaces_connection() {
+        let user_config = json!({
+            "cpu": { "huge-pages-jit": true },
+            "pools": [{ "url": "pool.example.com:3333" }],
+            "http": { "enabled": false },
+        });
+        let node_connection = CpuMinerNodeConnection::LocalMmproxy {
+            host_name: "127.0.0.1".to_string(),
+            port: 18081,
+            monero_address: "monero_address".to_string(),
+        };
+
+        let merged = merge_config(
+            user_config,
+            &node_connection,
+            18000,
+            "token",
+            Path::new("xmrig.log"),
+        )
+        .unwrap();
+
+        assert_eq!(merged["cpu"]["huge-pages-jit"], json!(true));
+        assert_eq!(merged["pools"][0]["url"], json!("127.0.0.1:18081"));
+        assert_eq!(merged["pools"][0]["daemon"], json!(true));
+        assert_eq!(merged["http"]["port"], json!(18000));
+        assert_eq!(merged["retry-pause"], json!(1));
+    }
+
+    #[test]
+    fn tuning_only_fills_settings_missing_from_config_file() {
+        let tuning = XmrigTuning {
+            cpu_affinity: Some("0x3".to_string()),
+            huge_pages_1gb: true,
+            msr_enabled: false,
+            randomx_mode: RandomXMode::Light,
+            donate_level: 5,
+            ..XmrigTuning::default()
+        };
+        let mut config = json!({
+            "donate-level": 0,
+            "randomx": { "mode": "fast" },
+        });
+
+        tuning.fill_config(&mut config, Some(4)).unwrap();
+
+        assert_eq!(config["donate-level"], json!(0));
+        assert_eq!(config["randomx"]["mode"], json!("fast"));
+        assert_eq!(config["randomx"]["1gb-pages"], json!(true));
+        assert_eq!(config["randomx"]["wrmsr"], json!(false));
+        assert_eq!(config["cpu"]["*"], json!({ "threads": 4, "affinity": 3 }));
+        assert!(tuning
+            .fill_config(&mut json!({ "cpu": true }), Some(4))
+            .is_err());
+    }
+
+    #[test]
+    fn affinity_mask_must_be_non_zero_hex() {
+        assert_eq!(parse_affinity_mask("0xF").unwrap(), 15);
+        assert!(parse_affinity_mask("0x0").is_err());
+        assert!(parse_affinity_mask("cores").is_err());
+    }
+}